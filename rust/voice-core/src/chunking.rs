@@ -0,0 +1,76 @@
+//! Content-defined chunking for note bodies, so `chunks` can store content addressed by
+//! hash and sync only transmits the hashes a peer is actually missing (see
+//! [`crate::database::Database::store_content_chunks`] and the `/sync/chunks` endpoint
+//! in [`crate::sync_server`]). Boundaries are picked with a Gear-hash rolling checksum
+//! (the same building block FastCDC uses) rather than a fixed block size, so inserting or
+//! deleting a few bytes in the middle of a note only reshuffles the chunk(s) touching the
+//! edit - everything before and after the edit re-chunks identically.
+
+use std::sync::OnceLock;
+
+use sha2::{Digest, Sha256};
+
+/// Chunks smaller than this are never cut early, even if the rolling hash says to -
+/// keeps pathological inputs (e.g. repetitive text) from producing tiny chunks.
+pub const MIN_CHUNK_SIZE: usize = 2 * 1024;
+/// A chunk is always cut at this size even if no boundary hash has matched yet.
+pub const MAX_CHUNK_SIZE: usize = 8 * 1024;
+/// Target average chunk size; the boundary mask is derived from it.
+const AVG_CHUNK_SIZE: usize = 4 * 1024;
+const BOUNDARY_MASK: u64 = (AVG_CHUNK_SIZE - 1) as u64;
+
+/// Deterministic per-byte-value table for the Gear rolling hash, generated once with a
+/// splitmix64 sequence rather than pulled from a `rand` dependency this crate doesn't
+/// otherwise need.
+fn gear_table() -> &'static [u64; 256] {
+    static TABLE: OnceLock<[u64; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0u64; 256];
+        let mut seed: u64 = 0x9E3779B97F4A7C15;
+        for entry in table.iter_mut() {
+            seed = seed.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^= z >> 31;
+            *entry = z;
+        }
+        table
+    })
+}
+
+/// Split `data` into content-defined chunks of [`MIN_CHUNK_SIZE`] to [`MAX_CHUNK_SIZE`]
+/// bytes. A boundary falls wherever the rolling Gear hash's bits under [`BOUNDARY_MASK`]
+/// are all zero, which - because those bits only depend on the trailing window of
+/// bytes - lands in the same place on both sides of an edit as long as that edit doesn't
+/// touch the window itself.
+pub fn chunk_bytes(data: &[u8]) -> Vec<&[u8]> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let table = gear_table();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut hash: u64 = 0;
+
+    for (i, &byte) in data.iter().enumerate() {
+        hash = (hash << 1).wrapping_add(table[byte as usize]);
+        let len = i + 1 - start;
+        let at_hash_boundary = len >= MIN_CHUNK_SIZE && (hash & BOUNDARY_MASK) == 0;
+        if at_hash_boundary || len == MAX_CHUNK_SIZE || i == data.len() - 1 {
+            chunks.push(&data[start..=i]);
+            start = i + 1;
+            hash = 0;
+        }
+    }
+
+    chunks
+}
+
+/// Content-address a chunk by its SHA-256 hash.
+pub fn chunk_hash(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
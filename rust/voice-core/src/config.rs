@@ -14,7 +14,10 @@ use std::path::{Path, PathBuf};
 
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::error::{VoiceError, VoiceResult};
@@ -76,7 +79,74 @@ pub struct PeerConfig {
     pub peer_id: String,
     pub peer_name: String,
     pub peer_url: String,
-    pub certificate_fingerprint: Option<String>,
+    /// Trusted certificate fingerprints, newest first. Holding more than one lets a
+    /// peer rotate its certificate (via `tls::rotate_peer_fingerprint`) without
+    /// re-triggering TOFU, while still rejecting any fingerprint that was never
+    /// presented or vouched for.
+    #[serde(default)]
+    pub certificate_fingerprints: Vec<String>,
+    /// The peer's Ed25519 public key (hex-encoded), used by [`Config::verify_peer`]
+    /// to authenticate signed sync envelopes. `None` until exchanged, e.g. during
+    /// pairing or the first handshake.
+    #[serde(default)]
+    pub public_key: Option<String>,
+    /// Explicit trust lifecycle for this peer's certificate pinning. See
+    /// [`Config::peer_trust_conflict`].
+    #[serde(default)]
+    pub trust_state: TrustState,
+    /// The fingerprint currently pinned and accepted for this peer. `None` until
+    /// first established, e.g. via [`Config::observe_peer_fingerprint`]'s TOFU pin.
+    #[serde(default)]
+    pub pinned_fingerprint: Option<String>,
+    /// Set when a connecting peer presents a fingerprint that doesn't match
+    /// `pinned_fingerprint`, holding the new one pending the user's decision. Cleared
+    /// by [`Config::confirm_peer_fingerprint`] or [`Config::revoke_peer`].
+    #[serde(default)]
+    pub conflicting_fingerprint: Option<String>,
+}
+
+/// Explicit lifecycle for a peer's certificate trust, so a fingerprint change is
+/// surfaced for confirmation rather than [`Config::update_peer_certificate`]'s blind
+/// overwrite silently accepting what could be a MITM'd certificate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustState {
+    /// Awaiting user confirmation: never connected yet, or its presented
+    /// fingerprint changed and hasn't been confirmed or rejected (see
+    /// [`Config::peer_trust_conflict`]).
+    Pending,
+    /// Connections presenting `pinned_fingerprint` are accepted.
+    Trusted,
+    /// Explicitly rejected; all connections refused until the peer is re-paired.
+    Revoked,
+}
+
+impl Default for TrustState {
+    /// Peers persisted before this field existed already had a fingerprint pinned
+    /// through the old flow, so they deserialize as already trusted rather than
+    /// retroactively demanding reconfirmation.
+    fn default() -> Self {
+        TrustState::Trusted
+    }
+}
+
+impl TrustState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrustState::Pending => "pending",
+            TrustState::Trusted => "trusted",
+            TrustState::Revoked => "revoked",
+        }
+    }
+}
+
+/// Both sides of an unresolved certificate change for a peer, surfaced by
+/// [`Config::peer_trust_conflict`] so the user can [`Config::confirm_peer_fingerprint`]
+/// or [`Config::revoke_peer`].
+#[derive(Debug, Clone)]
+pub struct PeerTrustConflict {
+    pub pinned_fingerprint: Option<String>,
+    pub presented_fingerprint: String,
 }
 
 /// Sync configuration
@@ -88,18 +158,179 @@ pub struct SyncConfig {
     pub server_port: u16,
     #[serde(default)]
     pub peers: Vec<PeerConfig>,
+    /// ACME provisioning settings, used instead of self-signed + TOFU when enabled
+    #[serde(default)]
+    pub acme: Option<crate::acme::AcmeConfig>,
 }
 
 fn default_server_port() -> u16 {
     8384
 }
 
+/// Format version of [`Config::generate_pairing_token`]'s wire layout. Bump this and
+/// branch on it in [`PairingToken::decode`] if the layout ever needs to change.
+const PAIRING_TOKEN_VERSION: u8 = 1;
+
+/// The decoded contents of a pairing token produced by
+/// [`Config::generate_pairing_token`], consumed by [`Config::accept_pairing_token`].
+///
+/// Wire layout (before base58): `version(1)` ‖ `device_id(16)` ‖ `server_port(2, BE)`
+/// ‖ `cert_fingerprint(32, SHA-256)` ‖ `device_name(1 + N, length-prefixed UTF-8,
+/// optional)` ‖ `crc32(4, BE, over everything preceding it)` — an innernet/spacedrive
+/// style "copy one string" out-of-band pairing flow, modeled so the cert pin is
+/// established before any data flows rather than typed in by hand.
+struct PairingToken {
+    device_id: Uuid,
+    server_port: u16,
+    cert_fingerprint: [u8; 32],
+    device_name: Option<String>,
+}
+
+impl PairingToken {
+    fn encode(&self) -> String {
+        let mut buf = Vec::with_capacity(1 + 16 + 2 + 32 + 4);
+        buf.push(PAIRING_TOKEN_VERSION);
+        buf.extend_from_slice(self.device_id.as_bytes());
+        buf.extend_from_slice(&self.server_port.to_be_bytes());
+        buf.extend_from_slice(&self.cert_fingerprint);
+        if let Some(name) = &self.device_name {
+            let name_bytes = &name.as_bytes()[..name.len().min(255)];
+            buf.push(name_bytes.len() as u8);
+            buf.extend_from_slice(name_bytes);
+        }
+        let checksum = crc32fast::hash(&buf);
+        buf.extend_from_slice(&checksum.to_be_bytes());
+        bs58::encode(buf).into_string()
+    }
+
+    fn decode(token: &str) -> VoiceResult<Self> {
+        const HEADER_LEN: usize = 1 + 16 + 2 + 32;
+
+        let buf = bs58::decode(token)
+            .into_vec()
+            .map_err(|e| VoiceError::validation("pairing_token", format!("not valid base58: {e}")))?;
+        if buf.len() < HEADER_LEN + 4 {
+            return Err(VoiceError::validation("pairing_token", "too short to be a pairing token"));
+        }
+
+        let (body, checksum_bytes) = buf.split_at(buf.len() - 4);
+        let expected = u32::from_be_bytes(checksum_bytes.try_into().expect("split_at(len-4) yields 4 bytes"));
+        if crc32fast::hash(body) != expected {
+            return Err(VoiceError::validation("pairing_token", "checksum mismatch - token is corrupt or truncated"));
+        }
+
+        let version = body[0];
+        if version != PAIRING_TOKEN_VERSION {
+            return Err(VoiceError::validation("pairing_token", format!("unsupported token version {version}")));
+        }
+
+        let device_id = Uuid::from_slice(&body[1..17])
+            .map_err(|e| VoiceError::validation("pairing_token", format!("invalid device id: {e}")))?;
+        let server_port = u16::from_be_bytes([body[17], body[18]]);
+        let mut cert_fingerprint = [0u8; 32];
+        cert_fingerprint.copy_from_slice(&body[19..HEADER_LEN]);
+
+        let device_name = if body.len() > HEADER_LEN {
+            let name_len = body[HEADER_LEN] as usize;
+            let name_start = HEADER_LEN + 1;
+            let name_end = name_start + name_len;
+            let name_bytes = body
+                .get(name_start..name_end)
+                .ok_or_else(|| VoiceError::validation("pairing_token", "truncated device name"))?;
+            Some(
+                String::from_utf8(name_bytes.to_vec())
+                    .map_err(|e| VoiceError::validation("pairing_token", format!("invalid device name: {e}")))?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Self {
+            device_id,
+            server_port,
+            cert_fingerprint,
+            device_name,
+        })
+    }
+}
+
+/// This device's Ed25519 signing identity, used as the sync peer identity so
+/// envelopes can be authenticated rather than trusting a bare `device_id` (see
+/// [`Config::sign`]/[`Config::verify_peer`]). Generated on first use and stored
+/// at `certs_dir()/identity.pkcs8` with `0600` permissions; the private key never
+/// touches `config.json`. Mirrors `acme::AccountKey`'s load-or-create pattern.
+struct DeviceIdentity {
+    key_pair: Ed25519KeyPair,
+}
+
+impl DeviceIdentity {
+    fn load_or_create(path: &Path) -> VoiceResult<Self> {
+        let rng = SystemRandom::new();
+        let pkcs8 = if path.exists() {
+            fs::read(path)?
+        } else {
+            let doc = Ed25519KeyPair::generate_pkcs8(&rng)
+                .map_err(|e| VoiceError::Config(format!("failed to generate identity keypair: {}", e)))?;
+            let bytes = doc.as_ref().to_vec();
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, &bytes)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+            }
+            bytes
+        };
+
+        let key_pair = Ed25519KeyPair::from_pkcs8(&pkcs8)
+            .map_err(|e| VoiceError::Config(format!("invalid identity keypair: {}", e)))?;
+
+        Ok(Self { key_pair })
+    }
+}
+
+fn hex_nibble(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decode a hex string (either case) into bytes, rejecting anything malformed (odd
+/// length, non-hex digits) rather than silently truncating. Works over raw bytes rather
+/// than slicing the `&str` by character count, so non-ASCII input is rejected instead of
+/// panicking on a byte index that splits a multi-byte UTF-8 character.
+pub(crate) fn hex_decode(s: &str) -> Result<Vec<u8>, String> {
+    let bytes = s.as_bytes();
+    if bytes.len() % 2 != 0 {
+        return Err("odd-length hex string".to_string());
+    }
+    bytes
+        .chunks(2)
+        .map(|pair| {
+            let hi = hex_nibble(pair[0]).ok_or_else(|| format!("invalid hex digit: {}", pair[0] as char))?;
+            let lo = hex_nibble(pair[1]).ok_or_else(|| format!("invalid hex digit: {}", pair[1] as char))?;
+            Ok((hi << 4) | lo)
+        })
+        .collect()
+}
+
+/// Hex-encode `bytes` as lowercase hex, e.g. for a signature sent over the sync wire.
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 impl Default for SyncConfig {
     fn default() -> Self {
         Self {
             enabled: false,
             server_port: default_server_port(),
             peers: Vec::new(),
+            acme: None,
         }
     }
 }
@@ -131,6 +362,11 @@ pub struct ConfigData {
     pub sync: SyncConfig,
     /// Server certificate fingerprint
     pub server_certificate_fingerprint: Option<String>,
+    /// On-disk schema version. Missing (pre-versioning) files deserialize as `0`; see
+    /// [`migrate_and_parse`] for how `Config::new` walks a file forward to
+    /// [`CONFIG_VERSION`] before this is ever read back out.
+    #[serde(default)]
+    pub config_version: u32,
 }
 
 fn generate_device_id() -> String {
@@ -156,11 +392,62 @@ impl Default for ConfigData {
             device_name: get_default_device_name(),
             sync: SyncConfig::default(),
             server_certificate_fingerprint: None,
+            config_version: CONFIG_VERSION,
         }
     }
 }
 
+/// Current on-disk `ConfigData` schema version. Bump this and append a migration to
+/// [`migrations`] whenever the shape changes in a way that isn't just a new
+/// `#[serde(default)]` field (those already deserialize fine against older files).
+const CONFIG_VERSION: u32 = 1;
+
+/// Ordered schema migrations, indexed by the version they migrate *from* — entry `0`
+/// migrates version 0 to 1, entry `1` migrates version 1 to 2, and so on. Each closure
+/// transforms a parsed config [`serde_json::Value`] in place.
+///
+/// Empty for now: `config_version` was only just introduced, and a version-0 file
+/// already deserializes as version 1 unchanged. Because entries are indexed by their
+/// source version, a 1-to-2 migration goes at entry `1`, after a no-op entry `0`.
+fn migrations() -> Vec<fn(&mut serde_json::Value)> {
+    Vec::new()
+}
+
+/// Parse `content` into a live [`ConfigData`], migrating forward from whatever
+/// `config_version` it was written with (a missing field reads as `0`, i.e. written
+/// before versioning existed) before deserializing the final shape. This replaces a
+/// bare `serde_json::from_str`, which would otherwise need the *current* shape to
+/// match exactly and would reject anything written by a migration that hasn't run yet.
+fn migrate_and_parse(content: &str) -> VoiceResult<ConfigData> {
+    let mut value: serde_json::Value = serde_json::from_str(content)?;
+    let mut version = value.get("config_version").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+
+    for migration in migrations().into_iter().skip(version) {
+        migration(&mut value);
+        version += 1;
+    }
+
+    if let Some(object) = value.as_object_mut() {
+        object.insert("config_version".to_string(), serde_json::Value::from(CONFIG_VERSION));
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Copy a config file that failed to load to `config.json.bak-<timestamp>` in the same
+/// directory, so a corrupt or unrecognized file is preserved for inspection rather than
+/// silently discarded.
+fn backup_unreadable_config(config_file: &Path) -> VoiceResult<PathBuf> {
+    let backup_path = config_file.with_file_name(format!(
+        "config.json.bak-{}",
+        chrono::Utc::now().format("%Y%m%dT%H%M%SZ")
+    ));
+    fs::copy(config_file, &backup_path)?;
+    Ok(backup_path)
+}
+
 /// Configuration manager
+#[derive(Clone)]
 pub struct Config {
     config_dir: PathBuf,
     config_file: PathBuf,
@@ -180,16 +467,16 @@ impl Config {
         let config_file = config_dir.join("config.json");
 
         let data = if config_file.exists() {
-            match fs::read_to_string(&config_file) {
-                Ok(content) => serde_json::from_str(&content).unwrap_or_else(|_| {
-                    let mut default = ConfigData::default();
-                    default.database_file = config_dir.join("notes.db").to_string_lossy().to_string();
-                    default
-                }),
-                Err(_) => {
-                    let mut default = ConfigData::default();
-                    default.database_file = config_dir.join("notes.db").to_string_lossy().to_string();
-                    default
+            let content = fs::read_to_string(&config_file)?;
+            match migrate_and_parse(&content) {
+                Ok(data) => data,
+                Err(e) => {
+                    let backup_path = backup_unreadable_config(&config_file)?;
+                    return Err(VoiceError::Config(format!(
+                        "config at {} could not be loaded ({e}); original preserved at {}",
+                        config_file.display(),
+                        backup_path.display()
+                    )));
                 }
             }
         } else {
@@ -212,10 +499,14 @@ impl Config {
         Ok(config)
     }
 
-    /// Save configuration to file
+    /// Save configuration to file. Writes to a temp file in `config_dir` first and
+    /// renames it over `config.json`, so a crash or power loss mid-write can't leave
+    /// behind a truncated, unparseable file.
     pub fn save(&self) -> VoiceResult<()> {
         let content = serde_json::to_string_pretty(&self.data)?;
-        fs::write(&self.config_file, content)?;
+        let tmp_path = self.config_dir.join("config.json.tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, &self.config_file)?;
         Ok(())
     }
 
@@ -305,14 +596,27 @@ impl Config {
             existing.peer_name = peer_name.to_string();
             existing.peer_url = peer_url.to_string();
             if let Some(fp) = certificate_fingerprint {
-                existing.certificate_fingerprint = Some(fp.to_string());
+                existing.certificate_fingerprints = vec![fp.to_string()];
+                existing.pinned_fingerprint = Some(fp.to_string());
+                existing.conflicting_fingerprint = None;
+                existing.trust_state = TrustState::Trusted;
             }
         } else {
             self.data.sync.peers.push(PeerConfig {
                 peer_id: peer_id.to_string(),
                 peer_name: peer_name.to_string(),
                 peer_url: peer_url.to_string(),
-                certificate_fingerprint: certificate_fingerprint.map(String::from),
+                certificate_fingerprints: certificate_fingerprint
+                    .map(|fp| vec![fp.to_string()])
+                    .unwrap_or_default(),
+                public_key: None,
+                trust_state: if certificate_fingerprint.is_some() {
+                    TrustState::Trusted
+                } else {
+                    TrustState::Pending
+                },
+                pinned_fingerprint: certificate_fingerprint.map(|fp| fp.to_string()),
+                conflicting_fingerprint: None,
             });
         }
 
@@ -335,10 +639,74 @@ impl Config {
         self.data.sync.peers.iter().find(|p| p.peer_id == peer_id)
     }
 
-    /// Update a peer's certificate fingerprint
+    /// Replace a peer's entire trusted fingerprint set with a single fingerprint.
+    ///
+    /// This is the blunt "trust only this one" override; to rotate a fingerprint
+    /// while keeping the old one valid during a grace period, use
+    /// [`Config::add_peer_fingerprint`] / `tls::rotate_peer_fingerprint` instead.
     pub fn update_peer_certificate(&mut self, peer_id: &str, fingerprint: &str) -> VoiceResult<bool> {
         if let Some(peer) = self.data.sync.peers.iter_mut().find(|p| p.peer_id == peer_id) {
-            peer.certificate_fingerprint = Some(fingerprint.to_string());
+            peer.certificate_fingerprints = vec![fingerprint.to_string()];
+            self.save()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Get a peer's trusted fingerprint set (newest first).
+    pub fn peer_fingerprints(&self, peer_id: &str) -> Option<&[String]> {
+        self.get_peer(peer_id).map(|p| p.certificate_fingerprints.as_slice())
+    }
+
+    /// Add a new trusted fingerprint to a peer's set without disturbing existing ones.
+    /// Returns `false` if the peer is unknown.
+    pub fn add_peer_fingerprint(&mut self, peer_id: &str, fingerprint: &str) -> VoiceResult<bool> {
+        if let Some(peer) = self.data.sync.peers.iter_mut().find(|p| p.peer_id == peer_id) {
+            if !peer
+                .certificate_fingerprints
+                .iter()
+                .any(|fp| fp.eq_ignore_ascii_case(fingerprint))
+            {
+                peer.certificate_fingerprints.insert(0, fingerprint.to_string());
+            }
+            self.save()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Retire a previously-trusted fingerprint, e.g. once a rotation's grace period
+    /// has elapsed. Refuses to remove a peer's last remaining fingerprint, since that
+    /// would leave it with nothing to TOFU-compare against.
+    pub fn retire_peer_fingerprint(&mut self, peer_id: &str, fingerprint: &str) -> VoiceResult<bool> {
+        if let Some(peer) = self.data.sync.peers.iter_mut().find(|p| p.peer_id == peer_id) {
+            if peer.certificate_fingerprints.len() <= 1 {
+                return Err(VoiceError::validation(
+                    "fingerprint",
+                    "cannot retire a peer's last remaining trusted fingerprint",
+                ));
+            }
+            let original_len = peer.certificate_fingerprints.len();
+            peer.certificate_fingerprints
+                .retain(|fp| !fp.eq_ignore_ascii_case(fingerprint));
+            let removed = peer.certificate_fingerprints.len() < original_len;
+            if removed {
+                self.save()?;
+            }
+            Ok(removed)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Set (or clear) a peer's registered Ed25519 public key (hex-encoded), used by
+    /// [`Config::verify_peer`] to authenticate signed sync envelopes. Returns `false`
+    /// if the peer is unknown.
+    pub fn set_peer_public_key(&mut self, peer_id: &str, public_key_hex: &str) -> VoiceResult<bool> {
+        if let Some(peer) = self.data.sync.peers.iter_mut().find(|p| p.peer_id == peer_id) {
+            peer.public_key = Some(public_key_hex.to_string());
             self.save()?;
             Ok(true)
         } else {
@@ -346,6 +714,72 @@ impl Config {
         }
     }
 
+    /// Record the fingerprint a peer presented on a connection attempt, pinning it on
+    /// first contact (TOFU) or, if it differs from what's already pinned, moving the
+    /// peer to [`TrustState::Pending`] and holding both fingerprints for the user to
+    /// resolve via [`Config::confirm_peer_fingerprint`] or [`Config::revoke_peer`] —
+    /// unlike [`Config::update_peer_certificate`]'s blind overwrite. Returns `false`
+    /// if the peer is unknown.
+    pub fn observe_peer_fingerprint(&mut self, peer_id: &str, fingerprint: &str) -> VoiceResult<bool> {
+        let Some(peer) = self.data.sync.peers.iter_mut().find(|p| p.peer_id == peer_id) else {
+            return Ok(false);
+        };
+
+        match &peer.pinned_fingerprint {
+            None => {
+                peer.pinned_fingerprint = Some(fingerprint.to_string());
+                peer.trust_state = TrustState::Trusted;
+            }
+            Some(pinned) if pinned.eq_ignore_ascii_case(fingerprint) => {}
+            Some(_) => {
+                peer.conflicting_fingerprint = Some(fingerprint.to_string());
+                peer.trust_state = TrustState::Pending;
+            }
+        }
+
+        self.save()?;
+        Ok(true)
+    }
+
+    /// The pinned and newly presented fingerprints for a peer currently awaiting
+    /// trust confirmation, or `None` if it isn't in conflict.
+    pub fn peer_trust_conflict(&self, peer_id: &str) -> Option<PeerTrustConflict> {
+        let peer = self.get_peer(peer_id)?;
+        if peer.trust_state != TrustState::Pending {
+            return None;
+        }
+        Some(PeerTrustConflict {
+            pinned_fingerprint: peer.pinned_fingerprint.clone(),
+            presented_fingerprint: peer.conflicting_fingerprint.clone()?,
+        })
+    }
+
+    /// Accept `fingerprint` as the peer's pinned fingerprint, e.g. after the user
+    /// confirms a [`Config::peer_trust_conflict`] was a legitimate certificate
+    /// rotation rather than a MITM attempt. Returns `false` if the peer is unknown.
+    pub fn confirm_peer_fingerprint(&mut self, peer_id: &str, fingerprint: &str) -> VoiceResult<bool> {
+        let Some(peer) = self.data.sync.peers.iter_mut().find(|p| p.peer_id == peer_id) else {
+            return Ok(false);
+        };
+        peer.pinned_fingerprint = Some(fingerprint.to_string());
+        peer.conflicting_fingerprint = None;
+        peer.trust_state = TrustState::Trusted;
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Reject a peer, e.g. after the user decides a [`Config::peer_trust_conflict`] was
+    /// a likely MITM attempt rather than a legitimate rotation. All future connections
+    /// are refused until the peer is re-paired. Returns `false` if the peer is unknown.
+    pub fn revoke_peer(&mut self, peer_id: &str) -> VoiceResult<bool> {
+        let Some(peer) = self.data.sync.peers.iter_mut().find(|p| p.peer_id == peer_id) else {
+            return Ok(false);
+        };
+        peer.trust_state = TrustState::Revoked;
+        self.save()?;
+        Ok(true)
+    }
+
     /// Get the certificates directory
     pub fn certs_dir(&self) -> VoiceResult<PathBuf> {
         let certs_dir = self.config_dir.join("certs");
@@ -353,6 +787,158 @@ impl Config {
         Ok(certs_dir)
     }
 
+    /// Build a compact, copy-pasteable pairing token embedding this device's ID, sync
+    /// port, and TLS certificate fingerprint (wire layout documented on [`PairingToken`]).
+    /// The peer calls [`Config::accept_pairing_token`] with it instead of hand-typing
+    /// a 32-hex `peer_id` and a certificate fingerprint.
+    pub fn generate_pairing_token(&self) -> VoiceResult<String> {
+        let cert_path = self.certs_dir()?.join("server.crt");
+        let cert_fingerprint = crate::tls::compute_fingerprint_raw(&cert_path)?;
+        let token = PairingToken {
+            device_id: self.device_id()?,
+            server_port: self.data.sync.server_port,
+            cert_fingerprint,
+            device_name: Some(self.data.device_name.clone()),
+        };
+        Ok(token.encode())
+    }
+
+    /// Decode a token from [`Config::generate_pairing_token`] and register the
+    /// originating device as a trusted peer, with its TLS fingerprint pre-filled from
+    /// the token rather than left to whatever certificate it happens to present on the
+    /// first connection — so trust-on-first-use has nothing left to blindly trust, and
+    /// a MITM presenting a different certificate is rejected outright. Returns the new
+    /// peer's `peer_id`.
+    ///
+    /// The token carries a port but not a routable host (addresses change too often to
+    /// bake in), so the peer is addressed by its device ID as an mDNS `.local`
+    /// hostname, the same host name [`crate::discovery`] advertises.
+    pub fn accept_pairing_token(&mut self, token: &str) -> VoiceResult<String> {
+        let decoded = PairingToken::decode(token)?;
+        let peer_id = decoded.device_id.simple().to_string();
+        let peer_name = decoded.device_name.clone().unwrap_or_else(|| peer_id.clone());
+        let peer_url = format!("https://{}.local:{}", peer_id, decoded.server_port);
+        let fingerprint_hex: Vec<String> = decoded.cert_fingerprint.iter().map(|b| format!("{:02x}", b)).collect();
+        let fingerprint = format!("SHA256:{}", fingerprint_hex.join(":"));
+
+        self.add_peer(&peer_id, &peer_name, &peer_url, Some(&fingerprint), true)?;
+        Ok(peer_id)
+    }
+
+    /// Path to this device's Ed25519 identity private key (PKCS#8, DER).
+    fn identity_key_path(&self) -> VoiceResult<PathBuf> {
+        Ok(self.certs_dir()?.join("identity.pkcs8"))
+    }
+
+    /// Load this device's signing identity, generating and persisting one on first use.
+    fn load_identity(&self) -> VoiceResult<DeviceIdentity> {
+        DeviceIdentity::load_or_create(&self.identity_key_path()?)
+    }
+
+    /// This device's Ed25519 public key, generating the identity keypair on first use.
+    pub fn identity_public_key(&self) -> VoiceResult<[u8; 32]> {
+        self.load_identity()?
+            .key_pair
+            .public_key()
+            .as_ref()
+            .try_into()
+            .map_err(|_| VoiceError::Config("unexpected Ed25519 public key length".to_string()))
+    }
+
+    /// Sign `message` with this device's identity private key, so a peer holding our
+    /// public key (see [`Config::identity_public_key`]) can authenticate it came from
+    /// us and wasn't tampered with in transit.
+    pub fn sign(&self, message: &[u8]) -> VoiceResult<[u8; 64]> {
+        self.load_identity()?
+            .key_pair
+            .sign(message)
+            .as_ref()
+            .try_into()
+            .map_err(|_| VoiceError::Config("unexpected Ed25519 signature length".to_string()))
+    }
+
+    /// Verify that `signature` over `message` was produced by `peer_id`'s registered
+    /// public key. `Ok(false)` (not an error) covers an unknown peer, a peer with no
+    /// registered key yet, or a signature that simply doesn't verify; only a
+    /// corrupt stored public key is surfaced as an error.
+    pub fn verify_peer(&self, peer_id: &str, message: &[u8], signature: &[u8]) -> VoiceResult<bool> {
+        let Some(peer) = self.get_peer(peer_id) else {
+            return Ok(false);
+        };
+        let Some(public_key_hex) = &peer.public_key else {
+            return Ok(false);
+        };
+        let public_key = hex_decode(public_key_hex)
+            .map_err(|e| VoiceError::Config(format!("invalid stored public key for peer {peer_id}: {e}")))?;
+        let verifying_key = UnparsedPublicKey::new(&ED25519, &public_key);
+        Ok(verifying_key.verify(message, signature).is_ok())
+    }
+
+    /// SHA-256 of this device's Ed25519 public key, hex-encoded — a self-certifying
+    /// identifier a peer can independently recompute from the public key it was handed,
+    /// rather than merely trusting the claimed ID. An opt-in alternative to the random
+    /// UUID7 [`Config::device_id_hex`] for callers that want identity and key bound
+    /// together, e.g. a pairing token or QR code.
+    pub fn identity_fingerprint(&self) -> VoiceResult<String> {
+        let public_key = self.identity_public_key()?;
+        let mut hasher = Sha256::new();
+        hasher.update(public_key);
+        Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Start advertising this device over mDNS and browsing for other devices on the
+    /// LAN (see the [`crate::discovery`] module). Only meaningful once sync is
+    /// actually reachable, so this is a no-op — not an error — when `sync.enabled`
+    /// is `false`.
+    pub fn start_discovery(&self) -> VoiceResult<()> {
+        if !self.data.sync.enabled {
+            return Ok(());
+        }
+        crate::discovery::start(self)
+    }
+
+    /// Stop mDNS advertising and browsing started by [`Config::start_discovery`].
+    pub fn stop_discovery(&self) {
+        crate::discovery::stop();
+    }
+
+    /// Devices seen advertising on the LAN that aren't yet in `sync.peers`.
+    pub fn discovered_peers(&self) -> Vec<crate::discovery::DiscoveredPeer> {
+        crate::discovery::discovered_peers()
+            .into_iter()
+            .filter(|peer| self.get_peer(&peer.device_id).is_none())
+            .collect()
+    }
+
+    /// Move a device found via [`Config::discovered_peers`] into the persisted peer
+    /// list, reusing its discovered URL and certificate fingerprint so the first TLS
+    /// connection can be verified trust-on-first-use without any manual entry.
+    /// Returns `false` if `device_id` hasn't been discovered.
+    pub fn promote_discovered_peer(&mut self, device_id: &str) -> VoiceResult<bool> {
+        let Some(peer) = crate::discovery::discovered_peer(device_id) else {
+            return Ok(false);
+        };
+        self.add_peer(
+            &peer.device_id,
+            &peer.device_name,
+            &peer.peer_url,
+            peer.certificate_fingerprint.as_deref(),
+            false,
+        )?;
+        Ok(true)
+    }
+
+    /// Get the ACME provisioning configuration, if set
+    pub fn acme_config(&self) -> Option<&crate::acme::AcmeConfig> {
+        self.data.sync.acme.as_ref()
+    }
+
+    /// Set (or clear) the ACME provisioning configuration
+    pub fn set_acme_config(&mut self, acme: Option<crate::acme::AcmeConfig>) -> VoiceResult<()> {
+        self.data.sync.acme = acme;
+        self.save()
+    }
+
     /// Get TUI colors
     pub fn tui_colors(&self) -> (&str, &str) {
         (
@@ -418,6 +1004,15 @@ pub struct PyConfig {
     inner: Config,
 }
 
+impl PyConfig {
+    /// Clone out the underlying [`Config`], e.g. to hand an owned copy to a background
+    /// thread (see [`crate::sync_server::py_start_sync_server`]) without tying its
+    /// lifetime to this Python object.
+    pub(crate) fn inner_clone(&self) -> Config {
+        self.inner.clone()
+    }
+}
+
 #[pymethods]
 impl PyConfig {
     #[new]
@@ -490,7 +1085,10 @@ impl PyConfig {
             dict.set_item("peer_id", &peer.peer_id)?;
             dict.set_item("peer_name", &peer.peer_name)?;
             dict.set_item("peer_url", &peer.peer_url)?;
-            dict.set_item("certificate_fingerprint", &peer.certificate_fingerprint)?;
+            dict.set_item("certificate_fingerprints", peer.certificate_fingerprints.clone())?;
+            dict.set_item("public_key", peer.public_key.clone())?;
+            dict.set_item("trust_state", peer.trust_state.as_str())?;
+            dict.set_item("pinned_fingerprint", peer.pinned_fingerprint.clone())?;
             list.append(dict)?;
         }
         Ok(list.into())
@@ -525,25 +1123,138 @@ impl PyConfig {
                 dict.set_item("peer_id", &peer.peer_id)?;
                 dict.set_item("peer_name", &peer.peer_name)?;
                 dict.set_item("peer_url", &peer.peer_url)?;
-                dict.set_item("certificate_fingerprint", &peer.certificate_fingerprint)?;
+                dict.set_item("certificate_fingerprints", peer.certificate_fingerprints.clone())?;
+                dict.set_item("trust_state", peer.trust_state.as_str())?;
+                dict.set_item("pinned_fingerprint", peer.pinned_fingerprint.clone())?;
                 Ok(Some(dict.into()))
             }
             None => Ok(None),
         }
     }
 
-    /// Update peer certificate
+    /// Update peer certificate (replaces the entire trusted fingerprint set)
     fn update_peer_certificate(&mut self, peer_id: &str, fingerprint: &str) -> PyResult<bool> {
         let updated = self.inner.update_peer_certificate(peer_id, fingerprint)?;
         Ok(updated)
     }
 
+    /// Add a fingerprint to a peer's trusted set without disturbing existing ones
+    fn add_peer_fingerprint(&mut self, peer_id: &str, fingerprint: &str) -> PyResult<bool> {
+        let added = self.inner.add_peer_fingerprint(peer_id, fingerprint)?;
+        Ok(added)
+    }
+
+    /// Retire a previously-trusted fingerprint from a peer's set
+    fn retire_peer_fingerprint(&mut self, peer_id: &str, fingerprint: &str) -> PyResult<bool> {
+        let removed = self.inner.retire_peer_fingerprint(peer_id, fingerprint)?;
+        Ok(removed)
+    }
+
+    /// Record the fingerprint a peer presented on a connection attempt, pinning it on
+    /// first contact or flagging a trust conflict if it changed
+    fn observe_peer_fingerprint(&mut self, peer_id: &str, fingerprint: &str) -> PyResult<bool> {
+        Ok(self.inner.observe_peer_fingerprint(peer_id, fingerprint)?)
+    }
+
+    /// Get a peer's unresolved certificate-change conflict, if any, as a dict with
+    /// `pinned_fingerprint` and `presented_fingerprint`
+    fn get_peer_trust_conflict(&self, py: Python<'_>, peer_id: &str) -> PyResult<Option<PyObject>> {
+        match self.inner.peer_trust_conflict(peer_id) {
+            Some(conflict) => {
+                let dict = PyDict::new(py);
+                dict.set_item("pinned_fingerprint", conflict.pinned_fingerprint)?;
+                dict.set_item("presented_fingerprint", conflict.presented_fingerprint)?;
+                Ok(Some(dict.into()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Confirm a peer's new fingerprint as legitimate, resolving its trust conflict
+    fn confirm_peer_fingerprint(&mut self, peer_id: &str, fingerprint: &str) -> PyResult<bool> {
+        Ok(self.inner.confirm_peer_fingerprint(peer_id, fingerprint)?)
+    }
+
+    /// Revoke a peer, refusing future connections until it's re-paired
+    fn revoke_peer(&mut self, peer_id: &str) -> PyResult<bool> {
+        Ok(self.inner.revoke_peer(peer_id)?)
+    }
+
     /// Get certificates directory
     fn get_certs_dir(&self) -> PyResult<String> {
         let path = self.inner.certs_dir()?;
         Ok(path.to_string_lossy().to_string())
     }
 
+    /// Generate a copy-pasteable pairing token for this device
+    fn generate_pairing_token(&self) -> PyResult<String> {
+        Ok(self.inner.generate_pairing_token()?)
+    }
+
+    /// Accept a pairing token from another device, registering it as a trusted peer
+    fn accept_pairing_token(&mut self, token: &str) -> PyResult<String> {
+        Ok(self.inner.accept_pairing_token(token)?)
+    }
+
+    /// Get this device's Ed25519 public key, hex-encoded
+    fn get_identity_public_key(&self) -> PyResult<String> {
+        let public_key = self.inner.identity_public_key()?;
+        Ok(public_key.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Sign a message with this device's identity private key, returning the
+    /// hex-encoded signature
+    fn sign(&self, message: &[u8]) -> PyResult<String> {
+        let signature = self.inner.sign(message)?;
+        Ok(hex_encode(&signature))
+    }
+
+    /// Verify a hex-encoded signature over a message against a peer's registered public key
+    fn verify_peer(&self, peer_id: &str, message: &[u8], signature_hex: &str) -> PyResult<bool> {
+        let signature = hex_decode(signature_hex)
+            .map_err(|e| crate::error::VoiceError::validation("signature", e))?;
+        Ok(self.inner.verify_peer(peer_id, message, &signature)?)
+    }
+
+    /// Set a peer's registered Ed25519 public key (hex-encoded)
+    fn set_peer_public_key(&mut self, peer_id: &str, public_key_hex: &str) -> PyResult<bool> {
+        Ok(self.inner.set_peer_public_key(peer_id, public_key_hex)?)
+    }
+
+    /// This device's self-certifying identity fingerprint (SHA-256 of its public key, hex-encoded)
+    fn get_identity_fingerprint(&self) -> PyResult<String> {
+        Ok(self.inner.identity_fingerprint()?)
+    }
+
+    /// Start advertising this device and browsing for peers on the LAN over mDNS
+    fn start_discovery(&self) -> PyResult<()> {
+        Ok(self.inner.start_discovery()?)
+    }
+
+    /// Stop mDNS advertising and browsing
+    fn stop_discovery(&self) {
+        self.inner.stop_discovery();
+    }
+
+    /// Get devices seen advertising on the LAN that aren't yet registered sync peers
+    fn get_discovered_peers(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let list = pyo3::types::PyList::empty(py);
+        for peer in self.inner.discovered_peers() {
+            let dict = PyDict::new(py);
+            dict.set_item("device_id", &peer.device_id)?;
+            dict.set_item("device_name", &peer.device_name)?;
+            dict.set_item("peer_url", &peer.peer_url)?;
+            dict.set_item("certificate_fingerprint", peer.certificate_fingerprint.clone())?;
+            list.append(dict)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Move a device found via discovery into the persisted peer list
+    fn promote_discovered_peer(&mut self, device_id: &str) -> PyResult<bool> {
+        Ok(self.inner.promote_discovered_peer(device_id)?)
+    }
+
     /// Get TUI colors
     fn get_tui_colors(&self, py: Python<'_>) -> PyResult<PyObject> {
         let (focused, unfocused) = self.inner.tui_colors();
@@ -584,7 +1295,9 @@ impl PyConfig {
             peer_dict.set_item("peer_id", &peer.peer_id)?;
             peer_dict.set_item("peer_name", &peer.peer_name)?;
             peer_dict.set_item("peer_url", &peer.peer_url)?;
-            peer_dict.set_item("certificate_fingerprint", &peer.certificate_fingerprint)?;
+            peer_dict.set_item("certificate_fingerprints", peer.certificate_fingerprints.clone())?;
+            peer_dict.set_item("trust_state", peer.trust_state.as_str())?;
+            peer_dict.set_item("pinned_fingerprint", peer.pinned_fingerprint.clone())?;
             peers_list.append(peer_dict)?;
         }
         dict.set_item("peers", peers_list)?;
@@ -624,6 +1337,40 @@ mod tests {
         assert_eq!(peer.peer_url, "https://example.com:8384");
     }
 
+    #[test]
+    fn test_add_peer_fingerprint_accumulates_without_losing_old_ones() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        let peer_id = "0".repeat(32);
+        config
+            .add_peer(&peer_id, "Test Peer", "https://example.com:8384", Some("SHA256:aa"), false)
+            .unwrap();
+
+        config.add_peer_fingerprint(&peer_id, "SHA256:bb").unwrap();
+
+        let fingerprints = config.peer_fingerprints(&peer_id).unwrap();
+        assert_eq!(fingerprints, &["SHA256:bb".to_string(), "SHA256:aa".to_string()]);
+    }
+
+    #[test]
+    fn test_retire_peer_fingerprint_refuses_to_remove_last_one() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        let peer_id = "0".repeat(32);
+        config
+            .add_peer(&peer_id, "Test Peer", "https://example.com:8384", Some("SHA256:aa"), false)
+            .unwrap();
+
+        assert!(config.retire_peer_fingerprint(&peer_id, "SHA256:aa").is_err());
+
+        config.add_peer_fingerprint(&peer_id, "SHA256:bb").unwrap();
+        let removed = config.retire_peer_fingerprint(&peer_id, "SHA256:aa").unwrap();
+        assert!(removed);
+        assert_eq!(config.peer_fingerprints(&peer_id).unwrap(), &["SHA256:bb".to_string()]);
+    }
+
     #[test]
     fn test_remove_peer() {
         let temp_dir = TempDir::new().unwrap();
@@ -648,6 +1395,182 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_pairing_token_round_trips_fingerprint_and_port() {
+        let sender_dir = TempDir::new().unwrap();
+        let sender = Config::new(Some(sender_dir.path().to_path_buf())).unwrap();
+        let certs_dir = sender.certs_dir().unwrap();
+        crate::tls::generate_self_signed_cert(
+            &certs_dir.join("server.crt"),
+            &certs_dir.join("server.key"),
+            "voice-test",
+            None,
+        )
+        .unwrap();
+
+        let token = sender.generate_pairing_token().unwrap();
+
+        let receiver_dir = TempDir::new().unwrap();
+        let mut receiver = Config::new(Some(receiver_dir.path().to_path_buf())).unwrap();
+        let peer_id = receiver.accept_pairing_token(&token).unwrap();
+
+        assert_eq!(peer_id, sender.device_id_hex());
+        let peer = receiver.get_peer(&peer_id).unwrap();
+        assert_eq!(peer.peer_name, sender.device_name());
+        assert!(peer.peer_url.contains(&sender.sync_server_port().to_string()));
+
+        let expected_fingerprint = crate::tls::compute_fingerprint(&certs_dir.join("server.crt")).unwrap();
+        assert_eq!(peer.certificate_fingerprints, vec![expected_fingerprint]);
+    }
+
+    #[test]
+    fn test_accept_pairing_token_rejects_bad_checksum() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        let mut token = bs58::encode(vec![1u8; 55]).into_string();
+        token.push('x');
+        assert!(config.accept_pairing_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_accept_pairing_token_rejects_garbage() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+        assert!(config.accept_pairing_token("not a token").is_err());
+    }
+
+    #[test]
+    fn test_hex_decode_rejects_multibyte_utf8_without_panicking() {
+        // An even "length" in `chars()` terms, but the byte offsets a naive `&s[i..i+2]`
+        // slice would use don't land on a char boundary - this must return an error
+        // instead of panicking.
+        assert!(hex_decode("€0").is_err());
+    }
+
+    #[test]
+    fn test_hex_decode_round_trips_valid_hex() {
+        assert_eq!(hex_decode("00ff").unwrap(), vec![0x00, 0xff]);
+    }
+
+    #[test]
+    fn test_hex_decode_rejects_odd_length_and_non_hex() {
+        assert!(hex_decode("abc").is_err());
+        assert!(hex_decode("zz").is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify_peer_round_trip() {
+        let sender_dir = TempDir::new().unwrap();
+        let sender = Config::new(Some(sender_dir.path().to_path_buf())).unwrap();
+
+        let receiver_dir = TempDir::new().unwrap();
+        let mut receiver = Config::new(Some(receiver_dir.path().to_path_buf())).unwrap();
+
+        let peer_id = "0".repeat(32);
+        receiver
+            .add_peer(&peer_id, "Sender", "https://example.com:8384", None, false)
+            .unwrap();
+
+        let public_key_hex: String = sender
+            .identity_public_key()
+            .unwrap()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        receiver.set_peer_public_key(&peer_id, &public_key_hex).unwrap();
+
+        let message = b"sync envelope";
+        let signature = sender.sign(message).unwrap();
+        assert!(receiver.verify_peer(&peer_id, message, &signature).unwrap());
+        assert!(!receiver.verify_peer(&peer_id, b"tampered", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_verify_peer_unknown_peer_or_missing_key_is_false_not_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        assert!(!config.verify_peer("nonexistent", b"msg", &[0u8; 64]).unwrap());
+
+        let peer_id = "0".repeat(32);
+        config
+            .add_peer(&peer_id, "Test Peer", "https://example.com:8384", None, false)
+            .unwrap();
+        assert!(!config.verify_peer(&peer_id, b"msg", &[0u8; 64]).unwrap());
+    }
+
+    #[test]
+    fn test_identity_public_key_is_stable_across_loads() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        let first = config.identity_public_key().unwrap();
+        let second = Config::new(Some(temp_dir.path().to_path_buf()))
+            .unwrap()
+            .identity_public_key()
+            .unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_observe_peer_fingerprint_pins_on_first_contact() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        let peer_id = "0".repeat(32);
+        config
+            .add_peer(&peer_id, "Test Peer", "https://example.com:8384", None, false)
+            .unwrap();
+        assert_eq!(config.get_peer(&peer_id).unwrap().trust_state, TrustState::Pending);
+
+        config.observe_peer_fingerprint(&peer_id, "SHA256:aa").unwrap();
+
+        let peer = config.get_peer(&peer_id).unwrap();
+        assert_eq!(peer.trust_state, TrustState::Trusted);
+        assert_eq!(peer.pinned_fingerprint.as_deref(), Some("SHA256:aa"));
+    }
+
+    #[test]
+    fn test_observe_peer_fingerprint_conflict_then_confirm() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        let peer_id = "0".repeat(32);
+        config
+            .add_peer(&peer_id, "Test Peer", "https://example.com:8384", Some("SHA256:aa"), false)
+            .unwrap();
+
+        config.observe_peer_fingerprint(&peer_id, "SHA256:bb").unwrap();
+        assert_eq!(config.get_peer(&peer_id).unwrap().trust_state, TrustState::Pending);
+
+        let conflict = config.peer_trust_conflict(&peer_id).unwrap();
+        assert_eq!(conflict.pinned_fingerprint.as_deref(), Some("SHA256:aa"));
+        assert_eq!(conflict.presented_fingerprint, "SHA256:bb");
+
+        config.confirm_peer_fingerprint(&peer_id, "SHA256:bb").unwrap();
+        let peer = config.get_peer(&peer_id).unwrap();
+        assert_eq!(peer.trust_state, TrustState::Trusted);
+        assert_eq!(peer.pinned_fingerprint.as_deref(), Some("SHA256:bb"));
+        assert!(config.peer_trust_conflict(&peer_id).is_none());
+    }
+
+    #[test]
+    fn test_observe_peer_fingerprint_conflict_then_revoke() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        let peer_id = "0".repeat(32);
+        config
+            .add_peer(&peer_id, "Test Peer", "https://example.com:8384", Some("SHA256:aa"), false)
+            .unwrap();
+
+        config.observe_peer_fingerprint(&peer_id, "SHA256:bb").unwrap();
+        config.revoke_peer(&peer_id).unwrap();
+
+        assert_eq!(config.get_peer(&peer_id).unwrap().trust_state, TrustState::Revoked);
+    }
+
     #[test]
     fn test_config_persistence() {
         let temp_dir = TempDir::new().unwrap();
@@ -664,4 +1587,43 @@ mod tests {
             assert!(config.is_sync_enabled());
         }
     }
+
+    #[test]
+    fn test_new_config_loads_against_file_missing_config_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("config.json");
+        fs::write(&config_file, r#"{"device_name": "Pre-Versioning Device"}"#).unwrap();
+
+        let config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+        assert_eq!(config.device_name(), "Pre-Versioning Device");
+        assert_eq!(config.data.config_version, CONFIG_VERSION);
+    }
+
+    #[test]
+    fn test_corrupt_config_is_backed_up_instead_of_silently_reset() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("config.json");
+        fs::write(&config_file, "not valid json at all").unwrap();
+
+        let result = Config::new(Some(temp_dir.path().to_path_buf()));
+        assert!(result.is_err());
+
+        let backups: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("config.json.bak-"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(fs::read_to_string(backups[0].path()).unwrap(), "not valid json at all");
+    }
+
+    #[test]
+    fn test_save_does_not_leave_a_tmp_file_behind() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+        config.set_device_name("Saved Device").unwrap();
+
+        assert!(!temp_dir.path().join("config.json.tmp").exists());
+        assert!(temp_dir.path().join("config.json").exists());
+    }
 }
@@ -0,0 +1,458 @@
+//! Sync conflict storage and resolution.
+//!
+//! When two devices mutate the same note or tag between syncs, [`crate::database::Database`]
+//! records a conflict row here instead of silently picking a winner. Conflicts are kept in a
+//! single `conflicts` table keyed by a `conflict_type` discriminant with a JSON payload, mirroring
+//! the "boring serde" approach already used for config persistence rather than one table per
+//! conflict shape.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rusqlite::Connection;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::error::VoiceResult;
+
+const CREATE_CONFLICTS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS conflicts (
+    id BLOB PRIMARY KEY,
+    conflict_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    resolved_at TEXT
+)";
+
+/// Create the `conflicts` table if it doesn't already exist.
+pub(crate) fn init_schema(conn: &Connection) -> VoiceResult<()> {
+    conn.execute_batch(CREATE_CONFLICTS_TABLE)?;
+    Ok(())
+}
+
+fn insert_conflict(
+    conn: &Connection,
+    conflict_type: &str,
+    entity_id: &str,
+    payload: &serde_json::Value,
+) -> VoiceResult<String> {
+    let id = Uuid::now_v7();
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO conflicts (id, conflict_type, entity_id, payload, created_at) VALUES (?, ?, ?, ?, ?)",
+        rusqlite::params![id.as_bytes().to_vec(), conflict_type, entity_id, payload.to_string(), now],
+    )?;
+    Ok(id.simple().to_string())
+}
+
+fn conflicts_of_type(
+    conn: &Connection,
+    conflict_type: &str,
+    include_resolved: bool,
+) -> VoiceResult<Vec<serde_json::Value>> {
+    let query = if include_resolved {
+        "SELECT id, payload, created_at, resolved_at FROM conflicts \
+         WHERE conflict_type = ? ORDER BY created_at"
+    } else {
+        "SELECT id, payload, created_at, resolved_at FROM conflicts \
+         WHERE conflict_type = ? AND resolved_at IS NULL ORDER BY created_at"
+    };
+
+    let mut stmt = conn.prepare(query)?;
+    let rows = stmt.query_map(rusqlite::params![conflict_type], |row| {
+        let id_bytes: Vec<u8> = row.get(0)?;
+        let payload: String = row.get(1)?;
+        let created_at: String = row.get(2)?;
+        let resolved_at: Option<String> = row.get(3)?;
+        Ok((id_bytes, payload, created_at, resolved_at))
+    })?;
+
+    let mut out = Vec::new();
+    for row in rows {
+        let (id_bytes, payload, created_at, resolved_at) = row?;
+        let id_hex = crate::validation::uuid_bytes_to_hex(&id_bytes)?;
+        let mut value: serde_json::Value = serde_json::from_str(&payload)?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert("conflict_id".to_string(), serde_json::json!(id_hex));
+            obj.insert("created_at".to_string(), serde_json::json!(created_at));
+            obj.insert("resolved".to_string(), serde_json::json!(resolved_at.is_some()));
+        }
+        out.push(value);
+    }
+    Ok(out)
+}
+
+/// Fetch a single conflict's payload regardless of type, used when resolving.
+pub(crate) fn conflict_payload(conn: &Connection, conflict_id: &str) -> VoiceResult<Option<serde_json::Value>> {
+    let uuid = crate::validation::validate_uuid_hex(conflict_id, "conflict_id")?;
+    let payload: Option<String> = conn
+        .query_row(
+            "SELECT payload FROM conflicts WHERE id = ?",
+            rusqlite::params![uuid.as_bytes().to_vec()],
+            |row| row.get(0),
+        )
+        .ok();
+    Ok(payload.map(|p| serde_json::from_str(&p)).transpose()?)
+}
+
+/// Mark a conflict resolved. Returns `false` if it didn't exist or was already resolved.
+pub(crate) fn mark_resolved(conn: &Connection, conflict_id: &str) -> VoiceResult<bool> {
+    let uuid = crate::validation::validate_uuid_hex(conflict_id, "conflict_id")?;
+    let now = Utc::now().to_rfc3339();
+    let updated = conn.execute(
+        "UPDATE conflicts SET resolved_at = ? WHERE id = ? AND resolved_at IS NULL",
+        rusqlite::params![now, uuid.as_bytes().to_vec()],
+    )?;
+    Ok(updated > 0)
+}
+
+pub(crate) fn unresolved_counts(conn: &Connection) -> VoiceResult<HashMap<String, i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT conflict_type, COUNT(*) FROM conflicts WHERE resolved_at IS NULL GROUP BY conflict_type",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        let conflict_type: String = row.get(0)?;
+        let count: i64 = row.get(1)?;
+        Ok((conflict_type, count))
+    })?;
+    let mut counts = HashMap::new();
+    for row in rows {
+        let (t, c) = row?;
+        counts.insert(t, c);
+    }
+    Ok(counts)
+}
+
+// ============================================================================
+// Typed create_* helpers, one per conflict shape
+// ============================================================================
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_note_content_conflict(
+    conn: &Connection,
+    note_id: &str,
+    local_content: &str,
+    local_modified_at: &str,
+    remote_content: &str,
+    remote_modified_at: &str,
+    remote_device_id: Option<&str>,
+    remote_device_name: Option<&str>,
+) -> VoiceResult<String> {
+    let payload = serde_json::json!({
+        "note_id": note_id,
+        "local_content": local_content,
+        "local_modified_at": local_modified_at,
+        "remote_content": remote_content,
+        "remote_modified_at": remote_modified_at,
+        "remote_device_id": remote_device_id,
+        "remote_device_name": remote_device_name,
+    });
+    insert_conflict(conn, "note_content", note_id, &payload)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_note_delete_conflict(
+    conn: &Connection,
+    note_id: &str,
+    surviving_content: &str,
+    surviving_modified_at: &str,
+    surviving_device_id: Option<&str>,
+    deleted_content: Option<&str>,
+    deleted_at: &str,
+    deleting_device_id: Option<&str>,
+    deleting_device_name: Option<&str>,
+) -> VoiceResult<String> {
+    let payload = serde_json::json!({
+        "note_id": note_id,
+        "surviving_content": surviving_content,
+        "surviving_modified_at": surviving_modified_at,
+        "surviving_device_id": surviving_device_id,
+        "deleted_content": deleted_content,
+        "deleted_at": deleted_at,
+        "deleting_device_id": deleting_device_id,
+        "deleting_device_name": deleting_device_name,
+    });
+    insert_conflict(conn, "note_delete", note_id, &payload)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_tag_rename_conflict(
+    conn: &Connection,
+    tag_id: &str,
+    local_name: &str,
+    local_modified_at: &str,
+    remote_name: &str,
+    remote_modified_at: &str,
+    remote_device_id: Option<&str>,
+    remote_device_name: Option<&str>,
+) -> VoiceResult<String> {
+    let payload = serde_json::json!({
+        "tag_id": tag_id,
+        "local_name": local_name,
+        "local_modified_at": local_modified_at,
+        "remote_name": remote_name,
+        "remote_modified_at": remote_modified_at,
+        "remote_device_id": remote_device_id,
+        "remote_device_name": remote_device_name,
+    });
+    insert_conflict(conn, "tag_rename", tag_id, &payload)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_note_tag_conflict(
+    conn: &Connection,
+    note_id: &str,
+    tag_id: &str,
+    local_created_at: Option<&str>,
+    local_modified_at: Option<&str>,
+    local_deleted_at: Option<&str>,
+    remote_created_at: Option<&str>,
+    remote_modified_at: Option<&str>,
+    remote_deleted_at: Option<&str>,
+    remote_device_id: Option<&str>,
+    remote_device_name: Option<&str>,
+) -> VoiceResult<String> {
+    let entity_id = format!("{}:{}", note_id, tag_id);
+    let payload = serde_json::json!({
+        "note_id": note_id,
+        "tag_id": tag_id,
+        "local_created_at": local_created_at,
+        "local_modified_at": local_modified_at,
+        "local_deleted_at": local_deleted_at,
+        "remote_created_at": remote_created_at,
+        "remote_modified_at": remote_modified_at,
+        "remote_deleted_at": remote_deleted_at,
+        "remote_device_id": remote_device_id,
+        "remote_device_name": remote_device_name,
+    });
+    insert_conflict(conn, "note_tag", &entity_id, &payload)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_tag_parent_conflict(
+    conn: &Connection,
+    tag_id: &str,
+    local_parent_id: Option<&str>,
+    local_modified_at: &str,
+    remote_parent_id: Option<&str>,
+    remote_modified_at: &str,
+    remote_device_id: Option<&str>,
+    remote_device_name: Option<&str>,
+) -> VoiceResult<String> {
+    let payload = serde_json::json!({
+        "tag_id": tag_id,
+        "local_parent_id": local_parent_id,
+        "local_modified_at": local_modified_at,
+        "remote_parent_id": remote_parent_id,
+        "remote_modified_at": remote_modified_at,
+        "remote_device_id": remote_device_id,
+        "remote_device_name": remote_device_name,
+    });
+    insert_conflict(conn, "tag_parent", tag_id, &payload)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_tag_delete_conflict(
+    conn: &Connection,
+    tag_id: &str,
+    surviving_name: &str,
+    surviving_parent_id: Option<&str>,
+    surviving_modified_at: &str,
+    surviving_device_id: Option<&str>,
+    surviving_device_name: Option<&str>,
+    deleted_at: &str,
+    deleting_device_id: Option<&str>,
+    deleting_device_name: Option<&str>,
+) -> VoiceResult<String> {
+    let payload = serde_json::json!({
+        "tag_id": tag_id,
+        "surviving_name": surviving_name,
+        "surviving_parent_id": surviving_parent_id,
+        "surviving_modified_at": surviving_modified_at,
+        "surviving_device_id": surviving_device_id,
+        "surviving_device_name": surviving_device_name,
+        "deleted_at": deleted_at,
+        "deleting_device_id": deleting_device_id,
+        "deleting_device_name": deleting_device_name,
+    });
+    insert_conflict(conn, "tag_delete", tag_id, &payload)
+}
+
+pub(crate) fn note_content_conflicts(conn: &Connection, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+    conflicts_of_type(conn, "note_content", include_resolved)
+}
+
+pub(crate) fn note_delete_conflicts(conn: &Connection, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+    conflicts_of_type(conn, "note_delete", include_resolved)
+}
+
+pub(crate) fn tag_rename_conflicts(conn: &Connection, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+    conflicts_of_type(conn, "tag_rename", include_resolved)
+}
+
+pub(crate) fn tag_parent_conflicts(conn: &Connection, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+    conflicts_of_type(conn, "tag_parent", include_resolved)
+}
+
+pub(crate) fn tag_delete_conflicts(conn: &Connection, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+    conflicts_of_type(conn, "tag_delete", include_resolved)
+}
+
+pub(crate) fn note_tag_conflicts(conn: &Connection, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+    conflicts_of_type(conn, "note_tag", include_resolved)
+}
+
+// ============================================================================
+// Python bindings
+// ============================================================================
+
+fn json_dict<'py>(py: Python<'py>, value: &serde_json::Value) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    if let Some(obj) = value.as_object() {
+        for (key, v) in obj {
+            dict.set_item(key, crate::database::json_value_to_pyobject(py, v)?)?;
+        }
+    }
+    Ok(dict)
+}
+
+/// Focused facade over [`Database`] for inspecting and resolving sync conflicts, so
+/// Python call sites that only care about conflicts don't need a full `Database` handle.
+#[pyclass(name = "ConflictManager", unsendable)]
+pub struct PyConflictManager {
+    db: Database,
+}
+
+#[pymethods]
+impl PyConflictManager {
+    #[new]
+    fn new(db_path: &str) -> PyResult<Self> {
+        let db = Database::new(db_path)?;
+        Ok(Self { db })
+    }
+
+    fn get_unresolved_conflict_counts<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
+        let counts = self.db.get_unresolved_conflict_counts()?;
+        let dict = PyDict::new(py);
+        for (key, value) in &counts {
+            dict.set_item(key, value)?;
+        }
+        Ok(dict.into_any().unbind())
+    }
+
+    #[pyo3(signature = (include_resolved=false))]
+    fn get_note_content_conflicts<'py>(&self, py: Python<'py>, include_resolved: bool) -> PyResult<PyObject> {
+        let conflicts = self.db.get_note_content_conflicts(include_resolved)?;
+        let list = pyo3::types::PyList::empty(py);
+        for conflict in &conflicts {
+            list.append(json_dict(py, conflict)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    #[pyo3(signature = (include_resolved=false))]
+    fn get_note_delete_conflicts<'py>(&self, py: Python<'py>, include_resolved: bool) -> PyResult<PyObject> {
+        let conflicts = self.db.get_note_delete_conflicts(include_resolved)?;
+        let list = pyo3::types::PyList::empty(py);
+        for conflict in &conflicts {
+            list.append(json_dict(py, conflict)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    #[pyo3(signature = (include_resolved=false))]
+    fn get_tag_rename_conflicts<'py>(&self, py: Python<'py>, include_resolved: bool) -> PyResult<PyObject> {
+        let conflicts = self.db.get_tag_rename_conflicts(include_resolved)?;
+        let list = pyo3::types::PyList::empty(py);
+        for conflict in &conflicts {
+            list.append(json_dict(py, conflict)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    fn resolve_note_content_conflict(&self, conflict_id: &str, new_content: &str) -> PyResult<bool> {
+        Ok(self.db.resolve_note_content_conflict(conflict_id, new_content)?)
+    }
+
+    /// Keep both sides of a note content conflict: leave the note as whichever content
+    /// already won the sync and create a new note carrying the other side's content.
+    /// Returns the new note's id, or `None` if the conflict doesn't exist.
+    fn fork_note_content_conflict(&self, conflict_id: &str) -> PyResult<Option<String>> {
+        Ok(self.db.fork_note_content_conflict(conflict_id)?)
+    }
+
+    fn resolve_note_delete_conflict(&self, conflict_id: &str, restore_note: bool) -> PyResult<bool> {
+        Ok(self.db.resolve_note_delete_conflict(conflict_id, restore_note)?)
+    }
+
+    fn resolve_tag_rename_conflict(&self, conflict_id: &str, new_name: &str) -> PyResult<bool> {
+        Ok(self.db.resolve_tag_rename_conflict(conflict_id, new_name)?)
+    }
+}
+
+/// Read-only view of a note content conflict, returned to Python for display/resolution UI.
+#[pyclass(name = "NoteContentConflict")]
+#[derive(Clone)]
+pub struct PyNoteContentConflict {
+    #[pyo3(get)]
+    pub conflict_id: String,
+    #[pyo3(get)]
+    pub note_id: String,
+    #[pyo3(get)]
+    pub local_content: String,
+    #[pyo3(get)]
+    pub local_modified_at: String,
+    #[pyo3(get)]
+    pub remote_content: String,
+    #[pyo3(get)]
+    pub remote_modified_at: String,
+    #[pyo3(get)]
+    pub remote_device_id: Option<String>,
+    #[pyo3(get)]
+    pub remote_device_name: Option<String>,
+    #[pyo3(get)]
+    pub resolved: bool,
+}
+
+/// Read-only view of a note delete conflict (one side edited, the other deleted).
+#[pyclass(name = "NoteDeleteConflict")]
+#[derive(Clone)]
+pub struct PyNoteDeleteConflict {
+    #[pyo3(get)]
+    pub conflict_id: String,
+    #[pyo3(get)]
+    pub note_id: String,
+    #[pyo3(get)]
+    pub surviving_content: String,
+    #[pyo3(get)]
+    pub surviving_modified_at: String,
+    #[pyo3(get)]
+    pub deleted_content: Option<String>,
+    #[pyo3(get)]
+    pub deleted_at: String,
+    #[pyo3(get)]
+    pub resolved: bool,
+}
+
+/// Read-only view of a tag rename conflict.
+#[pyclass(name = "TagRenameConflict")]
+#[derive(Clone)]
+pub struct PyTagRenameConflict {
+    #[pyo3(get)]
+    pub conflict_id: String,
+    #[pyo3(get)]
+    pub tag_id: String,
+    #[pyo3(get)]
+    pub local_name: String,
+    #[pyo3(get)]
+    pub local_modified_at: String,
+    #[pyo3(get)]
+    pub remote_name: String,
+    #[pyo3(get)]
+    pub remote_modified_at: String,
+    #[pyo3(get)]
+    pub resolved: bool,
+}
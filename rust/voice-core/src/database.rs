@@ -0,0 +1,3422 @@
+//! SQLite-backed storage for notes, tags, note-tag associations, and sync bookkeeping.
+//!
+//! IDs are UUID7, stored as raw 16-byte BLOBs and converted to/from 32-char hex strings
+//! ([`crate::validation::uuid_bytes_to_hex`]) at every API boundary, matching the convention
+//! used by [`crate::sync_server`]. Alongside each note/tag/note_tag row we keep a small
+//! [`VersionVector`] (`device_id -> counter`) so sync can tell genuinely concurrent edits
+//! apart from a stale peer, instead of trusting wall-clock timestamps, plus an [`HlcStamp`]
+//! that lets a genuinely concurrent edit be resolved deterministically instead of always
+//! favoring whichever side happens to be local. We also maintain a [`crate::merkle`]
+//! hash tree over the same IDs (`merkle_buckets`) so two peers can find exactly the
+//! buckets that diverged without scanning the whole dataset, and a content-addressed
+//! `chunks` table (see [`crate::chunking`]) that a note's content is split into, with
+//! `note_chunks` recording which ordered hash list reconstructs which note, so sync can
+//! transmit only the chunks a peer doesn't already have instead of the note's full content.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+
+use chrono::Utc;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::chunking;
+use crate::conflicts;
+use crate::error::{VoiceError, VoiceResult};
+use crate::merkle;
+use crate::sync_client::SyncChange;
+use crate::validation;
+
+const CREATE_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS notes (
+    id BLOB PRIMARY KEY,
+    created_at TEXT NOT NULL,
+    content TEXT NOT NULL,
+    modified_at TEXT,
+    deleted_at TEXT,
+    version_vector TEXT NOT NULL DEFAULT '{}',
+    hlc TEXT NOT NULL DEFAULT '{}'
+);
+CREATE TABLE IF NOT EXISTS tags (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL,
+    parent_id BLOB,
+    created_at TEXT,
+    modified_at TEXT,
+    version_vector TEXT NOT NULL DEFAULT '{}',
+    hlc TEXT NOT NULL DEFAULT '{}'
+);
+CREATE TABLE IF NOT EXISTS note_tags (
+    note_id BLOB NOT NULL,
+    tag_id BLOB NOT NULL,
+    created_at TEXT NOT NULL,
+    modified_at TEXT,
+    deleted_at TEXT,
+    version_vector TEXT NOT NULL DEFAULT '{}',
+    hlc TEXT NOT NULL DEFAULT '{}',
+    PRIMARY KEY (note_id, tag_id)
+);
+CREATE TABLE IF NOT EXISTS sync_peers (
+    peer_id BLOB PRIMARY KEY,
+    peer_name TEXT,
+    last_sync_at TEXT
+);
+CREATE TABLE IF NOT EXISTS note_embeddings (
+    note_id BLOB PRIMARY KEY,
+    model_id TEXT NOT NULL,
+    dim INTEGER NOT NULL,
+    vector BLOB NOT NULL,
+    norm REAL NOT NULL
+);
+CREATE TABLE IF NOT EXISTS merkle_buckets (
+    prefix BLOB PRIMARY KEY,
+    hash BLOB NOT NULL
+);
+CREATE TABLE IF NOT EXISTS chunks (
+    hash BLOB PRIMARY KEY,
+    data BLOB NOT NULL,
+    refcount INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS note_chunks (
+    note_id BLOB NOT NULL,
+    ordinal INTEGER NOT NULL,
+    hash BLOB NOT NULL,
+    PRIMARY KEY (note_id, ordinal)
+);
+";
+
+/// Placeholder model identifier stored alongside each embedding. The Python layer may
+/// feed embeddings from whatever model it chooses; we don't yet need to disambiguate
+/// between models at query time, so every row is tagged with the same constant.
+const DEFAULT_EMBEDDING_MODEL_ID: &str = "default";
+
+fn vector_norm(vector: &[f32]) -> f32 {
+    vector.iter().map(|v| v * v).sum::<f32>().sqrt()
+}
+
+fn cosine_similarity(query: &[f32], query_norm: f32, stored_bytes: &[u8], stored_norm: f64) -> f64 {
+    if stored_norm == 0.0 || query_norm == 0.0 {
+        return 0.0;
+    }
+    let mut dot: f32 = 0.0;
+    for (i, chunk) in stored_bytes.chunks_exact(4).enumerate() {
+        let stored_value = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        dot += stored_value * query[i];
+    }
+    (dot as f64) / (query_norm as f64 * stored_norm)
+}
+
+/// One scored candidate in [`Database::semantic_search_notes`]'s bounded min-heap.
+/// `Ord` is reversed relative to `score` so that `BinaryHeap`'s max-heap semantics evict
+/// the *lowest*-scoring candidate once the heap grows past `top_k`.
+struct ScoredNoteId {
+    score: f64,
+    note_id: String,
+}
+
+impl PartialEq for ScoredNoteId {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score
+    }
+}
+
+impl Eq for ScoredNoteId {}
+
+impl PartialOrd for ScoredNoteId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredNoteId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.score.partial_cmp(&self.score).unwrap_or(Ordering::Equal)
+    }
+}
+
+// ============================================================================
+// Local device identity
+// ============================================================================
+
+static LOCAL_DEVICE_ID: OnceLock<Mutex<Uuid>> = OnceLock::new();
+
+fn local_device_cell() -> &'static Mutex<Uuid> {
+    LOCAL_DEVICE_ID.get_or_init(|| Mutex::new(Uuid::now_v7()))
+}
+
+/// Set the device ID this process should stamp into version vectors for local mutations.
+pub fn set_local_device_id(device_id: Uuid) {
+    *local_device_cell().lock().unwrap() = device_id;
+}
+
+/// The device ID this process stamps into version vectors for local mutations.
+pub fn get_local_device_id() -> Uuid {
+    *local_device_cell().lock().unwrap()
+}
+
+#[pyfunction]
+#[pyo3(name = "set_local_device_id")]
+pub fn py_set_local_device_id(device_id: &str) -> PyResult<()> {
+    let uuid = validation::validate_uuid_hex(device_id, "device_id")?;
+    set_local_device_id(uuid);
+    Ok(())
+}
+
+// ============================================================================
+// Version vectors
+// ============================================================================
+
+/// How two version vectors relate to each other causally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorOrdering {
+    /// Identical on every component.
+    Equal,
+    /// `self` is ahead on every component where they differ.
+    Dominates,
+    /// `other` is ahead on every component where they differ.
+    Dominated,
+    /// Each side is ahead on at least one component: a genuine conflict.
+    Concurrent,
+}
+
+/// A per-entity `device_id -> counter` map used to detect concurrent edits during sync.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VersionVector(pub HashMap<String, u64>);
+
+impl VersionVector {
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn from_json(raw: &str) -> Self {
+        serde_json::from_str(raw).unwrap_or_default()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.0).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Bump this device's own counter. Call on every local mutation of the entity.
+    pub fn increment(&mut self, device_id: &str) {
+        *self.0.entry(device_id.to_string()).or_insert(0) += 1;
+    }
+
+    /// Component-wise comparison against `other`.
+    pub fn compare(&self, other: &VersionVector) -> VectorOrdering {
+        let mut self_ahead = false;
+        let mut other_ahead = false;
+        let keys: HashSet<&String> = self.0.keys().chain(other.0.keys()).collect();
+        for key in keys {
+            let a = self.0.get(key).copied().unwrap_or(0);
+            let b = other.0.get(key).copied().unwrap_or(0);
+            if a > b {
+                self_ahead = true;
+            }
+            if b > a {
+                other_ahead = true;
+            }
+        }
+        match (self_ahead, other_ahead) {
+            (false, false) => VectorOrdering::Equal,
+            (true, false) => VectorOrdering::Dominates,
+            (false, true) => VectorOrdering::Dominated,
+            (true, true) => VectorOrdering::Concurrent,
+        }
+    }
+
+    /// Element-wise max of `self` and `other`, used once a conflict is resolved.
+    pub fn merge(&self, other: &VersionVector) -> VersionVector {
+        let mut merged = self.0.clone();
+        for (device, count) in &other.0 {
+            let entry = merged.entry(device.clone()).or_insert(0);
+            if *count > *entry {
+                *entry = *count;
+            }
+        }
+        VersionVector(merged)
+    }
+}
+
+// ============================================================================
+// Hybrid logical clock
+// ============================================================================
+
+/// A Hybrid Logical Clock stamp: wall-clock time plus a tie-breaking counter and the
+/// device that produced it. Unlike [`VersionVector`] (which only tells us *whether*
+/// two entities diverged), an `HlcStamp` gives [`crate::sync_server`] a total, deterministic
+/// order to pick a winner *within* a [`VectorOrdering::Concurrent`] edit, without trusting
+/// raw wall-clock timestamps (which can run backward or collide across devices).
+///
+/// Field order matters: the derived `Ord` compares `wall_ms`, then `counter`, then
+/// `device_id`, which is exactly the precedence an HLC comparison needs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct HlcStamp {
+    pub wall_ms: i64,
+    pub counter: u32,
+    pub device_id: String,
+}
+
+impl HlcStamp {
+    pub fn from_json(raw: &str) -> Self {
+        serde_json::from_str(raw).unwrap_or_default()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Advance `device_id`'s clock past `previous` (the entity's last-known stamp, if
+    /// any). If the local wall clock has moved past `previous.wall_ms`, the new stamp
+    /// just takes the current time with a fresh counter; otherwise (clock skew, or a
+    /// second mutation in the same millisecond) the wall time holds and the counter
+    /// ticks forward, so stamps from one device are always strictly increasing.
+    pub fn tick(previous: Option<&HlcStamp>, device_id: &str) -> HlcStamp {
+        let physical_now = Utc::now().timestamp_millis();
+        let (wall_ms, counter) = match previous {
+            Some(prev) if prev.wall_ms >= physical_now => (prev.wall_ms, prev.counter + 1),
+            _ => (physical_now, 0),
+        };
+        HlcStamp { wall_ms, counter, device_id: device_id.to_string() }
+    }
+}
+
+// ============================================================================
+// Row types
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub struct NoteRow {
+    pub id: String,
+    pub created_at: String,
+    pub content: String,
+    pub modified_at: Option<String>,
+    pub deleted_at: Option<String>,
+    pub tag_names: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TagRow {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    pub created_at: Option<String>,
+    pub modified_at: Option<String>,
+}
+
+/// Column names for one relation's rows in a [`ColumnarSnapshot`], in the exact
+/// order [`Database::export_snapshot`] writes them and [`Database::import_snapshot`]
+/// reads them back by position.
+const NOTE_SNAPSHOT_HEADERS: [&str; 7] =
+    ["id", "created_at", "content", "modified_at", "deleted_at", "version_vector", "hlc"];
+const TAG_SNAPSHOT_HEADERS: [&str; 7] =
+    ["id", "name", "parent_id", "created_at", "modified_at", "version_vector", "hlc"];
+const NOTE_TAG_SNAPSHOT_HEADERS: [&str; 7] =
+    ["note_id", "tag_id", "created_at", "modified_at", "deleted_at", "version_vector", "hlc"];
+
+/// One relation's data in columnar form: a header row naming each column, followed
+/// by one array of values per record in that same column order. Compared to a list
+/// of per-row dicts, this avoids allocating a `PyDict` per row when crossing PyO3.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnarTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// A full-store backup/migration snapshot: one [`ColumnarTable`] per relation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnarSnapshot {
+    pub notes: ColumnarTable,
+    pub tags: ColumnarTable,
+    pub note_tags: ColumnarTable,
+}
+
+/// Result of [`Database::query_tag_graph`]: tags for the `descendants`/`ancestors`
+/// modes, or notes for the `notes_in_subtree` mode.
+#[derive(Debug, Clone)]
+pub enum TagGraphResult {
+    Tags(Vec<TagRow>),
+    Notes(Vec<NoteRow>),
+}
+
+/// Row counts applied by [`Database::import_snapshot`].
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub notes_applied: i64,
+    pub tags_applied: i64,
+    pub note_tags_applied: i64,
+    pub conflicts: i64,
+}
+
+pub(crate) fn json_value_to_pyobject(py: Python<'_>, value: &serde_json::Value) -> PyResult<PyObject> {
+    match value {
+        serde_json::Value::Null => Ok(py.None()),
+        serde_json::Value::Bool(b) => Ok(b.into_pyobject(py)?.to_owned().into_any().unbind()),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Ok(i.into_pyobject(py)?.into_any().unbind())
+            } else if let Some(f) = n.as_f64() {
+                Ok(f.into_pyobject(py)?.into_any().unbind())
+            } else {
+                Ok(py.None())
+            }
+        }
+        serde_json::Value::String(s) => Ok(s.into_pyobject(py)?.into_any().unbind()),
+        serde_json::Value::Array(arr) => {
+            let list = PyList::empty(py);
+            for item in arr {
+                list.append(json_value_to_pyobject(py, item)?)?;
+            }
+            Ok(list.into_any().unbind())
+        }
+        serde_json::Value::Object(obj) => {
+            let dict = PyDict::new(py);
+            for (k, v) in obj {
+                dict.set_item(k, json_value_to_pyobject(py, v)?)?;
+            }
+            Ok(dict.into_any().unbind())
+        }
+    }
+}
+
+/// Inverse of [`json_value_to_pyobject`]: convert a Python value received from a
+/// caller (e.g. an `import_snapshot` argument) back into a [`serde_json::Value`].
+fn pyobject_to_json_value(value: &Bound<'_, pyo3::types::PyAny>) -> PyResult<serde_json::Value> {
+    if value.is_none() {
+        return Ok(serde_json::Value::Null);
+    }
+    if let Ok(b) = value.extract::<bool>() {
+        return Ok(serde_json::Value::Bool(b));
+    }
+    if let Ok(i) = value.extract::<i64>() {
+        return Ok(serde_json::json!(i));
+    }
+    if let Ok(f) = value.extract::<f64>() {
+        return Ok(serde_json::json!(f));
+    }
+    if let Ok(s) = value.extract::<String>() {
+        return Ok(serde_json::Value::String(s));
+    }
+    if let Ok(list) = value.downcast::<PyList>() {
+        let mut arr = Vec::with_capacity(list.len());
+        for item in list.iter() {
+            arr.push(pyobject_to_json_value(&item)?);
+        }
+        return Ok(serde_json::Value::Array(arr));
+    }
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        let mut map = serde_json::Map::new();
+        for (k, v) in dict.iter() {
+            let key: String = k.extract()?;
+            map.insert(key, pyobject_to_json_value(&v)?);
+        }
+        return Ok(serde_json::Value::Object(map));
+    }
+    Err(pyo3::exceptions::PyTypeError::new_err("unsupported value in snapshot"))
+}
+
+pub(crate) fn note_row_to_dict<'py>(py: Python<'py>, note: &NoteRow) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", &note.id)?;
+    dict.set_item("created_at", &note.created_at)?;
+    dict.set_item("content", &note.content)?;
+    dict.set_item("modified_at", &note.modified_at)?;
+    dict.set_item("deleted_at", &note.deleted_at)?;
+    dict.set_item("tag_names", &note.tag_names)?;
+    Ok(dict)
+}
+
+fn tag_row_to_dict<'py>(py: Python<'py>, tag: &TagRow) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", &tag.id)?;
+    dict.set_item("name", &tag.name)?;
+    dict.set_item("parent_id", &tag.parent_id)?;
+    dict.set_item("created_at", &tag.created_at)?;
+    dict.set_item("modified_at", &tag.modified_at)?;
+    Ok(dict)
+}
+
+// ============================================================================
+// Database
+// ============================================================================
+
+pub struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    pub fn new(path: &str) -> VoiceResult<Self> {
+        let conn = Connection::open(path)?;
+        let db = Self { conn };
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    pub fn new_in_memory() -> VoiceResult<Self> {
+        let conn = Connection::open_in_memory()?;
+        let db = Self { conn };
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    fn init_schema(&self) -> VoiceResult<()> {
+        self.conn.execute_batch(CREATE_SCHEMA)?;
+        conflicts::init_schema(&self.conn)?;
+        Ok(())
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub fn close(self) -> VoiceResult<()> {
+        drop(self);
+        Ok(())
+    }
+
+    fn local_vector_bump(&self, existing: Option<&str>) -> String {
+        let mut vector = existing.map(VersionVector::from_json).unwrap_or_default();
+        vector.increment(&get_local_device_id().simple().to_string());
+        vector.to_json()
+    }
+
+    /// Stamp a fresh HLC for a local mutation, ticking past `existing` (the entity's
+    /// previously stored stamp, if any). Call alongside [`Self::local_vector_bump`] on
+    /// every local create/update/delete so sync can break concurrent-edit ties.
+    fn local_hlc_tick(&self, existing: Option<&str>) -> String {
+        let previous = existing.map(HlcStamp::from_json).unwrap_or_default();
+        let device_id = get_local_device_id().simple().to_string();
+        HlcStamp::tick(Some(&previous), &device_id).to_json()
+    }
+
+    // ------------------------------------------------------------------
+    // Transactions
+    // ------------------------------------------------------------------
+
+    /// Begin an explicit transaction on this connection. Every write method called
+    /// afterwards (on this same `Database`) participates in it until [`Self::commit_transaction`]
+    /// or [`Self::rollback_transaction`] ends it.
+    pub fn begin_transaction(&self) -> VoiceResult<()> {
+        self.conn.execute_batch("BEGIN")?;
+        Ok(())
+    }
+
+    pub fn savepoint(&self, name: &str) -> VoiceResult<()> {
+        validation::validate_savepoint_name(name)?;
+        self.conn.execute_batch(&format!("SAVEPOINT {name}"))?;
+        Ok(())
+    }
+
+    pub fn release_savepoint(&self, name: &str) -> VoiceResult<()> {
+        validation::validate_savepoint_name(name)?;
+        self.conn.execute_batch(&format!("RELEASE SAVEPOINT {name}"))?;
+        Ok(())
+    }
+
+    pub fn rollback_to_savepoint(&self, name: &str) -> VoiceResult<()> {
+        validation::validate_savepoint_name(name)?;
+        self.conn.execute_batch(&format!("ROLLBACK TO SAVEPOINT {name}"))?;
+        Ok(())
+    }
+
+    pub fn commit_transaction(&self) -> VoiceResult<()> {
+        self.conn.execute_batch("COMMIT")?;
+        Ok(())
+    }
+
+    pub fn rollback_transaction(&self) -> VoiceResult<()> {
+        self.conn.execute_batch("ROLLBACK")?;
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Notes
+    // ------------------------------------------------------------------
+
+    pub fn create_note(&self, content: &str) -> VoiceResult<String> {
+        validation::validate_note_content(content)?;
+        let id = Uuid::now_v7();
+        let now = Utc::now().to_rfc3339();
+        let vector = self.local_vector_bump(None);
+        let hlc = self.local_hlc_tick(None);
+        self.conn.execute(
+            "INSERT INTO notes (id, created_at, content, version_vector, hlc) VALUES (?, ?, ?, ?, ?)",
+            rusqlite::params![id.as_bytes().to_vec(), now, content, vector, hlc],
+        )?;
+        self.merkle_touch(id.as_bytes())?;
+        let note_id = id.simple().to_string();
+        self.store_content_chunks(&note_id, content)?;
+        Ok(note_id)
+    }
+
+    fn note_tag_names(&self, note_id: &[u8]) -> VoiceResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tags.name FROM note_tags \
+             JOIN tags ON tags.id = note_tags.tag_id \
+             WHERE note_tags.note_id = ? AND note_tags.deleted_at IS NULL \
+             ORDER BY tags.name",
+        )?;
+        let names = stmt
+            .query_map(rusqlite::params![note_id], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(names)
+    }
+
+    pub fn get_note(&self, note_id: &str) -> VoiceResult<Option<NoteRow>> {
+        let uuid = validation::validate_note_id(note_id)?;
+        let id_bytes = uuid.as_bytes().to_vec();
+        let row = self
+            .conn
+            .query_row(
+                "SELECT id, created_at, content, modified_at, deleted_at FROM notes WHERE id = ?",
+                rusqlite::params![id_bytes],
+                |row| {
+                    let id_bytes: Vec<u8> = row.get(0)?;
+                    Ok((
+                        id_bytes,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, Option<String>>(4)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        match row {
+            Some((id_bytes, created_at, content, modified_at, deleted_at)) => {
+                let tag_names = self.note_tag_names(&id_bytes)?;
+                Ok(Some(NoteRow {
+                    id: validation::uuid_bytes_to_hex(&id_bytes)?,
+                    created_at,
+                    content,
+                    modified_at,
+                    deleted_at,
+                    tag_names,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn update_note(&self, note_id: &str, content: &str) -> VoiceResult<bool> {
+        validation::validate_note_content(content)?;
+        let uuid = validation::validate_note_id(note_id)?;
+        let id_bytes = uuid.as_bytes().to_vec();
+        let existing: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT version_vector, hlc FROM notes WHERE id = ?",
+                rusqlite::params![id_bytes],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((existing_vector, existing_hlc)) = existing else {
+            return Ok(false);
+        };
+        let vector = self.local_vector_bump(Some(&existing_vector));
+        let hlc = self.local_hlc_tick(Some(&existing_hlc));
+        let now = Utc::now().to_rfc3339();
+        let updated = self.conn.execute(
+            "UPDATE notes SET content = ?, modified_at = ?, version_vector = ?, hlc = ? WHERE id = ?",
+            rusqlite::params![content, now, vector, hlc, id_bytes],
+        )?;
+        self.merkle_touch(&id_bytes)?;
+        if updated > 0 {
+            self.store_content_chunks(note_id, content)?;
+        }
+        Ok(updated > 0)
+    }
+
+    pub fn delete_note(&self, note_id: &str) -> VoiceResult<bool> {
+        let uuid = validation::validate_note_id(note_id)?;
+        let id_bytes = uuid.as_bytes().to_vec();
+        let existing: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT version_vector, hlc FROM notes WHERE id = ? AND deleted_at IS NULL",
+                rusqlite::params![id_bytes],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((existing_vector, existing_hlc)) = existing else {
+            return Ok(false);
+        };
+        let vector = self.local_vector_bump(Some(&existing_vector));
+        let hlc = self.local_hlc_tick(Some(&existing_hlc));
+        let now = Utc::now().to_rfc3339();
+        let updated = self.conn.execute(
+            "UPDATE notes SET deleted_at = ?, version_vector = ?, hlc = ? WHERE id = ?",
+            rusqlite::params![now, vector, hlc, id_bytes],
+        )?;
+        self.merkle_touch(&id_bytes)?;
+        Ok(updated > 0)
+    }
+
+    pub fn get_all_notes(&self) -> VoiceResult<Vec<NoteRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, created_at, content, modified_at, deleted_at FROM notes \
+             WHERE deleted_at IS NULL ORDER BY COALESCE(modified_at, created_at) DESC",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, Vec<u8>>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut notes = Vec::with_capacity(rows.len());
+        for (id_bytes, created_at, content, modified_at, deleted_at) in rows {
+            let tag_names = self.note_tag_names(&id_bytes)?;
+            notes.push(NoteRow {
+                id: validation::uuid_bytes_to_hex(&id_bytes)?,
+                created_at,
+                content,
+                modified_at,
+                deleted_at,
+                tag_names,
+            });
+        }
+        Ok(notes)
+    }
+
+    pub fn filter_notes(&self, tag_ids: &[String]) -> VoiceResult<Vec<NoteRow>> {
+        if tag_ids.is_empty() {
+            return self.get_all_notes();
+        }
+        let tag_uuids = validation::validate_tag_ids(tag_ids)?;
+        let all_notes = self.get_all_notes()?;
+
+        let mut matching = Vec::new();
+        'note: for note in all_notes {
+            let note_id = validation::validate_note_id(&note.id)?;
+            for tag_uuid in &tag_uuids {
+                let has_tag: Option<i64> = self
+                    .conn
+                    .query_row(
+                        "SELECT 1 FROM note_tags WHERE note_id = ? AND tag_id = ? AND deleted_at IS NULL",
+                        rusqlite::params![note_id.as_bytes().to_vec(), tag_uuid.as_bytes().to_vec()],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                if has_tag.is_none() {
+                    continue 'note;
+                }
+            }
+            matching.push(note);
+        }
+        Ok(matching)
+    }
+
+    pub fn search_notes(
+        &self,
+        text_query: Option<&str>,
+        tag_id_groups: Option<&Vec<Vec<String>>>,
+    ) -> VoiceResult<Vec<NoteRow>> {
+        validation::validate_search_query(text_query)?;
+        validation::validate_tag_id_groups(tag_id_groups)?;
+        let mut notes = self.get_all_notes()?;
+
+        if let Some(groups) = tag_id_groups {
+            for group in groups {
+                if group.is_empty() {
+                    continue;
+                }
+                let matches_group = self.filter_notes(group)?;
+                let allowed: HashSet<String> = matches_group.into_iter().map(|n| n.id).collect();
+                notes.retain(|n| allowed.contains(&n.id));
+            }
+        }
+
+        if let Some(query) = text_query {
+            let needle = query.to_lowercase();
+            notes.retain(|n| n.content.to_lowercase().contains(&needle));
+        }
+
+        Ok(notes)
+    }
+
+    // ------------------------------------------------------------------
+    // Embeddings
+    // ------------------------------------------------------------------
+
+    /// Store (or replace) a note's embedding vector, e.g. from a caller-chosen sentence
+    /// embedding model. The vector's norm is computed once here and cached alongside it,
+    /// so [`Self::semantic_search_notes`] doesn't recompute it on every query.
+    pub fn upsert_note_embedding(&self, note_id: &str, embedding: &[f32]) -> VoiceResult<()> {
+        let uuid = validation::validate_note_id(note_id)?;
+        if embedding.is_empty() {
+            return Err(VoiceError::validation("embedding", "cannot be empty"));
+        }
+        let norm = vector_norm(embedding);
+        let mut vector_bytes = Vec::with_capacity(embedding.len() * 4);
+        for v in embedding {
+            vector_bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        self.conn.execute(
+            "INSERT INTO note_embeddings (note_id, model_id, dim, vector, norm) VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(note_id) DO UPDATE SET model_id = excluded.model_id, dim = excluded.dim, vector = excluded.vector, norm = excluded.norm",
+            rusqlite::params![
+                uuid.as_bytes().to_vec(),
+                DEFAULT_EMBEDDING_MODEL_ID,
+                embedding.len() as i64,
+                vector_bytes,
+                norm as f64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Rank notes by cosine similarity of their stored embedding to `query_embedding`,
+    /// brute-force over every stored vector (fine for the expected note counts). Keeps a
+    /// bounded min-heap of size `top_k` rather than sorting the full result set, and drops
+    /// anything scoring below `min_score`. Returns `(note, score)` pairs sorted by
+    /// descending score.
+    pub fn semantic_search_notes(&self, query_embedding: &[f32], top_k: usize, min_score: f64) -> VoiceResult<Vec<(NoteRow, f64)>> {
+        if query_embedding.is_empty() {
+            return Err(VoiceError::validation("query_embedding", "cannot be empty"));
+        }
+        let query_norm = vector_norm(query_embedding);
+        if query_norm == 0.0 {
+            return Err(VoiceError::validation("query_embedding", "cannot be the zero vector"));
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT note_id, dim, vector, norm FROM note_embeddings")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, Vec<u8>>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, Vec<u8>>(2)?,
+                    row.get::<_, f64>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut heap: BinaryHeap<ScoredNoteId> = BinaryHeap::new();
+        for (note_id_bytes, dim, vector_bytes, stored_norm) in rows {
+            if dim as usize != query_embedding.len() {
+                return Err(VoiceError::validation(
+                    "query_embedding",
+                    format!("dimension {} does not match stored embedding dimension {}", query_embedding.len(), dim),
+                ));
+            }
+            let score = cosine_similarity(query_embedding, query_norm, &vector_bytes, stored_norm);
+            let note_id = validation::uuid_bytes_to_hex(&note_id_bytes)?;
+            heap.push(ScoredNoteId { score, note_id });
+            if heap.len() > top_k {
+                heap.pop();
+            }
+        }
+
+        // `ScoredNoteId`'s `Ord` is reversed (so the heap's "greatest" element is the
+        // lowest-scoring one, letting us evict it when the heap grows past `top_k`), which
+        // means `into_sorted_vec`'s ascending order is already highest-score-first.
+        let scored: Vec<ScoredNoteId> = heap
+            .into_sorted_vec()
+            .into_iter()
+            .filter(|s| s.score >= min_score)
+            .collect();
+
+        let mut results = Vec::with_capacity(scored.len());
+        for scored_id in scored {
+            if let Some(note) = self.get_note(&scored_id.note_id)? {
+                results.push((note, scored_id.score));
+            }
+        }
+        Ok(results)
+    }
+
+    // ------------------------------------------------------------------
+    // Tags
+    // ------------------------------------------------------------------
+
+    pub fn create_tag(&self, name: &str, parent_id: Option<&str>) -> VoiceResult<String> {
+        validation::validate_tag_name(name)?;
+        let parent_uuid = validation::validate_parent_tag_id(parent_id, None)?;
+        let id = Uuid::now_v7();
+        let now = Utc::now().to_rfc3339();
+        let vector = self.local_vector_bump(None);
+        let hlc = self.local_hlc_tick(None);
+        self.conn.execute(
+            "INSERT INTO tags (id, name, parent_id, created_at, version_vector, hlc) VALUES (?, ?, ?, ?, ?, ?)",
+            rusqlite::params![
+                id.as_bytes().to_vec(),
+                name,
+                parent_uuid.map(|u| u.as_bytes().to_vec()),
+                now,
+                vector,
+                hlc,
+            ],
+        )?;
+        self.merkle_touch(id.as_bytes())?;
+        Ok(id.simple().to_string())
+    }
+
+    fn row_to_tag(
+        id_bytes: Vec<u8>,
+        name: String,
+        parent_id_bytes: Option<Vec<u8>>,
+        created_at: Option<String>,
+        modified_at: Option<String>,
+    ) -> VoiceResult<TagRow> {
+        Ok(TagRow {
+            id: validation::uuid_bytes_to_hex(&id_bytes)?,
+            name,
+            parent_id: parent_id_bytes.map(|b| validation::uuid_bytes_to_hex(&b)).transpose()?,
+            created_at,
+            modified_at,
+        })
+    }
+
+    pub fn get_tag(&self, tag_id: &str) -> VoiceResult<Option<TagRow>> {
+        let uuid = validation::validate_tag_id(tag_id)?;
+        let row = self
+            .conn
+            .query_row(
+                "SELECT id, name, parent_id, created_at, modified_at FROM tags WHERE id = ?",
+                rusqlite::params![uuid.as_bytes().to_vec()],
+                |row| {
+                    Ok((
+                        row.get::<_, Vec<u8>>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<Vec<u8>>>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, Option<String>>(4)?,
+                    ))
+                },
+            )
+            .optional()?;
+        row.map(|(id, name, parent, created, modified)| Self::row_to_tag(id, name, parent, created, modified))
+            .transpose()
+    }
+
+    pub fn get_all_tags(&self) -> VoiceResult<Vec<TagRow>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, name, parent_id, created_at, modified_at FROM tags ORDER BY name")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, Vec<u8>>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<Vec<u8>>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.into_iter()
+            .map(|(id, name, parent, created, modified)| Self::row_to_tag(id, name, parent, created, modified))
+            .collect()
+    }
+
+    pub fn get_tags_by_name(&self, name: &str) -> VoiceResult<Vec<TagRow>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, name, parent_id, created_at, modified_at FROM tags WHERE name = ? ORDER BY name")?;
+        let rows = stmt
+            .query_map(rusqlite::params![name], |row| {
+                Ok((
+                    row.get::<_, Vec<u8>>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<Vec<u8>>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.into_iter()
+            .map(|(id, name, parent, created, modified)| Self::row_to_tag(id, name, parent, created, modified))
+            .collect()
+    }
+
+    pub fn is_tag_name_ambiguous(&self, name: &str) -> VoiceResult<bool> {
+        Ok(self.get_tags_by_name(name)?.len() > 1)
+    }
+
+    fn tag_path(&self, tag: &TagRow) -> VoiceResult<String> {
+        let mut segments = vec![tag.name.clone()];
+        let mut current = tag.parent_id.clone();
+        while let Some(parent_id) = current {
+            let parent = self
+                .get_tag(&parent_id)?
+                .ok_or_else(|| VoiceError::NotFound(format!("tag {}", parent_id)))?;
+            segments.push(parent.name.clone());
+            current = parent.parent_id;
+        }
+        segments.reverse();
+        Ok(segments.join("/"))
+    }
+
+    pub fn get_tag_by_path(&self, path: &str) -> VoiceResult<Option<TagRow>> {
+        let matches = self.get_all_tags_by_path(path)?;
+        Ok(matches.into_iter().next())
+    }
+
+    pub fn get_all_tags_by_path(&self, path: &str) -> VoiceResult<Vec<TagRow>> {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        if segments.is_empty() {
+            return Ok(Vec::new());
+        }
+        let leaf_name = segments.last().unwrap();
+        let mut candidates = self.get_tags_by_name(leaf_name)?;
+        if segments.len() > 1 {
+            candidates.retain(|tag| self.tag_path(tag).map(|p| p == path).unwrap_or(false));
+        }
+        Ok(candidates)
+    }
+
+    pub fn get_tag_descendants(&self, tag_id: &str) -> VoiceResult<Vec<Vec<u8>>> {
+        let uuid = validation::validate_tag_id(tag_id)?;
+        let mut descendants = Vec::new();
+        let mut frontier = vec![uuid.as_bytes().to_vec()];
+        while let Some(parent) = frontier.pop() {
+            let mut stmt = self.conn.prepare("SELECT id FROM tags WHERE parent_id = ?")?;
+            let children = stmt
+                .query_map(rusqlite::params![parent], |row| row.get::<_, Vec<u8>>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            for child in children {
+                descendants.push(child.clone());
+                frontier.push(child);
+            }
+        }
+        Ok(descendants)
+    }
+
+    /// Walk the `parent_id` edge outward from `start`, collecting every tag id reached
+    /// (not including `start` itself). A visited set guards termination even if the
+    /// tag table somehow contains a parent cycle.
+    fn tag_descendant_ids(&self, start: &Uuid) -> VoiceResult<Vec<Uuid>> {
+        let mut visited: HashSet<Vec<u8>> = HashSet::new();
+        visited.insert(start.as_bytes().to_vec());
+        let mut frontier = vec![start.as_bytes().to_vec()];
+        let mut descendants = Vec::new();
+        while let Some(parent) = frontier.pop() {
+            let mut stmt = self.conn.prepare("SELECT id FROM tags WHERE parent_id = ?")?;
+            let children = stmt
+                .query_map(rusqlite::params![parent], |row| row.get::<_, Vec<u8>>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            for child in children {
+                if visited.insert(child.clone()) {
+                    descendants.push(child.clone());
+                    frontier.push(child);
+                }
+            }
+        }
+        descendants.into_iter().map(|b| Uuid::from_slice(&b).map_err(VoiceError::from)).collect()
+    }
+
+    /// Walk the `parent_id` edge inward from `start` up to the root(s), collecting every
+    /// ancestor tag id (not including `start` itself). A visited set guards termination.
+    fn tag_ancestor_ids(&self, start: &Uuid) -> VoiceResult<Vec<Uuid>> {
+        let mut visited: HashSet<Vec<u8>> = HashSet::new();
+        visited.insert(start.as_bytes().to_vec());
+        let mut current = Some(start.as_bytes().to_vec());
+        let mut ancestors = Vec::new();
+        while let Some(id_bytes) = current {
+            let parent: Option<Vec<u8>> = self
+                .conn
+                .query_row("SELECT parent_id FROM tags WHERE id = ?", rusqlite::params![id_bytes], |row| row.get(0))
+                .optional()?
+                .flatten();
+            current = match parent {
+                Some(p) if visited.insert(p.clone()) => {
+                    ancestors.push(p.clone());
+                    Some(p)
+                }
+                _ => None,
+            };
+        }
+        ancestors.into_iter().map(|b| Uuid::from_slice(&b).map_err(VoiceError::from)).collect()
+    }
+
+    fn tags_by_ids(&self, ids: &[Uuid]) -> VoiceResult<Vec<TagRow>> {
+        let tags = ids
+            .iter()
+            .map(|id| self.get_tag(&id.simple().to_string()))
+            .collect::<VoiceResult<Vec<Option<TagRow>>>>()?;
+        Ok(tags.into_iter().flatten().collect())
+    }
+
+    /// Notes tagged with at least one of `tag_ids` (an "any of" join, unlike
+    /// [`Self::filter_notes`]'s "all of" semantics), used to resolve a closed tag
+    /// subtree to the notes reachable under it.
+    fn notes_tagged_with_any(&self, tag_ids: &[String]) -> VoiceResult<Vec<NoteRow>> {
+        if tag_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let tag_uuids = validation::validate_tag_ids(tag_ids)?;
+        let all_notes = self.get_all_notes()?;
+
+        let mut matching = Vec::new();
+        'note: for note in all_notes {
+            let note_id = validation::validate_note_id(&note.id)?;
+            for tag_uuid in &tag_uuids {
+                let has_tag: Option<i64> = self
+                    .conn
+                    .query_row(
+                        "SELECT 1 FROM note_tags WHERE note_id = ? AND tag_id = ? AND deleted_at IS NULL",
+                        rusqlite::params![note_id.as_bytes().to_vec(), tag_uuid.as_bytes().to_vec()],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                if has_tag.is_some() {
+                    matching.push(note);
+                    continue 'note;
+                }
+            }
+        }
+        Ok(matching)
+    }
+
+    /// Recursive graph query over the tag hierarchy: a small fixed-point evaluator that
+    /// expands a working set seeded with `tag_id` along the `parent_id` edge (or its
+    /// reverse) until no new ids are added, then optionally joins the closed tag set
+    /// against `note_tags`. This is what turns `filter_notes`/`search_notes`'s flat tag
+    /// matching into true hierarchical subtree search.
+    ///
+    /// `mode` is one of `"descendants"`, `"ancestors"`, or `"notes_in_subtree"`.
+    pub fn query_tag_graph(&self, tag_id: &str, mode: &str) -> VoiceResult<TagGraphResult> {
+        let start = validation::validate_tag_id(tag_id)?;
+        match mode {
+            "descendants" => Ok(TagGraphResult::Tags(self.tags_by_ids(&self.tag_descendant_ids(&start)?)?)),
+            "ancestors" => Ok(TagGraphResult::Tags(self.tags_by_ids(&self.tag_ancestor_ids(&start)?)?)),
+            "notes_in_subtree" => {
+                let mut subtree = self.tag_descendant_ids(&start)?;
+                subtree.push(start);
+                let hex_ids: Vec<String> = subtree.iter().map(|id| id.simple().to_string()).collect();
+                Ok(TagGraphResult::Notes(self.notes_tagged_with_any(&hex_ids)?))
+            }
+            other => Err(VoiceError::validation("mode", format!("unknown tag graph mode: {other}"))),
+        }
+    }
+
+    pub fn rename_tag(&self, tag_id: &str, new_name: &str) -> VoiceResult<bool> {
+        validation::validate_tag_name(new_name)?;
+        let uuid = validation::validate_tag_id(tag_id)?;
+        let id_bytes = uuid.as_bytes().to_vec();
+        let existing: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT version_vector, hlc FROM tags WHERE id = ?",
+                rusqlite::params![id_bytes],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((existing_vector, existing_hlc)) = existing else {
+            return Ok(false);
+        };
+        let vector = self.local_vector_bump(Some(&existing_vector));
+        let hlc = self.local_hlc_tick(Some(&existing_hlc));
+        let now = Utc::now().to_rfc3339();
+        let updated = self.conn.execute(
+            "UPDATE tags SET name = ?, modified_at = ?, version_vector = ?, hlc = ? WHERE id = ?",
+            rusqlite::params![new_name, now, vector, hlc, id_bytes],
+        )?;
+        self.merkle_touch(&id_bytes)?;
+        Ok(updated > 0)
+    }
+
+    pub fn delete_tag(&self, tag_id: &str) -> VoiceResult<bool> {
+        let uuid = validation::validate_tag_id(tag_id)?;
+        let id_bytes = uuid.as_bytes().to_vec();
+        let updated = self.conn.execute("DELETE FROM tags WHERE id = ?", rusqlite::params![id_bytes])?;
+        self.conn
+            .execute("DELETE FROM note_tags WHERE tag_id = ?", rusqlite::params![id_bytes])?;
+        Ok(updated > 0)
+    }
+
+    // ------------------------------------------------------------------
+    // Note <-> Tag associations
+    // ------------------------------------------------------------------
+
+    pub fn add_tag_to_note(&self, note_id: &str, tag_id: &str) -> VoiceResult<bool> {
+        let note_uuid = validation::validate_note_id(note_id)?;
+        let tag_uuid = validation::validate_tag_id(tag_id)?;
+        let now = Utc::now().to_rfc3339();
+        let vector = self.local_vector_bump(None);
+        let hlc = self.local_hlc_tick(None);
+        let updated = self.conn.execute(
+            "INSERT OR IGNORE INTO note_tags (note_id, tag_id, created_at, version_vector, hlc) VALUES (?, ?, ?, ?, ?)",
+            rusqlite::params![note_uuid.as_bytes().to_vec(), tag_uuid.as_bytes().to_vec(), now, vector, hlc],
+        )?;
+        if updated > 0 {
+            // note_tags has no single-UUID identity of its own, so it's partitioned
+            // by its note's ID - the same entity a peer already navigates by.
+            self.merkle_touch(note_uuid.as_bytes())?;
+        }
+        Ok(updated > 0)
+    }
+
+    pub fn remove_tag_from_note(&self, note_id: &str, tag_id: &str) -> VoiceResult<bool> {
+        let note_uuid = validation::validate_note_id(note_id)?;
+        let tag_uuid = validation::validate_tag_id(tag_id)?;
+        let note_bytes = note_uuid.as_bytes().to_vec();
+        let tag_bytes = tag_uuid.as_bytes().to_vec();
+        let existing: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT version_vector, hlc FROM note_tags WHERE note_id = ? AND tag_id = ? AND deleted_at IS NULL",
+                rusqlite::params![note_bytes, tag_bytes],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((existing_vector, existing_hlc)) = existing else {
+            return Ok(false);
+        };
+        let vector = self.local_vector_bump(Some(&existing_vector));
+        let hlc = self.local_hlc_tick(Some(&existing_hlc));
+        let now = Utc::now().to_rfc3339();
+        let updated = self.conn.execute(
+            "UPDATE note_tags SET modified_at = ?, deleted_at = ?, version_vector = ?, hlc = ? \
+             WHERE note_id = ? AND tag_id = ?",
+            rusqlite::params![now, now, vector, hlc, note_uuid.as_bytes().to_vec(), tag_uuid.as_bytes().to_vec()],
+        )?;
+        if updated > 0 {
+            self.merkle_touch(note_uuid.as_bytes())?;
+        }
+        Ok(updated > 0)
+    }
+
+    pub fn get_note_tags(&self, note_id: &str) -> VoiceResult<Vec<TagRow>> {
+        let note_uuid = validation::validate_note_id(note_id)?;
+        let mut stmt = self.conn.prepare(
+            "SELECT tags.id, tags.name, tags.parent_id, tags.created_at, tags.modified_at \
+             FROM note_tags JOIN tags ON tags.id = note_tags.tag_id \
+             WHERE note_tags.note_id = ? AND note_tags.deleted_at IS NULL ORDER BY tags.name",
+        )?;
+        let rows = stmt
+            .query_map(rusqlite::params![note_uuid.as_bytes().to_vec()], |row| {
+                Ok((
+                    row.get::<_, Vec<u8>>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<Vec<u8>>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.into_iter()
+            .map(|(id, name, parent, created, modified)| Self::row_to_tag(id, name, parent, created, modified))
+            .collect()
+    }
+
+    // ------------------------------------------------------------------
+    // Sync: peer bookkeeping
+    // ------------------------------------------------------------------
+
+    pub fn get_peer_last_sync(&self, peer_device_id: &str) -> VoiceResult<Option<String>> {
+        let uuid = validation::validate_device_id(peer_device_id)?;
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT last_sync_at FROM sync_peers WHERE peer_id = ?",
+                rusqlite::params![uuid.as_bytes().to_vec()],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten())
+    }
+
+    pub fn update_peer_sync_time(&self, peer_device_id: &str, peer_name: Option<&str>) -> VoiceResult<()> {
+        let uuid = validation::validate_device_id(peer_device_id)?;
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO sync_peers (peer_id, peer_name, last_sync_at) VALUES (?, ?, ?) \
+             ON CONFLICT(peer_id) DO UPDATE SET last_sync_at = excluded.last_sync_at, \
+             peer_name = COALESCE(excluded.peer_name, sync_peers.peer_name)",
+            rusqlite::params![uuid.as_bytes().to_vec(), peer_name, now],
+        )?;
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Sync: raw data + apply
+    // ------------------------------------------------------------------
+
+    pub fn get_note_raw(&self, note_id: &str) -> VoiceResult<Option<HashMap<String, serde_json::Value>>> {
+        let uuid = validation::validate_note_id(note_id)?;
+        let row = self
+            .conn
+            .query_row(
+                "SELECT created_at, content, modified_at, deleted_at, version_vector, hlc FROM notes WHERE id = ?",
+                rusqlite::params![uuid.as_bytes().to_vec()],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, String>(4)?,
+                        row.get::<_, String>(5)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        Ok(row.map(|(created_at, content, modified_at, deleted_at, version_vector, hlc)| {
+            let mut map = HashMap::new();
+            map.insert("created_at".to_string(), serde_json::json!(created_at));
+            map.insert("content".to_string(), serde_json::json!(content));
+            map.insert("modified_at".to_string(), serde_json::json!(modified_at));
+            map.insert("deleted_at".to_string(), serde_json::json!(deleted_at));
+            map.insert("version_vector".to_string(), serde_json::json!(VersionVector::from_json(&version_vector).0));
+            map.insert("hlc".to_string(), serde_json::json!(HlcStamp::from_json(&hlc)));
+            map
+        }))
+    }
+
+    pub fn get_tag_raw(&self, tag_id: &str) -> VoiceResult<Option<HashMap<String, serde_json::Value>>> {
+        let uuid = validation::validate_tag_id(tag_id)?;
+        let row = self
+            .conn
+            .query_row(
+                "SELECT name, parent_id, created_at, modified_at, version_vector, hlc FROM tags WHERE id = ?",
+                rusqlite::params![uuid.as_bytes().to_vec()],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, Option<Vec<u8>>>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, String>(4)?,
+                        row.get::<_, String>(5)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        match row {
+            Some((name, parent_id_bytes, created_at, modified_at, version_vector, hlc)) => {
+                let parent_id = parent_id_bytes.map(|b| validation::uuid_bytes_to_hex(&b)).transpose()?;
+                let mut map = HashMap::new();
+                map.insert("name".to_string(), serde_json::json!(name));
+                map.insert("parent_id".to_string(), serde_json::json!(parent_id));
+                map.insert("created_at".to_string(), serde_json::json!(created_at));
+                map.insert("modified_at".to_string(), serde_json::json!(modified_at));
+                map.insert("version_vector".to_string(), serde_json::json!(VersionVector::from_json(&version_vector).0));
+                map.insert("hlc".to_string(), serde_json::json!(HlcStamp::from_json(&hlc)));
+                Ok(Some(map))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_note_tag_raw(&self, note_id: &str, tag_id: &str) -> VoiceResult<Option<HashMap<String, serde_json::Value>>> {
+        let note_uuid = validation::validate_note_id(note_id)?;
+        let tag_uuid = validation::validate_tag_id(tag_id)?;
+        let row = self
+            .conn
+            .query_row(
+                "SELECT created_at, modified_at, deleted_at, version_vector, hlc FROM note_tags WHERE note_id = ? AND tag_id = ?",
+                rusqlite::params![note_uuid.as_bytes().to_vec(), tag_uuid.as_bytes().to_vec()],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        Ok(row.map(|(created_at, modified_at, deleted_at, version_vector, hlc)| {
+            let mut map = HashMap::new();
+            map.insert("created_at".to_string(), serde_json::json!(created_at));
+            map.insert("modified_at".to_string(), serde_json::json!(modified_at));
+            map.insert("deleted_at".to_string(), serde_json::json!(deleted_at));
+            map.insert("version_vector".to_string(), serde_json::json!(VersionVector::from_json(&version_vector).0));
+            map.insert("hlc".to_string(), serde_json::json!(HlcStamp::from_json(&hlc)));
+            map
+        }))
+    }
+
+    /// Extract the version vector a sync peer attached to a change's data payload,
+    /// defaulting to an empty vector for peers that don't send one yet.
+    fn incoming_vector(data: &serde_json::Value) -> VersionVector {
+        data.get("version_vector")
+            .and_then(|v| serde_json::from_value::<HashMap<String, u64>>(v.clone()).ok())
+            .map(VersionVector)
+            .unwrap_or_default()
+    }
+
+    // ------------------------------------------------------------------
+    // Merkle anti-entropy tree
+    // ------------------------------------------------------------------
+
+    /// The `(entity_id, hlc)` pairs of every note, tag, and note_tag whose
+    /// partition key starts with `prefix` - the contents of one Merkle leaf
+    /// bucket. note_tags have no single-UUID identity, so they're keyed by
+    /// their note's ID, same as every other entity_id a peer navigates by.
+    fn merkle_leaf_entries(&self, prefix: &[u8]) -> VoiceResult<Vec<(String, HlcStamp)>> {
+        let plen = prefix.len() as i64;
+        let mut entries = Vec::new();
+
+        let mut stmt = self.conn.prepare("SELECT id, hlc FROM notes WHERE substr(id, 1, ?) = ?")?;
+        let rows = stmt
+            .query_map(rusqlite::params![plen, prefix], |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        for (id_bytes, hlc) in rows {
+            entries.push((validation::uuid_bytes_to_hex(&id_bytes)?, HlcStamp::from_json(&hlc)));
+        }
+
+        let mut stmt = self.conn.prepare("SELECT id, hlc FROM tags WHERE substr(id, 1, ?) = ?")?;
+        let rows = stmt
+            .query_map(rusqlite::params![plen, prefix], |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        for (id_bytes, hlc) in rows {
+            entries.push((validation::uuid_bytes_to_hex(&id_bytes)?, HlcStamp::from_json(&hlc)));
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT note_id, tag_id, hlc FROM note_tags WHERE substr(note_id, 1, ?) = ?")?;
+        let rows = stmt
+            .query_map(rusqlite::params![plen, prefix], |row| {
+                Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?, row.get::<_, String>(2)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        for (note_id_bytes, tag_id_bytes, hlc) in rows {
+            let entity_id = format!(
+                "{}:{}",
+                validation::uuid_bytes_to_hex(&note_id_bytes)?,
+                validation::uuid_bytes_to_hex(&tag_id_bytes)?
+            );
+            entries.push((entity_id, HlcStamp::from_json(&hlc)));
+        }
+
+        Ok(entries)
+    }
+
+    fn merkle_bucket_hash(&self, prefix: &[u8]) -> VoiceResult<Option<[u8; 32]>> {
+        let raw: Option<Vec<u8>> = self
+            .conn
+            .query_row("SELECT hash FROM merkle_buckets WHERE prefix = ?", rusqlite::params![prefix], |row| row.get(0))
+            .optional()?;
+        Ok(raw.map(|hash| {
+            let mut array = [0u8; 32];
+            array.copy_from_slice(&hash);
+            array
+        }))
+    }
+
+    /// Recompute the leaf bucket `id_bytes` falls into, then its one-byte branch
+    /// parent, and persist both. Called after every note/tag/note_tag write so
+    /// the tree stays correct without ever re-scanning the whole dataset.
+    fn merkle_touch(&self, id_bytes: &[u8]) -> VoiceResult<()> {
+        let leaf_prefix = &id_bytes[..merkle::LEAF_PREFIX_LEN];
+        let mut entries = self.merkle_leaf_entries(leaf_prefix)?;
+        let hash = merkle::leaf_hash(&mut entries);
+        self.conn.execute(
+            "INSERT INTO merkle_buckets (prefix, hash) VALUES (?, ?) \
+             ON CONFLICT(prefix) DO UPDATE SET hash = excluded.hash",
+            rusqlite::params![leaf_prefix, hash.to_vec()],
+        )?;
+
+        let branch_prefix = &leaf_prefix[..1];
+        let mut stmt = self
+            .conn
+            .prepare("SELECT prefix, hash FROM merkle_buckets WHERE length(prefix) = ? AND substr(prefix, 1, 1) = ?")?;
+        let children: Vec<(u8, [u8; 32])> = stmt
+            .query_map(rusqlite::params![merkle::LEAF_PREFIX_LEN as i64, branch_prefix], |row| {
+                Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|(child_prefix, hash)| {
+                let mut array = [0u8; 32];
+                array.copy_from_slice(&hash);
+                (child_prefix[1], array)
+            })
+            .collect();
+        let branch_hash = merkle::branch_hash(children);
+        self.conn.execute(
+            "INSERT INTO merkle_buckets (prefix, hash) VALUES (?, ?) \
+             ON CONFLICT(prefix) DO UPDATE SET hash = excluded.hash",
+            rusqlite::params![branch_prefix, branch_hash.to_vec()],
+        )?;
+        Ok(())
+    }
+
+    /// Fetch the Merkle node at `prefix` (0, 1, or [`merkle::LEAF_PREFIX_LEN`] bytes)
+    /// for the `/sync/merkle` handshake: its own hash plus the hashes of whichever
+    /// immediate children currently exist. The root and branch nodes are derived
+    /// on the fly from the handful of rows below them - `merkle_touch` is what
+    /// keeps those rows current without a full rescan.
+    pub fn merkle_node(&self, prefix: &[u8]) -> VoiceResult<(Vec<u8>, Vec<(u8, Vec<u8>)>)> {
+        if prefix.len() >= merkle::LEAF_PREFIX_LEN {
+            let hash = self.merkle_bucket_hash(prefix)?.unwrap_or([0u8; 32]);
+            return Ok((hash.to_vec(), Vec::new()));
+        }
+
+        let child_len = (prefix.len() + 1) as i64;
+        let mut stmt = self
+            .conn
+            .prepare("SELECT prefix, hash FROM merkle_buckets WHERE length(prefix) = ? AND substr(prefix, 1, ?) = ?")?;
+        let children: Vec<(u8, [u8; 32])> = stmt
+            .query_map(rusqlite::params![child_len, prefix.len() as i64, prefix], |row| {
+                Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|(child_prefix, hash)| {
+                let mut array = [0u8; 32];
+                array.copy_from_slice(&hash);
+                (*child_prefix.last().unwrap(), array)
+            })
+            .collect();
+        let hash = merkle::branch_hash(children.clone());
+        Ok((hash.to_vec(), children.into_iter().map(|(byte, hash)| (byte, hash.to_vec())).collect()))
+    }
+
+    // ------------------------------------------------------------------
+    // Content-defined chunking
+    // ------------------------------------------------------------------
+
+    /// Split `content` into content-defined chunks (see [`chunking::chunk_bytes`]), diff
+    /// the result against whatever `note_chunks` last recorded for `note_id`, and settle
+    /// both tables to match: a newly-referenced chunk is inserted into `chunks` (or has its
+    /// `refcount` bumped if some other note already stored it) and a chunk `note_id` no
+    /// longer uses is released via [`Self::release_content_chunks`]. Returns the ordered
+    /// hex-encoded hash list that reconstructs `content` via [`Self::reassemble_chunks`].
+    /// Safe to call repeatedly with the same content - an unchanged chunk set is a no-op.
+    pub fn store_content_chunks(&self, note_id: &str, content: &str) -> VoiceResult<Vec<String>> {
+        let uuid = validation::validate_note_id(note_id)?;
+        let note_id_bytes = uuid.as_bytes().to_vec();
+
+        let pieces: Vec<([u8; 32], &[u8])> = chunking::chunk_bytes(content.as_bytes())
+            .into_iter()
+            .map(|piece| (chunking::chunk_hash(piece), piece))
+            .collect();
+        let hashes: Vec<String> = pieces.iter().map(|(hash, _)| merkle::to_hex(hash)).collect();
+        let current: HashSet<Vec<u8>> = pieces.iter().map(|(hash, _)| hash.to_vec()).collect();
+
+        let previous: HashSet<Vec<u8>> = {
+            let mut stmt = self.conn.prepare("SELECT DISTINCT hash FROM note_chunks WHERE note_id = ?")?;
+            stmt.query_map(rusqlite::params![note_id_bytes], |row| row.get::<_, Vec<u8>>(0))?
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .collect()
+        };
+
+        for (hash, bytes) in &pieces {
+            if !previous.contains(hash.as_slice()) {
+                self.conn.execute(
+                    "INSERT INTO chunks (hash, data, refcount) VALUES (?, ?, 1) \
+                     ON CONFLICT(hash) DO UPDATE SET refcount = refcount + 1",
+                    rusqlite::params![hash.to_vec(), bytes.to_vec()],
+                )?;
+            }
+        }
+
+        self.conn
+            .execute("DELETE FROM note_chunks WHERE note_id = ?", rusqlite::params![note_id_bytes])?;
+        for (ordinal, (hash, _)) in pieces.iter().enumerate() {
+            self.conn.execute(
+                "INSERT INTO note_chunks (note_id, ordinal, hash) VALUES (?, ?, ?)",
+                rusqlite::params![note_id_bytes, ordinal as i64, hash.to_vec()],
+            )?;
+        }
+
+        let released: Vec<String> = previous
+            .iter()
+            .filter(|hash| !current.contains(hash.as_slice()))
+            .map(|hash| merkle::to_hex(hash))
+            .collect();
+        self.release_content_chunks(&released)?;
+
+        Ok(hashes)
+    }
+
+    /// Hex-encoded chunk hashes [`Self::store_content_chunks`] last recorded for `note_id`,
+    /// in the order that reconstructs its content via [`Self::reassemble_chunks`]. Empty if
+    /// `note_id` hasn't been chunked yet - a note written before this table existed, until
+    /// it's next saved.
+    pub fn note_chunk_hashes(&self, note_id: &str) -> VoiceResult<Vec<String>> {
+        let uuid = validation::validate_note_id(note_id)?;
+        let note_id_bytes = uuid.as_bytes().to_vec();
+        let mut stmt = self
+            .conn
+            .prepare("SELECT hash FROM note_chunks WHERE note_id = ? ORDER BY ordinal")?;
+        let hashes = stmt
+            .query_map(rusqlite::params![note_id_bytes], |row| row.get::<_, Vec<u8>>(0))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|hash| merkle::to_hex(&hash))
+            .collect();
+        Ok(hashes)
+    }
+
+    /// Decrement the refcount of each chunk in `hashes` - e.g. because the note that
+    /// referenced them was edited or deleted - deleting any chunk whose refcount reaches
+    /// zero so `chunks` doesn't grow unbounded with content nothing references anymore.
+    pub fn release_content_chunks(&self, hashes: &[String]) -> VoiceResult<()> {
+        for hash_hex in hashes {
+            let Some(hash) = merkle::from_hex(hash_hex) else { continue };
+            self.conn
+                .execute("UPDATE chunks SET refcount = refcount - 1 WHERE hash = ?", rusqlite::params![hash.clone()])?;
+            self.conn.execute("DELETE FROM chunks WHERE hash = ? AND refcount <= 0", rusqlite::params![hash])?;
+        }
+        Ok(())
+    }
+
+    /// Fetch one chunk's bytes by its hex-encoded hash, for `/sync/chunks` to serve to a
+    /// peer that's missing it. `None` if we don't have it either.
+    pub fn get_chunk(&self, hash_hex: &str) -> VoiceResult<Option<Vec<u8>>> {
+        let Some(hash) = merkle::from_hex(hash_hex) else { return Ok(None) };
+        Ok(self
+            .conn
+            .query_row("SELECT data FROM chunks WHERE hash = ?", rusqlite::params![hash], |row| row.get(0))
+            .optional()?)
+    }
+
+    /// Hashes from `hashes` that [`Self::get_chunk`] wouldn't find locally - what a peer
+    /// needs to actually request over `/sync/chunks` rather than assume it already has.
+    pub fn missing_chunks(&self, hashes: &[String]) -> VoiceResult<Vec<String>> {
+        let mut missing = Vec::new();
+        for hash_hex in hashes {
+            if self.get_chunk(hash_hex)?.is_none() {
+                missing.push(hash_hex.clone());
+            }
+        }
+        Ok(missing)
+    }
+
+    /// Record a chunk's bytes fetched from a peer via `/sync/chunks`, with no note
+    /// referencing it yet - [`Self::store_content_chunks`] is what acquires a reference
+    /// (and bumps `refcount`) once the note it belongs to is actually saved locally.
+    pub fn ingest_chunk(&self, hash_hex: &str, bytes: &[u8]) -> VoiceResult<()> {
+        let Some(hash) = merkle::from_hex(hash_hex) else {
+            return Err(VoiceError::sync(format!("invalid chunk hash {hash_hex}")));
+        };
+        self.conn.execute(
+            "INSERT OR IGNORE INTO chunks (hash, data, refcount) VALUES (?, ?, 0)",
+            rusqlite::params![hash, bytes],
+        )?;
+        Ok(())
+    }
+
+    /// Reassemble a note's content from its ordered chunk hash list, as received over
+    /// `/sync/changes`. Errors if a hash isn't present locally - the caller is expected
+    /// to have pulled every hash it was missing via `/sync/chunks` first.
+    pub fn reassemble_chunks(&self, hashes: &[String]) -> VoiceResult<String> {
+        let mut bytes = Vec::new();
+        for hash_hex in hashes {
+            let chunk = self
+                .get_chunk(hash_hex)?
+                .ok_or_else(|| VoiceError::sync(format!("missing chunk {hash_hex}")))?;
+            bytes.extend_from_slice(&chunk);
+        }
+        String::from_utf8(bytes).map_err(|_| VoiceError::database_op("reassembled chunk content was not valid UTF-8"))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_sync_note(
+        &self,
+        note_id: &str,
+        created_at: &str,
+        content: &str,
+        modified_at: Option<&str>,
+        deleted_at: Option<&str>,
+        remote_vector: &VersionVector,
+        remote_hlc: &HlcStamp,
+    ) -> VoiceResult<bool> {
+        let uuid = validation::validate_note_id(note_id)?;
+        let id_bytes = uuid.as_bytes().to_vec();
+        let existing: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT version_vector, hlc FROM notes WHERE id = ?",
+                rusqlite::params![id_bytes],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let (merged_vector, merged_hlc) = match existing {
+            Some((raw_vector, raw_hlc)) => (
+                VersionVector::from_json(&raw_vector).merge(remote_vector),
+                HlcStamp::from_json(&raw_hlc).max(remote_hlc.clone()),
+            ),
+            None => (remote_vector.clone(), remote_hlc.clone()),
+        };
+        self.conn.execute(
+            "INSERT INTO notes (id, created_at, content, modified_at, deleted_at, version_vector, hlc) \
+             VALUES (?, ?, ?, ?, ?, ?, ?) \
+             ON CONFLICT(id) DO UPDATE SET content = excluded.content, modified_at = excluded.modified_at, \
+             deleted_at = excluded.deleted_at, version_vector = excluded.version_vector, hlc = excluded.hlc",
+            rusqlite::params![id_bytes, created_at, content, modified_at, deleted_at, merged_vector.to_json(), merged_hlc.to_json()],
+        )?;
+        self.merkle_touch(&id_bytes)?;
+        self.store_content_chunks(note_id, content)?;
+        Ok(true)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_sync_tag(
+        &self,
+        tag_id: &str,
+        name: &str,
+        parent_id: Option<&str>,
+        created_at: &str,
+        modified_at: Option<&str>,
+        remote_vector: &VersionVector,
+        remote_hlc: &HlcStamp,
+    ) -> VoiceResult<bool> {
+        let uuid = validation::validate_tag_id(tag_id)?;
+        let id_bytes = uuid.as_bytes().to_vec();
+        let parent_bytes = parent_id
+            .map(validation::validate_tag_id)
+            .transpose()?
+            .map(|u| u.as_bytes().to_vec());
+        let existing: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT version_vector, hlc FROM tags WHERE id = ?",
+                rusqlite::params![id_bytes],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let (merged_vector, merged_hlc) = match existing {
+            Some((raw_vector, raw_hlc)) => (
+                VersionVector::from_json(&raw_vector).merge(remote_vector),
+                HlcStamp::from_json(&raw_hlc).max(remote_hlc.clone()),
+            ),
+            None => (remote_vector.clone(), remote_hlc.clone()),
+        };
+        self.conn.execute(
+            "INSERT INTO tags (id, name, parent_id, created_at, modified_at, version_vector, hlc) \
+             VALUES (?, ?, ?, ?, ?, ?, ?) \
+             ON CONFLICT(id) DO UPDATE SET name = excluded.name, parent_id = excluded.parent_id, \
+             modified_at = excluded.modified_at, version_vector = excluded.version_vector, hlc = excluded.hlc",
+            rusqlite::params![id_bytes, name, parent_bytes, created_at, modified_at, merged_vector.to_json(), merged_hlc.to_json()],
+        )?;
+        self.merkle_touch(&id_bytes)?;
+        Ok(true)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_sync_note_tag(
+        &self,
+        note_id: &str,
+        tag_id: &str,
+        created_at: &str,
+        modified_at: Option<&str>,
+        deleted_at: Option<&str>,
+        remote_vector: &VersionVector,
+        remote_hlc: &HlcStamp,
+    ) -> VoiceResult<bool> {
+        let note_uuid = validation::validate_note_id(note_id)?;
+        let tag_uuid = validation::validate_tag_id(tag_id)?;
+        let note_bytes = note_uuid.as_bytes().to_vec();
+        let tag_bytes = tag_uuid.as_bytes().to_vec();
+        let existing: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT version_vector, hlc FROM note_tags WHERE note_id = ? AND tag_id = ?",
+                rusqlite::params![note_bytes, tag_bytes],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let (merged_vector, merged_hlc) = match existing {
+            Some((raw_vector, raw_hlc)) => (
+                VersionVector::from_json(&raw_vector).merge(remote_vector),
+                HlcStamp::from_json(&raw_hlc).max(remote_hlc.clone()),
+            ),
+            None => (remote_vector.clone(), remote_hlc.clone()),
+        };
+        self.conn.execute(
+            "INSERT INTO note_tags (note_id, tag_id, created_at, modified_at, deleted_at, version_vector, hlc) \
+             VALUES (?, ?, ?, ?, ?, ?, ?) \
+             ON CONFLICT(note_id, tag_id) DO UPDATE SET modified_at = excluded.modified_at, \
+             deleted_at = excluded.deleted_at, version_vector = excluded.version_vector, hlc = excluded.hlc",
+            rusqlite::params![note_uuid.as_bytes().to_vec(), tag_uuid.as_bytes().to_vec(), created_at, modified_at, deleted_at, merged_vector.to_json(), merged_hlc.to_json()],
+        )?;
+        self.merkle_touch(note_uuid.as_bytes())?;
+        Ok(true)
+    }
+
+    /// `prefix`, when set, scopes the scan to one Merkle bucket (see
+    /// [`crate::merkle`]) instead of the whole table - what a client passes once
+    /// `/sync/merkle` has told it which bucket actually diverged, in place of
+    /// (or alongside) the usual `since` cursor.
+    pub fn get_changes_since(
+        &self,
+        since: Option<&str>,
+        limit: i64,
+        prefix: Option<&[u8]>,
+    ) -> VoiceResult<(Vec<SyncChange>, Option<String>)> {
+        let conn = &self.conn;
+        let mut changes = Vec::new();
+        let mut latest_timestamp: Option<String> = None;
+
+        let notes_query = match (since.is_some(), prefix.is_some()) {
+            (true, true) => {
+                "SELECT id, created_at, content, modified_at, deleted_at, version_vector, hlc FROM notes \
+                 WHERE (modified_at > ? OR (modified_at IS NULL AND created_at > ?)) AND substr(id, 1, ?) = ? \
+                 ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
+            }
+            (true, false) => {
+                "SELECT id, created_at, content, modified_at, deleted_at, version_vector, hlc FROM notes \
+                 WHERE modified_at > ? OR (modified_at IS NULL AND created_at > ?) \
+                 ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
+            }
+            (false, true) => {
+                "SELECT id, created_at, content, modified_at, deleted_at, version_vector, hlc FROM notes \
+                 WHERE substr(id, 1, ?) = ? ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
+            }
+            (false, false) => {
+                "SELECT id, created_at, content, modified_at, deleted_at, version_vector, hlc FROM notes \
+                 ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
+            }
+        };
+        let mut stmt = conn.prepare(notes_query)?;
+        let notes_rows: Vec<_> = match (since, prefix) {
+            (Some(ts), Some(p)) => stmt
+                .query_map(rusqlite::params![ts, ts, p.len() as i64, p, limit], Self::read_note_change_row)?
+                .collect::<Result<Vec<_>, _>>()?,
+            (Some(ts), None) => stmt
+                .query_map(rusqlite::params![ts, ts, limit], Self::read_note_change_row)?
+                .collect::<Result<Vec<_>, _>>()?,
+            (None, Some(p)) => stmt
+                .query_map(rusqlite::params![p.len() as i64, p, limit], Self::read_note_change_row)?
+                .collect::<Result<Vec<_>, _>>()?,
+            (None, None) => stmt
+                .query_map(rusqlite::params![limit], Self::read_note_change_row)?
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+
+        for (id_bytes, created_at, content, modified_at, deleted_at, version_vector, hlc) in notes_rows {
+            let id_hex = validation::uuid_bytes_to_hex(&id_bytes)?;
+            let operation = if deleted_at.is_some() {
+                "delete"
+            } else if modified_at.is_some() {
+                "update"
+            } else {
+                "create"
+            };
+            let timestamp = modified_at
+                .clone()
+                .or_else(|| deleted_at.clone())
+                .unwrap_or_else(|| created_at.clone());
+            if latest_timestamp.is_none() || latest_timestamp.as_ref() < Some(&timestamp) {
+                latest_timestamp = Some(timestamp.clone());
+            }
+            // Sent instead of `content`: the peer reassembles it from these chunk hashes
+            // (see `crate::sync_server::apply_note_change`) after fetching via `/sync/chunks`
+            // whichever ones it's missing, so an edit retransmits only the chunks it
+            // actually touched rather than the note's content in full.
+            let chunk_hashes = self.note_chunk_hashes(&id_hex)?;
+            let chunk_hashes = if chunk_hashes.is_empty() && !content.is_empty() {
+                // Backfill a note saved before chunking was wired into create/update/apply,
+                // so every future pull of it - not just its next local edit - skips `content`.
+                self.store_content_chunks(&id_hex, &content)?
+            } else {
+                chunk_hashes
+            };
+            changes.push(SyncChange {
+                entity_type: "note".to_string(),
+                entity_id: id_hex.clone(),
+                operation: operation.to_string(),
+                data: serde_json::json!({
+                    "id": id_hex,
+                    "created_at": created_at,
+                    "modified_at": modified_at,
+                    "deleted_at": deleted_at,
+                    "version_vector": VersionVector::from_json(&version_vector).0,
+                    "hlc": HlcStamp::from_json(&hlc),
+                    "chunk_hashes": chunk_hashes,
+                }),
+                timestamp,
+                device_id: String::new(),
+                device_name: None,
+            });
+        }
+
+        let remaining = limit - changes.len() as i64;
+        if remaining > 0 {
+            let tags_query = match (since.is_some(), prefix.is_some()) {
+                (true, true) => {
+                    "SELECT id, name, parent_id, created_at, modified_at, version_vector, hlc FROM tags \
+                     WHERE (modified_at > ? OR (modified_at IS NULL AND created_at > ?)) AND substr(id, 1, ?) = ? \
+                     ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
+                }
+                (true, false) => {
+                    "SELECT id, name, parent_id, created_at, modified_at, version_vector, hlc FROM tags \
+                     WHERE modified_at > ? OR (modified_at IS NULL AND created_at > ?) \
+                     ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
+                }
+                (false, true) => {
+                    "SELECT id, name, parent_id, created_at, modified_at, version_vector, hlc FROM tags \
+                     WHERE substr(id, 1, ?) = ? ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
+                }
+                (false, false) => {
+                    "SELECT id, name, parent_id, created_at, modified_at, version_vector, hlc FROM tags \
+                     ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
+                }
+            };
+            let mut stmt = conn.prepare(tags_query)?;
+            let tag_rows: Vec<_> = match (since, prefix) {
+                (Some(ts), Some(p)) => stmt
+                    .query_map(rusqlite::params![ts, ts, p.len() as i64, p, remaining], Self::read_tag_change_row)?
+                    .collect::<Result<Vec<_>, _>>()?,
+                (Some(ts), None) => stmt
+                    .query_map(rusqlite::params![ts, ts, remaining], Self::read_tag_change_row)?
+                    .collect::<Result<Vec<_>, _>>()?,
+                (None, Some(p)) => stmt
+                    .query_map(rusqlite::params![p.len() as i64, p, remaining], Self::read_tag_change_row)?
+                    .collect::<Result<Vec<_>, _>>()?,
+                (None, None) => stmt
+                    .query_map(rusqlite::params![remaining], Self::read_tag_change_row)?
+                    .collect::<Result<Vec<_>, _>>()?,
+            };
+
+            for (id_bytes, name, parent_id_bytes, created_at, modified_at, version_vector, hlc) in tag_rows {
+                let id_hex = validation::uuid_bytes_to_hex(&id_bytes)?;
+                let parent_id_hex = parent_id_bytes.map(|b| validation::uuid_bytes_to_hex(&b)).transpose()?;
+                let operation = if modified_at.is_some() { "update" } else { "create" };
+                let timestamp = modified_at.clone().unwrap_or_else(|| created_at.clone());
+                if latest_timestamp.is_none() || latest_timestamp.as_ref() < Some(&timestamp) {
+                    latest_timestamp = Some(timestamp.clone());
+                }
+                changes.push(SyncChange {
+                    entity_type: "tag".to_string(),
+                    entity_id: id_hex.clone(),
+                    operation: operation.to_string(),
+                    data: serde_json::json!({
+                        "id": id_hex,
+                        "name": name,
+                        "parent_id": parent_id_hex,
+                        "created_at": created_at,
+                        "modified_at": modified_at,
+                        "version_vector": VersionVector::from_json(&version_vector).0,
+                        "hlc": HlcStamp::from_json(&hlc),
+                    }),
+                    timestamp,
+                    device_id: String::new(),
+                    device_name: None,
+                });
+            }
+        }
+
+        let remaining = limit - changes.len() as i64;
+        if remaining > 0 {
+            let note_tags_query = match (since.is_some(), prefix.is_some()) {
+                (true, true) => {
+                    "SELECT note_id, tag_id, created_at, modified_at, deleted_at, version_vector, hlc FROM note_tags \
+                     WHERE (created_at > ? OR deleted_at > ? OR modified_at > ?) AND substr(note_id, 1, ?) = ? \
+                     ORDER BY COALESCE(modified_at, deleted_at, created_at) LIMIT ?"
+                }
+                (true, false) => {
+                    "SELECT note_id, tag_id, created_at, modified_at, deleted_at, version_vector, hlc FROM note_tags \
+                     WHERE created_at > ? OR deleted_at > ? OR modified_at > ? \
+                     ORDER BY COALESCE(modified_at, deleted_at, created_at) LIMIT ?"
+                }
+                (false, true) => {
+                    "SELECT note_id, tag_id, created_at, modified_at, deleted_at, version_vector, hlc FROM note_tags \
+                     WHERE substr(note_id, 1, ?) = ? ORDER BY COALESCE(modified_at, deleted_at, created_at) LIMIT ?"
+                }
+                (false, false) => {
+                    "SELECT note_id, tag_id, created_at, modified_at, deleted_at, version_vector, hlc FROM note_tags \
+                     ORDER BY COALESCE(modified_at, deleted_at, created_at) LIMIT ?"
+                }
+            };
+            let mut stmt = conn.prepare(note_tags_query)?;
+            let note_tag_rows: Vec<_> = match (since, prefix) {
+                (Some(ts), Some(p)) => stmt
+                    .query_map(rusqlite::params![ts, ts, ts, p.len() as i64, p, remaining], Self::read_note_tag_change_row)?
+                    .collect::<Result<Vec<_>, _>>()?,
+                (Some(ts), None) => stmt
+                    .query_map(rusqlite::params![ts, ts, ts, remaining], Self::read_note_tag_change_row)?
+                    .collect::<Result<Vec<_>, _>>()?,
+                (None, Some(p)) => stmt
+                    .query_map(rusqlite::params![p.len() as i64, p, remaining], Self::read_note_tag_change_row)?
+                    .collect::<Result<Vec<_>, _>>()?,
+                (None, None) => stmt
+                    .query_map(rusqlite::params![remaining], Self::read_note_tag_change_row)?
+                    .collect::<Result<Vec<_>, _>>()?,
+            };
+
+            for (note_id_bytes, tag_id_bytes, created_at, modified_at, deleted_at, version_vector, hlc) in note_tag_rows {
+                let note_id_hex = validation::uuid_bytes_to_hex(&note_id_bytes)?;
+                let tag_id_hex = validation::uuid_bytes_to_hex(&tag_id_bytes)?;
+                let entity_id = format!("{}:{}", note_id_hex, tag_id_hex);
+                let operation = if deleted_at.is_some() {
+                    "delete"
+                } else if modified_at.is_some() {
+                    "update"
+                } else {
+                    "create"
+                };
+                let timestamp = modified_at
+                    .clone()
+                    .or_else(|| deleted_at.clone())
+                    .unwrap_or_else(|| created_at.clone());
+                if latest_timestamp.is_none() || latest_timestamp.as_ref() < Some(&timestamp) {
+                    latest_timestamp = Some(timestamp.clone());
+                }
+                changes.push(SyncChange {
+                    entity_type: "note_tag".to_string(),
+                    entity_id,
+                    operation: operation.to_string(),
+                    data: serde_json::json!({
+                        "note_id": note_id_hex,
+                        "tag_id": tag_id_hex,
+                        "created_at": created_at,
+                        "modified_at": modified_at,
+                        "deleted_at": deleted_at,
+                        "version_vector": VersionVector::from_json(&version_vector).0,
+                        "hlc": HlcStamp::from_json(&hlc),
+                    }),
+                    timestamp,
+                    device_id: String::new(),
+                    device_name: None,
+                });
+            }
+        }
+
+        Ok((changes, latest_timestamp))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn read_note_change_row(
+        row: &rusqlite::Row,
+    ) -> rusqlite::Result<(Vec<u8>, String, String, Option<String>, Option<String>, String, String)> {
+        Ok((
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+            row.get(6)?,
+        ))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn read_tag_change_row(
+        row: &rusqlite::Row,
+    ) -> rusqlite::Result<(Vec<u8>, String, Option<Vec<u8>>, String, Option<String>, String, String)> {
+        Ok((
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+            row.get(6)?,
+        ))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn read_note_tag_change_row(
+        row: &rusqlite::Row,
+    ) -> rusqlite::Result<(Vec<u8>, Vec<u8>, String, Option<String>, Option<String>, String, String)> {
+        Ok((
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+            row.get(6)?,
+        ))
+    }
+
+    pub fn get_full_dataset(&self) -> VoiceResult<serde_json::Value> {
+        let conn = &self.conn;
+
+        let mut notes = Vec::new();
+        let mut stmt =
+            conn.prepare("SELECT id, created_at, content, modified_at, deleted_at, version_vector, hlc FROM notes")?;
+        let note_rows = stmt.query_map([], Self::read_note_change_row)?;
+        for row in note_rows {
+            let (id_bytes, created_at, content, modified_at, deleted_at, version_vector, hlc) = row?;
+            let id_hex = validation::uuid_bytes_to_hex(&id_bytes)?;
+            notes.push(serde_json::json!({
+                "id": id_hex,
+                "created_at": created_at,
+                "content": content,
+                "modified_at": modified_at,
+                "deleted_at": deleted_at,
+                "version_vector": VersionVector::from_json(&version_vector).0,
+                "hlc": HlcStamp::from_json(&hlc),
+            }));
+        }
+
+        let mut tags = Vec::new();
+        let mut stmt =
+            conn.prepare("SELECT id, name, parent_id, created_at, modified_at, version_vector, hlc FROM tags")?;
+        let tag_rows = stmt.query_map([], Self::read_tag_change_row)?;
+        for row in tag_rows {
+            let (id_bytes, name, parent_id_bytes, created_at, modified_at, version_vector, hlc) = row?;
+            let id_hex = validation::uuid_bytes_to_hex(&id_bytes)?;
+            let parent_id_hex = parent_id_bytes.map(|b| validation::uuid_bytes_to_hex(&b)).transpose()?;
+            tags.push(serde_json::json!({
+                "id": id_hex,
+                "name": name,
+                "parent_id": parent_id_hex,
+                "created_at": created_at,
+                "modified_at": modified_at,
+                "version_vector": VersionVector::from_json(&version_vector).0,
+                "hlc": HlcStamp::from_json(&hlc),
+            }));
+        }
+
+        let mut note_tags = Vec::new();
+        let mut stmt = conn.prepare(
+            "SELECT note_id, tag_id, created_at, modified_at, deleted_at, version_vector, hlc FROM note_tags",
+        )?;
+        let note_tag_rows = stmt.query_map([], Self::read_note_tag_change_row)?;
+        for row in note_tag_rows {
+            let (note_id_bytes, tag_id_bytes, created_at, modified_at, deleted_at, version_vector, hlc) = row?;
+            let note_id_hex = validation::uuid_bytes_to_hex(&note_id_bytes)?;
+            let tag_id_hex = validation::uuid_bytes_to_hex(&tag_id_bytes)?;
+            note_tags.push(serde_json::json!({
+                "note_id": note_id_hex,
+                "tag_id": tag_id_hex,
+                "created_at": created_at,
+                "modified_at": modified_at,
+                "deleted_at": deleted_at,
+                "version_vector": VersionVector::from_json(&version_vector).0,
+                "hlc": HlcStamp::from_json(&hlc),
+            }));
+        }
+
+        Ok(serde_json::json!({
+            "notes": notes,
+            "tags": tags,
+            "note_tags": note_tags,
+        }))
+    }
+
+    // ------------------------------------------------------------------
+    // Columnar snapshot export/import (backup + device migration)
+    // ------------------------------------------------------------------
+
+    /// Export the full store as a columnar snapshot (see [`ColumnarSnapshot`]), a more
+    /// compact alternative to [`Self::get_full_dataset`] for backup/restore and migrating
+    /// between devices.
+    pub fn export_snapshot(&self) -> VoiceResult<ColumnarSnapshot> {
+        let conn = &self.conn;
+
+        let mut stmt =
+            conn.prepare("SELECT id, created_at, content, modified_at, deleted_at, version_vector, hlc FROM notes")?;
+        let notes_rows = stmt
+            .query_map([], Self::read_note_change_row)?
+            .map(|row| {
+                let (id_bytes, created_at, content, modified_at, deleted_at, version_vector, hlc) = row?;
+                let id_hex = validation::uuid_bytes_to_hex(&id_bytes)?;
+                Ok(vec![
+                    serde_json::json!(id_hex),
+                    serde_json::json!(created_at),
+                    serde_json::json!(content),
+                    serde_json::json!(modified_at),
+                    serde_json::json!(deleted_at),
+                    serde_json::json!(version_vector),
+                    serde_json::json!(hlc),
+                ])
+            })
+            .collect::<VoiceResult<Vec<_>>>()?;
+
+        let mut stmt =
+            conn.prepare("SELECT id, name, parent_id, created_at, modified_at, version_vector, hlc FROM tags")?;
+        let tags_rows = stmt
+            .query_map([], Self::read_tag_change_row)?
+            .map(|row| {
+                let (id_bytes, name, parent_id_bytes, created_at, modified_at, version_vector, hlc) = row?;
+                let id_hex = validation::uuid_bytes_to_hex(&id_bytes)?;
+                let parent_id_hex = parent_id_bytes.map(|b| validation::uuid_bytes_to_hex(&b)).transpose()?;
+                Ok(vec![
+                    serde_json::json!(id_hex),
+                    serde_json::json!(name),
+                    serde_json::json!(parent_id_hex),
+                    serde_json::json!(created_at),
+                    serde_json::json!(modified_at),
+                    serde_json::json!(version_vector),
+                    serde_json::json!(hlc),
+                ])
+            })
+            .collect::<VoiceResult<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT note_id, tag_id, created_at, modified_at, deleted_at, version_vector, hlc FROM note_tags",
+        )?;
+        let note_tags_rows = stmt
+            .query_map([], Self::read_note_tag_change_row)?
+            .map(|row| {
+                let (note_id_bytes, tag_id_bytes, created_at, modified_at, deleted_at, version_vector, hlc) = row?;
+                let note_id_hex = validation::uuid_bytes_to_hex(&note_id_bytes)?;
+                let tag_id_hex = validation::uuid_bytes_to_hex(&tag_id_bytes)?;
+                Ok(vec![
+                    serde_json::json!(note_id_hex),
+                    serde_json::json!(tag_id_hex),
+                    serde_json::json!(created_at),
+                    serde_json::json!(modified_at),
+                    serde_json::json!(deleted_at),
+                    serde_json::json!(version_vector),
+                    serde_json::json!(hlc),
+                ])
+            })
+            .collect::<VoiceResult<Vec<_>>>()?;
+
+        Ok(ColumnarSnapshot {
+            notes: ColumnarTable {
+                headers: NOTE_SNAPSHOT_HEADERS.iter().map(|h| h.to_string()).collect(),
+                rows: notes_rows,
+            },
+            tags: ColumnarTable {
+                headers: TAG_SNAPSHOT_HEADERS.iter().map(|h| h.to_string()).collect(),
+                rows: tags_rows,
+            },
+            note_tags: ColumnarTable {
+                headers: NOTE_TAG_SNAPSHOT_HEADERS.iter().map(|h| h.to_string()).collect(),
+                rows: note_tags_rows,
+            },
+        })
+    }
+
+    /// Restore `snapshot` into this store. With `merge = false`, replaces the entire
+    /// store inside one transaction (bulk backup restore). With `merge = true`, routes
+    /// every row through [`crate::sync_server::apply_incoming_change`], the same
+    /// version-vector-aware conflict path used by live peer sync, so a restore onto a
+    /// non-empty store raises conflicts instead of silently clobbering local changes.
+    pub fn import_snapshot(&self, snapshot: &ColumnarSnapshot, merge: bool) -> VoiceResult<ImportSummary> {
+        if merge {
+            self.import_snapshot_merge(snapshot)
+        } else {
+            self.import_snapshot_replace(snapshot)
+        }
+    }
+
+    fn import_snapshot_replace(&self, snapshot: &ColumnarSnapshot) -> VoiceResult<ImportSummary> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM note_tags", [])?;
+        tx.execute("DELETE FROM notes", [])?;
+        tx.execute("DELETE FROM tags", [])?;
+
+        for row in &snapshot.notes.rows {
+            let id = row[0].as_str().ok_or_else(|| VoiceError::database_op("snapshot note row missing id"))?;
+            let uuid = validation::validate_note_id(id)?;
+            let created_at = row[1].as_str().unwrap_or_default();
+            let content = row[2].as_str().unwrap_or_default();
+            let modified_at = row[3].as_str();
+            let deleted_at = row[4].as_str();
+            let version_vector = row[5].as_str().unwrap_or("{}");
+            let hlc = row.get(6).and_then(|v| v.as_str()).unwrap_or("{}");
+            tx.execute(
+                "INSERT INTO notes (id, created_at, content, modified_at, deleted_at, version_vector, hlc) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                rusqlite::params![uuid.as_bytes().to_vec(), created_at, content, modified_at, deleted_at, version_vector, hlc],
+            )?;
+        }
+
+        for row in &snapshot.tags.rows {
+            let id = row[0].as_str().ok_or_else(|| VoiceError::database_op("snapshot tag row missing id"))?;
+            let uuid = validation::validate_tag_id(id)?;
+            let name = row[1].as_str().unwrap_or_default();
+            let parent_bytes = row[2]
+                .as_str()
+                .map(validation::validate_tag_id)
+                .transpose()?
+                .map(|u| u.as_bytes().to_vec());
+            let created_at = row[3].as_str();
+            let modified_at = row[4].as_str();
+            let version_vector = row[5].as_str().unwrap_or("{}");
+            let hlc = row.get(6).and_then(|v| v.as_str()).unwrap_or("{}");
+            tx.execute(
+                "INSERT INTO tags (id, name, parent_id, created_at, modified_at, version_vector, hlc) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                rusqlite::params![uuid.as_bytes().to_vec(), name, parent_bytes, created_at, modified_at, version_vector, hlc],
+            )?;
+        }
+
+        for row in &snapshot.note_tags.rows {
+            let note_id = row[0].as_str().ok_or_else(|| VoiceError::database_op("snapshot note_tag row missing note_id"))?;
+            let tag_id = row[1].as_str().ok_or_else(|| VoiceError::database_op("snapshot note_tag row missing tag_id"))?;
+            let note_uuid = validation::validate_note_id(note_id)?;
+            let tag_uuid = validation::validate_tag_id(tag_id)?;
+            let created_at = row[2].as_str().unwrap_or_default();
+            let modified_at = row[3].as_str();
+            let deleted_at = row[4].as_str();
+            let version_vector = row[5].as_str().unwrap_or("{}");
+            let hlc = row.get(6).and_then(|v| v.as_str()).unwrap_or("{}");
+            tx.execute(
+                "INSERT INTO note_tags (note_id, tag_id, created_at, modified_at, deleted_at, version_vector, hlc) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                rusqlite::params![note_uuid.as_bytes().to_vec(), tag_uuid.as_bytes().to_vec(), created_at, modified_at, deleted_at, version_vector, hlc],
+            )?;
+        }
+
+        let summary = ImportSummary {
+            notes_applied: snapshot.notes.rows.len() as i64,
+            tags_applied: snapshot.tags.rows.len() as i64,
+            note_tags_applied: snapshot.note_tags.rows.len() as i64,
+            conflicts: 0,
+        };
+        tx.commit()?;
+        Ok(summary)
+    }
+
+    fn import_snapshot_merge(&self, snapshot: &ColumnarSnapshot) -> VoiceResult<ImportSummary> {
+        let mut summary = ImportSummary::default();
+
+        for row in &snapshot.notes.rows {
+            let id = row[0].as_str().ok_or_else(|| VoiceError::database_op("snapshot note row missing id"))?.to_string();
+            let change = SyncChange {
+                entity_type: "note".to_string(),
+                entity_id: id,
+                operation: "update".to_string(),
+                data: serde_json::json!({
+                    "created_at": row[1],
+                    "content": row[2],
+                    "modified_at": row[3],
+                    "deleted_at": row[4],
+                    "version_vector": VersionVector::from_json(row[5].as_str().unwrap_or("{}")).0,
+                    "hlc": HlcStamp::from_json(row.get(6).and_then(|v| v.as_str()).unwrap_or("{}")),
+                }),
+                timestamp: row[1].as_str().unwrap_or_default().to_string(),
+                device_id: String::new(),
+                device_name: None,
+            };
+            Self::apply_merged_change(&change, self, &mut summary.notes_applied, &mut summary.conflicts)?;
+        }
+
+        for row in &snapshot.tags.rows {
+            let id = row[0].as_str().ok_or_else(|| VoiceError::database_op("snapshot tag row missing id"))?.to_string();
+            let change = SyncChange {
+                entity_type: "tag".to_string(),
+                entity_id: id,
+                operation: "update".to_string(),
+                data: serde_json::json!({
+                    "name": row[1],
+                    "parent_id": row[2],
+                    "created_at": row[3],
+                    "modified_at": row[4],
+                    "version_vector": VersionVector::from_json(row[5].as_str().unwrap_or("{}")).0,
+                    "hlc": HlcStamp::from_json(row.get(6).and_then(|v| v.as_str()).unwrap_or("{}")),
+                }),
+                timestamp: row[3].as_str().unwrap_or_default().to_string(),
+                device_id: String::new(),
+                device_name: None,
+            };
+            Self::apply_merged_change(&change, self, &mut summary.tags_applied, &mut summary.conflicts)?;
+        }
+
+        for row in &snapshot.note_tags.rows {
+            let note_id = row[0].as_str().ok_or_else(|| VoiceError::database_op("snapshot note_tag row missing note_id"))?;
+            let tag_id = row[1].as_str().ok_or_else(|| VoiceError::database_op("snapshot note_tag row missing tag_id"))?;
+            let change = SyncChange {
+                entity_type: "note_tag".to_string(),
+                entity_id: format!("{}:{}", note_id, tag_id),
+                operation: "update".to_string(),
+                data: serde_json::json!({
+                    "created_at": row[2],
+                    "modified_at": row[3],
+                    "deleted_at": row[4],
+                    "version_vector": VersionVector::from_json(row[5].as_str().unwrap_or("{}")).0,
+                    "hlc": HlcStamp::from_json(row.get(6).and_then(|v| v.as_str()).unwrap_or("{}")),
+                }),
+                timestamp: row[2].as_str().unwrap_or_default().to_string(),
+                device_id: String::new(),
+                device_name: None,
+            };
+            Self::apply_merged_change(&change, self, &mut summary.note_tags_applied, &mut summary.conflicts)?;
+        }
+
+        Ok(summary)
+    }
+
+    fn apply_merged_change(change: &SyncChange, db: &Database, applied: &mut i64, conflicts: &mut i64) -> VoiceResult<()> {
+        match crate::sync_server::apply_incoming_change(db, change, None)? {
+            crate::sync_server::ApplyOutcome::Applied => *applied += 1,
+            crate::sync_server::ApplyOutcome::Conflict => *conflicts += 1,
+            crate::sync_server::ApplyOutcome::Skipped => {}
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Conflicts
+    // ------------------------------------------------------------------
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_note_content_conflict(
+        &self,
+        note_id: &str,
+        local_content: &str,
+        local_modified_at: &str,
+        remote_content: &str,
+        remote_modified_at: &str,
+        remote_device_id: Option<&str>,
+        remote_device_name: Option<&str>,
+    ) -> VoiceResult<String> {
+        conflicts::create_note_content_conflict(
+            &self.conn,
+            note_id,
+            local_content,
+            local_modified_at,
+            remote_content,
+            remote_modified_at,
+            remote_device_id,
+            remote_device_name,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_note_delete_conflict(
+        &self,
+        note_id: &str,
+        surviving_content: &str,
+        surviving_modified_at: &str,
+        surviving_device_id: Option<&str>,
+        deleted_content: Option<&str>,
+        deleted_at: &str,
+        deleting_device_id: Option<&str>,
+        deleting_device_name: Option<&str>,
+    ) -> VoiceResult<String> {
+        conflicts::create_note_delete_conflict(
+            &self.conn,
+            note_id,
+            surviving_content,
+            surviving_modified_at,
+            surviving_device_id,
+            deleted_content,
+            deleted_at,
+            deleting_device_id,
+            deleting_device_name,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_tag_rename_conflict(
+        &self,
+        tag_id: &str,
+        local_name: &str,
+        local_modified_at: &str,
+        remote_name: &str,
+        remote_modified_at: &str,
+        remote_device_id: Option<&str>,
+        remote_device_name: Option<&str>,
+    ) -> VoiceResult<String> {
+        conflicts::create_tag_rename_conflict(
+            &self.conn,
+            tag_id,
+            local_name,
+            local_modified_at,
+            remote_name,
+            remote_modified_at,
+            remote_device_id,
+            remote_device_name,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_note_tag_conflict(
+        &self,
+        note_id: &str,
+        tag_id: &str,
+        local_created_at: Option<&str>,
+        local_modified_at: Option<&str>,
+        local_deleted_at: Option<&str>,
+        remote_created_at: Option<&str>,
+        remote_modified_at: Option<&str>,
+        remote_deleted_at: Option<&str>,
+        remote_device_id: Option<&str>,
+        remote_device_name: Option<&str>,
+    ) -> VoiceResult<String> {
+        conflicts::create_note_tag_conflict(
+            &self.conn,
+            note_id,
+            tag_id,
+            local_created_at,
+            local_modified_at,
+            local_deleted_at,
+            remote_created_at,
+            remote_modified_at,
+            remote_deleted_at,
+            remote_device_id,
+            remote_device_name,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_tag_parent_conflict(
+        &self,
+        tag_id: &str,
+        local_parent_id: Option<&str>,
+        local_modified_at: &str,
+        remote_parent_id: Option<&str>,
+        remote_modified_at: &str,
+        remote_device_id: Option<&str>,
+        remote_device_name: Option<&str>,
+    ) -> VoiceResult<String> {
+        conflicts::create_tag_parent_conflict(
+            &self.conn,
+            tag_id,
+            local_parent_id,
+            local_modified_at,
+            remote_parent_id,
+            remote_modified_at,
+            remote_device_id,
+            remote_device_name,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_tag_delete_conflict(
+        &self,
+        tag_id: &str,
+        surviving_name: &str,
+        surviving_parent_id: Option<&str>,
+        surviving_modified_at: &str,
+        surviving_device_id: Option<&str>,
+        surviving_device_name: Option<&str>,
+        deleted_at: &str,
+        deleting_device_id: Option<&str>,
+        deleting_device_name: Option<&str>,
+    ) -> VoiceResult<String> {
+        conflicts::create_tag_delete_conflict(
+            &self.conn,
+            tag_id,
+            surviving_name,
+            surviving_parent_id,
+            surviving_modified_at,
+            surviving_device_id,
+            surviving_device_name,
+            deleted_at,
+            deleting_device_id,
+            deleting_device_name,
+        )
+    }
+
+    pub fn get_unresolved_conflict_counts(&self) -> VoiceResult<HashMap<String, i64>> {
+        conflicts::unresolved_counts(&self.conn)
+    }
+
+    pub fn get_note_content_conflicts(&self, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+        conflicts::note_content_conflicts(&self.conn, include_resolved)
+    }
+
+    pub fn get_note_delete_conflicts(&self, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+        conflicts::note_delete_conflicts(&self.conn, include_resolved)
+    }
+
+    pub fn get_tag_rename_conflicts(&self, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+        conflicts::tag_rename_conflicts(&self.conn, include_resolved)
+    }
+
+    pub fn get_tag_parent_conflicts(&self, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+        conflicts::tag_parent_conflicts(&self.conn, include_resolved)
+    }
+
+    pub fn get_tag_delete_conflicts(&self, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+        conflicts::tag_delete_conflicts(&self.conn, include_resolved)
+    }
+
+    pub fn get_note_tag_conflicts(&self, include_resolved: bool) -> VoiceResult<Vec<serde_json::Value>> {
+        conflicts::note_tag_conflicts(&self.conn, include_resolved)
+    }
+
+    pub fn resolve_note_content_conflict(&self, conflict_id: &str, new_content: &str) -> VoiceResult<bool> {
+        validation::validate_note_content(new_content)?;
+        let payload = match conflicts::conflict_payload(&self.conn, conflict_id)? {
+            Some(p) => p,
+            None => return Ok(false),
+        };
+        let note_id = payload["note_id"]
+            .as_str()
+            .ok_or_else(|| VoiceError::database_op("conflict payload missing note_id"))?;
+        self.update_note(note_id, new_content)?;
+        conflicts::mark_resolved(&self.conn, conflict_id)
+    }
+
+    /// Fetch a note content conflict's raw payload (`note_id`, `local_content`,
+    /// `remote_content`, ...) so a caller can decide which side to keep without
+    /// duplicating [`Self::get_note_content_conflicts`]'s full listing.
+    pub fn note_content_conflict_payload(&self, conflict_id: &str) -> VoiceResult<Option<serde_json::Value>> {
+        conflicts::conflict_payload(&self.conn, conflict_id)
+    }
+
+    /// Resolve a note content conflict by keeping both sides: the note itself is left as
+    /// whichever content already won the sync (see [`crate::sync_server::apply_note_change`]),
+    /// and a brand-new note is created carrying the other side's content, so resolving
+    /// never discards an edit. Returns the new note's id, or `None` if the conflict
+    /// doesn't exist.
+    pub fn fork_note_content_conflict(&self, conflict_id: &str) -> VoiceResult<Option<String>> {
+        let payload = match conflicts::conflict_payload(&self.conn, conflict_id)? {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+        let note_id = payload["note_id"]
+            .as_str()
+            .ok_or_else(|| VoiceError::database_op("conflict payload missing note_id"))?;
+        let local_content = payload["local_content"].as_str().unwrap_or("");
+        let remote_content = payload["remote_content"].as_str().unwrap_or("");
+
+        let current_content = self
+            .get_note_raw(note_id)?
+            .and_then(|row| row.get("content").and_then(|v| v.as_str()).map(str::to_string))
+            .unwrap_or_default();
+        let losing_content = if current_content == local_content { remote_content } else { local_content };
+
+        let forked_note_id = self.create_note(losing_content)?;
+        conflicts::mark_resolved(&self.conn, conflict_id)?;
+        Ok(Some(forked_note_id))
+    }
+
+    pub fn resolve_note_delete_conflict(&self, conflict_id: &str, restore_note: bool) -> VoiceResult<bool> {
+        let payload = match conflicts::conflict_payload(&self.conn, conflict_id)? {
+            Some(p) => p,
+            None => return Ok(false),
+        };
+        let note_id = payload["note_id"]
+            .as_str()
+            .ok_or_else(|| VoiceError::database_op("conflict payload missing note_id"))?;
+        if restore_note {
+            let uuid = validation::validate_note_id(note_id)?;
+            let now = Utc::now().to_rfc3339();
+            let vector = self.local_vector_bump(None);
+            self.conn.execute(
+                "UPDATE notes SET deleted_at = NULL, modified_at = ?, version_vector = ? WHERE id = ?",
+                rusqlite::params![now, vector, uuid.as_bytes().to_vec()],
+            )?;
+        } else {
+            self.delete_note(note_id)?;
+        }
+        conflicts::mark_resolved(&self.conn, conflict_id)
+    }
+
+    pub fn resolve_tag_rename_conflict(&self, conflict_id: &str, new_name: &str) -> VoiceResult<bool> {
+        validation::validate_tag_name(new_name)?;
+        let payload = match conflicts::conflict_payload(&self.conn, conflict_id)? {
+            Some(p) => p,
+            None => return Ok(false),
+        };
+        let tag_id = payload["tag_id"]
+            .as_str()
+            .ok_or_else(|| VoiceError::database_op("conflict payload missing tag_id"))?;
+        self.rename_tag(tag_id, new_name)?;
+        conflicts::mark_resolved(&self.conn, conflict_id)
+    }
+}
+
+// ============================================================================
+// Python bindings
+// ============================================================================
+
+#[pyclass(name = "Database", unsendable)]
+pub struct PyDatabase {
+    inner: Option<Database>,
+    change_callbacks: Mutex<Vec<(u64, Py<PyAny>)>>,
+    next_callback_id: Mutex<u64>,
+    firing_callbacks: Mutex<bool>,
+}
+
+impl PyDatabase {
+    pub(crate) fn inner_ref(&self) -> PyResult<&Database> {
+        self.inner
+            .as_ref()
+            .ok_or_else(|| crate::error::PyDatabaseError::new_err("Database has been closed"))
+    }
+
+    /// Take ownership of the underlying [`Database`], leaving this wrapper closed (same
+    /// state as after [`PyDatabase::close`]). Used to hand the connection off to a
+    /// background thread, e.g. [`crate::sync_server::py_start_sync_server`].
+    pub(crate) fn take_inner(&mut self) -> PyResult<Database> {
+        self.inner
+            .take()
+            .ok_or_else(|| crate::error::PyDatabaseError::new_err("Database has been closed"))
+    }
+
+    /// Notify registered change callbacks that `relation` had an `id` mutated by `op`.
+    ///
+    /// Only called after a write statement has succeeded, never on a validation or
+    /// database error. Writes made through a [`PyTransaction`] are reported as each one
+    /// succeeds, before the transaction commits, and are not withdrawn if it is later
+    /// rolled back. Guarded against re-entrancy: if a callback itself mutates the
+    /// database, the nested `emit_change` is a no-op rather than recursing or firing the
+    /// outer callbacks a second time.
+    fn emit_change(&self, py: Python<'_>, relation: &str, op: &str, id: &str) {
+        let callbacks: Vec<Py<PyAny>> = {
+            let mut firing = self.firing_callbacks.lock().expect("firing_callbacks mutex poisoned");
+            let registered = self.change_callbacks.lock().expect("change_callbacks mutex poisoned");
+            if *firing || registered.is_empty() {
+                return;
+            }
+            *firing = true;
+            registered.iter().map(|(_, callback)| callback.clone_ref(py)).collect()
+        };
+        let event = PyDict::new(py);
+        let _ = event.set_item("relation", relation);
+        let _ = event.set_item("op", op);
+        let _ = event.set_item("id", id);
+        let _ = event.set_item("timestamp", Utc::now().to_rfc3339());
+        for callback in &callbacks {
+            let _ = callback.call1(py, (event.clone(),));
+        }
+        *self.firing_callbacks.lock().expect("firing_callbacks mutex poisoned") = false;
+    }
+}
+
+#[pymethods]
+impl PyDatabase {
+    #[new]
+    #[pyo3(signature = (db_path=None))]
+    fn new(db_path: Option<&str>) -> PyResult<Self> {
+        let db = match db_path {
+            Some(path) => Database::new(path)?,
+            None => Database::new_in_memory()?,
+        };
+        Ok(Self {
+            inner: Some(db),
+            change_callbacks: Mutex::new(Vec::new()),
+            next_callback_id: Mutex::new(0),
+            firing_callbacks: Mutex::new(false),
+        })
+    }
+
+    /// Register a Python callable to be invoked after every successful write (see
+    /// `emit_change` for writes made inside a transaction), with
+    /// `{"relation", "op", "id", "timestamp"}`. Returns a token for `unregister_change_callback`.
+    fn register_change_callback(&self, callback: Py<PyAny>) -> PyResult<u64> {
+        let mut next_id = self.next_callback_id.lock().expect("next_callback_id mutex poisoned");
+        let token = *next_id;
+        *next_id += 1;
+        self.change_callbacks
+            .lock()
+            .expect("change_callbacks mutex poisoned")
+            .push((token, callback));
+        Ok(token)
+    }
+
+    /// Remove a callback previously returned by `register_change_callback`. Returns
+    /// `false` if `token` was never registered or was already unregistered.
+    fn unregister_change_callback(&self, token: u64) -> PyResult<bool> {
+        let mut callbacks = self.change_callbacks.lock().expect("change_callbacks mutex poisoned");
+        let before = callbacks.len();
+        callbacks.retain(|(id, _)| *id != token);
+        Ok(callbacks.len() != before)
+    }
+
+    fn close(&mut self) -> PyResult<()> {
+        if let Some(db) = self.inner.take() {
+            db.close()?;
+        }
+        Ok(())
+    }
+
+    /// Begin an explicit transaction, returning a [`PyTransaction`] handle through which
+    /// note/tag writes and conflict resolutions can be batched into one atomic scope. Use
+    /// it directly (`tx.commit()`/`tx.rollback()`) or as a context manager, which commits
+    /// on clean exit and rolls back if the `with` block raises.
+    fn begin(slf: Bound<'_, PyDatabase>) -> PyResult<PyTransaction> {
+        slf.borrow().inner_ref()?.begin_transaction()?;
+        Ok(PyTransaction {
+            db: slf.unbind(),
+            savepoints: Mutex::new(Vec::new()),
+            finished: Mutex::new(false),
+        })
+    }
+
+    fn create_note(&self, py: Python<'_>, content: &str) -> PyResult<String> {
+        let id = self.inner_ref()?.create_note(content)?;
+        self.emit_change(py, "notes", "create", &id);
+        Ok(id)
+    }
+
+    fn get_note<'py>(&self, py: Python<'py>, note_id: &str) -> PyResult<Option<PyObject>> {
+        match self.inner_ref()?.get_note(note_id)? {
+            Some(n) => Ok(Some(note_row_to_dict(py, &n)?.into_any().unbind())),
+            None => Ok(None),
+        }
+    }
+
+    fn update_note(&self, py: Python<'_>, note_id: &str, content: &str) -> PyResult<bool> {
+        let updated = self.inner_ref()?.update_note(note_id, content)?;
+        if updated {
+            self.emit_change(py, "notes", "update", note_id);
+        }
+        Ok(updated)
+    }
+
+    fn delete_note(&self, py: Python<'_>, note_id: &str) -> PyResult<bool> {
+        let deleted = self.inner_ref()?.delete_note(note_id)?;
+        if deleted {
+            self.emit_change(py, "notes", "delete", note_id);
+        }
+        Ok(deleted)
+    }
+
+    fn get_all_notes<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
+        let notes = self.inner_ref()?.get_all_notes()?;
+        let list = PyList::empty(py);
+        for note in &notes {
+            list.append(note_row_to_dict(py, note)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    #[pyo3(signature = (name, parent_id=None))]
+    fn create_tag(&self, py: Python<'_>, name: &str, parent_id: Option<&str>) -> PyResult<String> {
+        let id = self.inner_ref()?.create_tag(name, parent_id)?;
+        self.emit_change(py, "tags", "create", &id);
+        Ok(id)
+    }
+
+    fn get_tag<'py>(&self, py: Python<'py>, tag_id: &str) -> PyResult<Option<PyObject>> {
+        match self.inner_ref()?.get_tag(tag_id)? {
+            Some(t) => Ok(Some(tag_row_to_dict(py, &t)?.into_any().unbind())),
+            None => Ok(None),
+        }
+    }
+
+    fn get_all_tags<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
+        let tags = self.inner_ref()?.get_all_tags()?;
+        let list = PyList::empty(py);
+        for tag in &tags {
+            list.append(tag_row_to_dict(py, tag)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    fn get_tags_by_name<'py>(&self, py: Python<'py>, name: &str) -> PyResult<PyObject> {
+        let tags = self.inner_ref()?.get_tags_by_name(name)?;
+        let list = PyList::empty(py);
+        for tag in &tags {
+            list.append(tag_row_to_dict(py, tag)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    fn get_tag_by_path<'py>(&self, py: Python<'py>, path: &str) -> PyResult<Option<PyObject>> {
+        match self.inner_ref()?.get_tag_by_path(path)? {
+            Some(t) => Ok(Some(tag_row_to_dict(py, &t)?.into_any().unbind())),
+            None => Ok(None),
+        }
+    }
+
+    fn get_all_tags_by_path<'py>(&self, py: Python<'py>, path: &str) -> PyResult<PyObject> {
+        let tags = self.inner_ref()?.get_all_tags_by_path(path)?;
+        let list = PyList::empty(py);
+        for tag in &tags {
+            list.append(tag_row_to_dict(py, tag)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    fn is_tag_name_ambiguous(&self, name: &str) -> PyResult<bool> {
+        Ok(self.inner_ref()?.is_tag_name_ambiguous(name)?)
+    }
+
+    fn get_tag_descendants<'py>(&self, py: Python<'py>, tag_id: &str) -> PyResult<PyObject> {
+        let descendants = self.inner_ref()?.get_tag_descendants(tag_id)?;
+        let list = PyList::empty(py);
+        for id_bytes in &descendants {
+            list.append(validation::uuid_bytes_to_hex(id_bytes)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    fn query_tag_graph<'py>(&self, py: Python<'py>, tag_id: &str, mode: &str) -> PyResult<PyObject> {
+        match self.inner_ref()?.query_tag_graph(tag_id, mode)? {
+            TagGraphResult::Tags(tags) => {
+                let list = PyList::empty(py);
+                for tag in &tags {
+                    list.append(tag_row_to_dict(py, tag)?)?;
+                }
+                Ok(list.into_any().unbind())
+            }
+            TagGraphResult::Notes(notes) => {
+                let list = PyList::empty(py);
+                for note in &notes {
+                    list.append(note_row_to_dict(py, note)?)?;
+                }
+                Ok(list.into_any().unbind())
+            }
+        }
+    }
+
+    fn rename_tag(&self, py: Python<'_>, tag_id: &str, new_name: &str) -> PyResult<bool> {
+        let renamed = self.inner_ref()?.rename_tag(tag_id, new_name)?;
+        if renamed {
+            self.emit_change(py, "tags", "update", tag_id);
+        }
+        Ok(renamed)
+    }
+
+    fn delete_tag(&self, py: Python<'_>, tag_id: &str) -> PyResult<bool> {
+        let deleted = self.inner_ref()?.delete_tag(tag_id)?;
+        if deleted {
+            self.emit_change(py, "tags", "delete", tag_id);
+        }
+        Ok(deleted)
+    }
+
+    fn add_tag_to_note(&self, py: Python<'_>, note_id: &str, tag_id: &str) -> PyResult<bool> {
+        let added = self.inner_ref()?.add_tag_to_note(note_id, tag_id)?;
+        if added {
+            self.emit_change(py, "note_tags", "create", &format!("{note_id}:{tag_id}"));
+        }
+        Ok(added)
+    }
+
+    fn remove_tag_from_note(&self, py: Python<'_>, note_id: &str, tag_id: &str) -> PyResult<bool> {
+        let removed = self.inner_ref()?.remove_tag_from_note(note_id, tag_id)?;
+        if removed {
+            self.emit_change(py, "note_tags", "delete", &format!("{note_id}:{tag_id}"));
+        }
+        Ok(removed)
+    }
+
+    fn get_note_tags<'py>(&self, py: Python<'py>, note_id: &str) -> PyResult<PyObject> {
+        let tags = self.inner_ref()?.get_note_tags(note_id)?;
+        let list = PyList::empty(py);
+        for tag in &tags {
+            list.append(tag_row_to_dict(py, tag)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    fn filter_notes<'py>(&self, py: Python<'py>, tag_ids: Vec<String>) -> PyResult<PyObject> {
+        let notes = self.inner_ref()?.filter_notes(&tag_ids)?;
+        let list = PyList::empty(py);
+        for note in &notes {
+            list.append(note_row_to_dict(py, note)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    #[pyo3(signature = (text_query=None, tag_id_groups=None))]
+    fn search_notes<'py>(
+        &self,
+        py: Python<'py>,
+        text_query: Option<&str>,
+        tag_id_groups: Option<Vec<Vec<String>>>,
+    ) -> PyResult<PyObject> {
+        let notes = self.inner_ref()?.search_notes(text_query, tag_id_groups.as_ref())?;
+        let list = PyList::empty(py);
+        for note in &notes {
+            list.append(note_row_to_dict(py, note)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    fn get_peer_last_sync(&self, peer_device_id: &str) -> PyResult<Option<String>> {
+        Ok(self.inner_ref()?.get_peer_last_sync(peer_device_id)?)
+    }
+
+    #[pyo3(signature = (peer_device_id, peer_name=None))]
+    fn update_peer_sync_time(&self, peer_device_id: &str, peer_name: Option<&str>) -> PyResult<()> {
+        Ok(self.inner_ref()?.update_peer_sync_time(peer_device_id, peer_name)?)
+    }
+
+    #[pyo3(signature = (since=None, limit=1000))]
+    fn get_changes_since<'py>(&self, py: Python<'py>, since: Option<&str>, limit: i64) -> PyResult<PyObject> {
+        let (changes, latest) = self.inner_ref()?.get_changes_since(since, limit, None)?;
+        let result = PyDict::new(py);
+        let changes_list = PyList::empty(py);
+        for change in &changes {
+            let json = serde_json::to_value(change).map_err(crate::error::VoiceError::from)?;
+            changes_list.append(json_value_to_pyobject(py, &json)?)?;
+        }
+        result.set_item("changes", changes_list)?;
+        result.set_item("latest_timestamp", latest)?;
+        Ok(result.into_any().unbind())
+    }
+
+    fn get_full_dataset<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
+        let dataset = self.inner_ref()?.get_full_dataset()?;
+        json_value_to_pyobject(py, &dataset)
+    }
+
+    fn export_snapshot<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
+        let snapshot = self.inner_ref()?.export_snapshot()?;
+        let json = serde_json::to_value(&snapshot).map_err(VoiceError::from)?;
+        json_value_to_pyobject(py, &json)
+    }
+
+    #[pyo3(signature = (snapshot, *, merge=false))]
+    fn import_snapshot<'py>(&self, py: Python<'py>, snapshot: Bound<'py, PyAny>, merge: bool) -> PyResult<PyObject> {
+        let snapshot_json = pyobject_to_json_value(&snapshot)?;
+        let snapshot: ColumnarSnapshot = serde_json::from_value(snapshot_json).map_err(VoiceError::from)?;
+        let summary = self.inner_ref()?.import_snapshot(&snapshot, merge)?;
+        let json = serde_json::to_value(&summary).map_err(VoiceError::from)?;
+        json_value_to_pyobject(py, &json)
+    }
+
+    #[pyo3(signature = (note_id, created_at, content, modified_at=None, deleted_at=None, version_vector=None, hlc=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn apply_sync_note(
+        &self,
+        py: Python<'_>,
+        note_id: &str,
+        created_at: &str,
+        content: &str,
+        modified_at: Option<&str>,
+        deleted_at: Option<&str>,
+        version_vector: Option<HashMap<String, u64>>,
+        hlc: Option<String>,
+    ) -> PyResult<bool> {
+        let vector = VersionVector(version_vector.unwrap_or_default());
+        let hlc = hlc.map(|raw| HlcStamp::from_json(&raw)).unwrap_or_default();
+        let applied = self
+            .inner_ref()?
+            .apply_sync_note(note_id, created_at, content, modified_at, deleted_at, &vector, &hlc)?;
+        if applied {
+            let op = if deleted_at.is_some() { "delete" } else { "update" };
+            self.emit_change(py, "notes", op, note_id);
+        }
+        Ok(applied)
+    }
+
+    #[pyo3(signature = (tag_id, name, parent_id, created_at, modified_at=None, version_vector=None, hlc=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn apply_sync_tag(
+        &self,
+        py: Python<'_>,
+        tag_id: &str,
+        name: &str,
+        parent_id: Option<&str>,
+        created_at: &str,
+        modified_at: Option<&str>,
+        version_vector: Option<HashMap<String, u64>>,
+        hlc: Option<String>,
+    ) -> PyResult<bool> {
+        let vector = VersionVector(version_vector.unwrap_or_default());
+        let hlc = hlc.map(|raw| HlcStamp::from_json(&raw)).unwrap_or_default();
+        let applied = self
+            .inner_ref()?
+            .apply_sync_tag(tag_id, name, parent_id, created_at, modified_at, &vector, &hlc)?;
+        if applied {
+            self.emit_change(py, "tags", "update", tag_id);
+        }
+        Ok(applied)
+    }
+
+    #[pyo3(signature = (note_id, tag_id, created_at, modified_at=None, deleted_at=None, version_vector=None, hlc=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn apply_sync_note_tag(
+        &self,
+        py: Python<'_>,
+        note_id: &str,
+        tag_id: &str,
+        created_at: &str,
+        modified_at: Option<&str>,
+        deleted_at: Option<&str>,
+        version_vector: Option<HashMap<String, u64>>,
+        hlc: Option<String>,
+    ) -> PyResult<bool> {
+        let vector = VersionVector(version_vector.unwrap_or_default());
+        let hlc = hlc.map(|raw| HlcStamp::from_json(&raw)).unwrap_or_default();
+        let applied = self
+            .inner_ref()?
+            .apply_sync_note_tag(note_id, tag_id, created_at, modified_at, deleted_at, &vector, &hlc)?;
+        if applied {
+            let op = if deleted_at.is_some() { "delete" } else { "update" };
+            self.emit_change(py, "note_tags", op, &format!("{note_id}:{tag_id}"));
+        }
+        Ok(applied)
+    }
+
+    fn get_note_raw<'py>(&self, py: Python<'py>, note_id: &str) -> PyResult<Option<PyObject>> {
+        match self.inner_ref()?.get_note_raw(note_id)? {
+            Some(n) => {
+                let dict = PyDict::new(py);
+                for (k, v) in &n {
+                    dict.set_item(k, json_value_to_pyobject(py, v)?)?;
+                }
+                Ok(Some(dict.into_any().unbind()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn get_tag_raw<'py>(&self, py: Python<'py>, tag_id: &str) -> PyResult<Option<PyObject>> {
+        match self.inner_ref()?.get_tag_raw(tag_id)? {
+            Some(t) => {
+                let dict = PyDict::new(py);
+                for (k, v) in &t {
+                    dict.set_item(k, json_value_to_pyobject(py, v)?)?;
+                }
+                Ok(Some(dict.into_any().unbind()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn get_note_tag_raw<'py>(&self, py: Python<'py>, note_id: &str, tag_id: &str) -> PyResult<Option<PyObject>> {
+        match self.inner_ref()?.get_note_tag_raw(note_id, tag_id)? {
+            Some(n) => {
+                let dict = PyDict::new(py);
+                for (k, v) in &n {
+                    dict.set_item(k, json_value_to_pyobject(py, v)?)?;
+                }
+                Ok(Some(dict.into_any().unbind()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    #[pyo3(signature = (note_id, local_content, local_modified_at, remote_content, remote_modified_at, remote_device_id=None, remote_device_name=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn create_note_content_conflict(
+        &self,
+        note_id: &str,
+        local_content: &str,
+        local_modified_at: &str,
+        remote_content: &str,
+        remote_modified_at: &str,
+        remote_device_id: Option<&str>,
+        remote_device_name: Option<&str>,
+    ) -> PyResult<String> {
+        Ok(self.inner_ref()?.create_note_content_conflict(
+            note_id,
+            local_content,
+            local_modified_at,
+            remote_content,
+            remote_modified_at,
+            remote_device_id,
+            remote_device_name,
+        )?)
+    }
+
+    fn get_unresolved_conflict_counts<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
+        let counts = self.inner_ref()?.get_unresolved_conflict_counts()?;
+        let dict = PyDict::new(py);
+        for (key, value) in &counts {
+            dict.set_item(key, value)?;
+        }
+        Ok(dict.into_any().unbind())
+    }
+
+    #[pyo3(signature = (include_resolved=false))]
+    fn get_note_content_conflicts<'py>(&self, py: Python<'py>, include_resolved: bool) -> PyResult<PyObject> {
+        let conflicts = self.inner_ref()?.get_note_content_conflicts(include_resolved)?;
+        let list = PyList::empty(py);
+        for conflict in &conflicts {
+            list.append(json_value_to_pyobject(py, conflict)?)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    fn resolve_note_content_conflict(&self, conflict_id: &str, new_content: &str) -> PyResult<bool> {
+        Ok(self.inner_ref()?.resolve_note_content_conflict(conflict_id, new_content)?)
+    }
+
+    fn fork_note_content_conflict(&self, conflict_id: &str) -> PyResult<Option<String>> {
+        Ok(self.inner_ref()?.fork_note_content_conflict(conflict_id)?)
+    }
+
+    fn resolve_note_delete_conflict(&self, conflict_id: &str, restore_note: bool) -> PyResult<bool> {
+        Ok(self.inner_ref()?.resolve_note_delete_conflict(conflict_id, restore_note)?)
+    }
+
+    fn resolve_tag_rename_conflict(&self, conflict_id: &str, new_name: &str) -> PyResult<bool> {
+        Ok(self.inner_ref()?.resolve_tag_rename_conflict(conflict_id, new_name)?)
+    }
+}
+
+/// An explicit transaction on a [`PyDatabase`], returned by [`PyDatabase::begin`]. All
+/// note/tag write methods and conflict resolutions are mirrored here so a batch of edits
+/// can be applied in one atomic scope, with savepoints for partial rollback within it.
+///
+/// Holds the same underlying connection as the `PyDatabase` it was created from (SQLite
+/// has one implicit transaction per connection, not a separate handle per transaction), so
+/// methods on the original `PyDatabase` should not be called again until this transaction
+/// is committed or rolled back.
+#[pyclass(name = "Transaction", unsendable)]
+pub struct PyTransaction {
+    db: Py<PyDatabase>,
+    savepoints: Mutex<Vec<String>>,
+    finished: Mutex<bool>,
+}
+
+impl PyTransaction {
+    fn ensure_active(&self) -> PyResult<()> {
+        if *self.finished.lock().expect("finished mutex poisoned") {
+            return Err(crate::error::PyDatabaseError::new_err(
+                "transaction has already been committed or rolled back",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl PyTransaction {
+    fn savepoint(&self, py: Python<'_>, name: &str) -> PyResult<()> {
+        self.ensure_active()?;
+        self.db.bind(py).borrow().inner_ref()?.savepoint(name)?;
+        self.savepoints.lock().expect("savepoints mutex poisoned").push(name.to_string());
+        Ok(())
+    }
+
+    fn release_savepoint(&self, py: Python<'_>, name: &str) -> PyResult<()> {
+        self.ensure_active()?;
+        self.db.bind(py).borrow().inner_ref()?.release_savepoint(name)?;
+        self.savepoints.lock().expect("savepoints mutex poisoned").retain(|n| n != name);
+        Ok(())
+    }
+
+    fn rollback_to_savepoint(&self, py: Python<'_>, name: &str) -> PyResult<()> {
+        self.ensure_active()?;
+        self.db.bind(py).borrow().inner_ref()?.rollback_to_savepoint(name)?;
+        Ok(())
+    }
+
+    fn commit(&self, py: Python<'_>) -> PyResult<()> {
+        self.ensure_active()?;
+        self.db.bind(py).borrow().inner_ref()?.commit_transaction()?;
+        *self.finished.lock().expect("finished mutex poisoned") = true;
+        Ok(())
+    }
+
+    fn rollback(&self, py: Python<'_>) -> PyResult<()> {
+        self.ensure_active()?;
+        self.db.bind(py).borrow().inner_ref()?.rollback_transaction()?;
+        *self.finished.lock().expect("finished mutex poisoned") = true;
+        Ok(())
+    }
+
+    fn create_note(&self, py: Python<'_>, content: &str) -> PyResult<String> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let id = db.inner_ref()?.create_note(content)?;
+        db.emit_change(py, "notes", "create", &id);
+        Ok(id)
+    }
+
+    fn update_note(&self, py: Python<'_>, note_id: &str, content: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let updated = db.inner_ref()?.update_note(note_id, content)?;
+        if updated {
+            db.emit_change(py, "notes", "update", note_id);
+        }
+        Ok(updated)
+    }
+
+    fn delete_note(&self, py: Python<'_>, note_id: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let deleted = db.inner_ref()?.delete_note(note_id)?;
+        if deleted {
+            db.emit_change(py, "notes", "delete", note_id);
+        }
+        Ok(deleted)
+    }
+
+    #[pyo3(signature = (name, parent_id=None))]
+    fn create_tag(&self, py: Python<'_>, name: &str, parent_id: Option<&str>) -> PyResult<String> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let id = db.inner_ref()?.create_tag(name, parent_id)?;
+        db.emit_change(py, "tags", "create", &id);
+        Ok(id)
+    }
+
+    fn rename_tag(&self, py: Python<'_>, tag_id: &str, new_name: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let renamed = db.inner_ref()?.rename_tag(tag_id, new_name)?;
+        if renamed {
+            db.emit_change(py, "tags", "update", tag_id);
+        }
+        Ok(renamed)
+    }
+
+    fn delete_tag(&self, py: Python<'_>, tag_id: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let deleted = db.inner_ref()?.delete_tag(tag_id)?;
+        if deleted {
+            db.emit_change(py, "tags", "delete", tag_id);
+        }
+        Ok(deleted)
+    }
+
+    fn add_tag_to_note(&self, py: Python<'_>, note_id: &str, tag_id: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let added = db.inner_ref()?.add_tag_to_note(note_id, tag_id)?;
+        if added {
+            db.emit_change(py, "note_tags", "create", &format!("{note_id}:{tag_id}"));
+        }
+        Ok(added)
+    }
+
+    fn remove_tag_from_note(&self, py: Python<'_>, note_id: &str, tag_id: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let removed = db.inner_ref()?.remove_tag_from_note(note_id, tag_id)?;
+        if removed {
+            db.emit_change(py, "note_tags", "delete", &format!("{note_id}:{tag_id}"));
+        }
+        Ok(removed)
+    }
+
+    fn resolve_note_content_conflict(&self, py: Python<'_>, conflict_id: &str, new_content: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        Ok(self.db.bind(py).borrow().inner_ref()?.resolve_note_content_conflict(conflict_id, new_content)?)
+    }
+
+    fn fork_note_content_conflict(&self, py: Python<'_>, conflict_id: &str) -> PyResult<Option<String>> {
+        self.ensure_active()?;
+        Ok(self.db.bind(py).borrow().inner_ref()?.fork_note_content_conflict(conflict_id)?)
+    }
+
+    fn resolve_note_delete_conflict(&self, py: Python<'_>, conflict_id: &str, restore_note: bool) -> PyResult<bool> {
+        self.ensure_active()?;
+        Ok(self.db.bind(py).borrow().inner_ref()?.resolve_note_delete_conflict(conflict_id, restore_note)?)
+    }
+
+    fn resolve_tag_rename_conflict(&self, py: Python<'_>, conflict_id: &str, new_name: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        Ok(self.db.bind(py).borrow().inner_ref()?.resolve_tag_rename_conflict(conflict_id, new_name)?)
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (exc_type, exc_value, traceback))]
+    fn __exit__(
+        &self,
+        py: Python<'_>,
+        exc_type: Option<Py<PyAny>>,
+        exc_value: Option<Py<PyAny>>,
+        traceback: Option<Py<PyAny>>,
+    ) -> PyResult<bool> {
+        let _ = (exc_value, traceback);
+        if *self.finished.lock().expect("finished mutex poisoned") {
+            return Ok(false);
+        }
+        if exc_type.is_some() {
+            self.rollback(py)?;
+        } else {
+            self.commit(py)?;
+        }
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_vector_dominance() {
+        let mut local = VersionVector::new();
+        local.increment("device-a");
+        local.increment("device-a");
+        let mut remote = VersionVector::new();
+        remote.increment("device-a");
+
+        assert_eq!(local.compare(&remote), VectorOrdering::Dominates);
+        assert_eq!(remote.compare(&local), VectorOrdering::Dominated);
+    }
+
+    #[test]
+    fn test_version_vector_concurrent_edit_detected() {
+        let mut local = VersionVector::new();
+        local.increment("device-a");
+        let mut remote = VersionVector::new();
+        remote.increment("device-b");
+
+        assert_eq!(local.compare(&remote), VectorOrdering::Concurrent);
+    }
+
+    #[test]
+    fn test_version_vector_merge_takes_elementwise_max() {
+        let mut local = VersionVector::new();
+        local.0.insert("device-a".to_string(), 3);
+        local.0.insert("device-b".to_string(), 1);
+        let mut remote = VersionVector::new();
+        remote.0.insert("device-a".to_string(), 1);
+        remote.0.insert("device-b".to_string(), 5);
+
+        let merged = local.merge(&remote);
+        assert_eq!(merged.0.get("device-a"), Some(&3));
+        assert_eq!(merged.0.get("device-b"), Some(&5));
+    }
+
+    #[test]
+    fn test_create_and_get_note_roundtrip() {
+        let db = Database::new_in_memory().unwrap();
+        let id = db.create_note("hello").unwrap();
+        let note = db.get_note(&id).unwrap().unwrap();
+        assert_eq!(note.content, "hello");
+        assert!(note.modified_at.is_none());
+    }
+
+    #[test]
+    fn test_apply_sync_note_merges_version_vectors() {
+        let db = Database::new_in_memory().unwrap();
+        let id = db.create_note("hello").unwrap();
+
+        let mut remote_vector = VersionVector::new();
+        remote_vector.increment("remote-device");
+        let remote_hlc = HlcStamp::tick(None, "remote-device");
+        db.apply_sync_note(
+            &id,
+            "2024-01-01T00:00:00Z",
+            "hello from remote",
+            None,
+            None,
+            &remote_vector,
+            &remote_hlc,
+        )
+        .unwrap();
+
+        let raw = db.get_note_raw(&id).unwrap().unwrap();
+        let stored_vector = raw.get("version_vector").unwrap().as_object().unwrap();
+        assert!(stored_vector.contains_key("remote-device"));
+        let stored_hlc: HlcStamp = serde_json::from_value(raw.get("hlc").unwrap().clone()).unwrap();
+        assert_eq!(stored_hlc, remote_hlc);
+    }
+
+    #[test]
+    fn test_remove_tag_from_note_soft_deletes() {
+        let db = Database::new_in_memory().unwrap();
+        let note_id = db.create_note("hello").unwrap();
+        let tag_id = db.create_tag("work", None).unwrap();
+        db.add_tag_to_note(&note_id, &tag_id).unwrap();
+        assert_eq!(db.get_note_tags(&note_id).unwrap().len(), 1);
+
+        assert!(db.remove_tag_from_note(&note_id, &tag_id).unwrap());
+
+        // The association is gone from the active view...
+        assert!(db.get_note_tags(&note_id).unwrap().is_empty());
+
+        // ...but the row is tombstoned, not hard-deleted, so it still has a version
+        // vector/HLC a peer can reconcile against.
+        let (deleted_at, version_vector): (Option<String>, String) = db
+            .conn
+            .query_row(
+                "SELECT deleted_at, version_vector FROM note_tags WHERE note_id = ? AND tag_id = ?",
+                rusqlite::params![
+                    validation::validate_note_id(&note_id).unwrap().as_bytes().to_vec(),
+                    validation::validate_tag_id(&tag_id).unwrap().as_bytes().to_vec()
+                ],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert!(deleted_at.is_some());
+        assert_ne!(VersionVector::from_json(&version_vector), VersionVector::new());
+
+        // Removing an already-removed (or nonexistent) association is a no-op, not an error.
+        assert!(!db.remove_tag_from_note(&note_id, &tag_id).unwrap());
+    }
+}
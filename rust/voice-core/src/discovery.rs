@@ -0,0 +1,175 @@
+//! LAN peer auto-discovery over mDNS.
+//!
+//! Both manual `Config::add_peer` and the pairing-token flow (see
+//! [`crate::config::PairingToken`]) still require knowing another device's
+//! reachable URL up front. This module advertises a `_voice-sync._tcp` mDNS
+//! service whose TXT records carry this device's identity, and concurrently
+//! browses for other devices advertising the same service, so peers on the
+//! same LAN can find each other with no URL exchanged at all. Discovered
+//! devices are held in memory only (see [`DiscoveredPeer`]) until explicitly
+//! promoted into `sync.peers` via [`crate::config::Config::promote_discovered_peer`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+
+use crate::config::Config;
+use crate::error::{VoiceError, VoiceResult};
+
+const SERVICE_TYPE: &str = "_voice-sync._tcp.local.";
+
+/// A device seen advertising `_voice-sync._tcp` on the LAN that isn't (yet) a
+/// persisted sync peer.
+#[derive(Debug, Clone)]
+pub struct DiscoveredPeer {
+    pub device_id: String,
+    pub device_name: String,
+    pub peer_url: String,
+    pub certificate_fingerprint: Option<String>,
+}
+
+/// State for a running discovery session: the mDNS daemon (advertising this
+/// device and driving the browse) plus the devices it has resolved so far.
+struct DiscoveryHandle {
+    daemon: ServiceDaemon,
+    peers: Arc<Mutex<HashMap<String, DiscoveredPeer>>>,
+}
+
+static DISCOVERY: OnceLock<Mutex<Option<DiscoveryHandle>>> = OnceLock::new();
+
+/// Start advertising `config`'s device over mDNS and browsing for others.
+/// No-op if discovery is already running.
+pub fn start(config: &Config) -> VoiceResult<()> {
+    let slot = DISCOVERY.get_or_init(|| Mutex::new(None));
+    let mut guard = slot
+        .lock()
+        .map_err(|_| VoiceError::Config("discovery state lock poisoned".to_string()))?;
+    if guard.is_some() {
+        return Ok(());
+    }
+
+    let daemon =
+        ServiceDaemon::new().map_err(|e| VoiceError::Network(format!("failed to start mDNS daemon: {e}")))?;
+
+    let device_id = config.device_id_hex().to_string();
+    let port = config.sync_server_port();
+    let cert_fingerprint = config
+        .certs_dir()
+        .ok()
+        .and_then(|dir| crate::tls::compute_fingerprint(&dir.join("server.crt")).ok());
+
+    let mut properties = HashMap::new();
+    properties.insert("device_id".to_string(), device_id.clone());
+    properties.insert("device_name".to_string(), config.device_name().to_string());
+    properties.insert("server_port".to_string(), port.to_string());
+    if let Some(fp) = &cert_fingerprint {
+        properties.insert("cert_fingerprint".to_string(), fp.clone());
+    }
+
+    let service_info = ServiceInfo::new(
+        SERVICE_TYPE,
+        &device_id,
+        &format!("{device_id}.local."),
+        "",
+        port,
+        properties,
+    )
+    .map_err(|e| VoiceError::Network(format!("failed to build mDNS service info: {e}")))?
+    .enable_addr_auto();
+
+    daemon
+        .register(service_info)
+        .map_err(|e| VoiceError::Network(format!("failed to advertise mDNS service: {e}")))?;
+
+    let receiver = daemon
+        .browse(SERVICE_TYPE)
+        .map_err(|e| VoiceError::Network(format!("failed to browse for mDNS peers: {e}")))?;
+
+    let peers: Arc<Mutex<HashMap<String, DiscoveredPeer>>> = Arc::new(Mutex::new(HashMap::new()));
+    let browse_peers = Arc::clone(&peers);
+    let local_device_id = device_id.clone();
+    std::thread::Builder::new()
+        .name("voice-discovery".to_string())
+        .spawn(move || {
+            while let Ok(event) = receiver.recv() {
+                if let ServiceEvent::ServiceResolved(info) = event {
+                    if let Some(peer) = discovered_peer_from_info(&info, &local_device_id) {
+                        if let Ok(mut peers) = browse_peers.lock() {
+                            peers.insert(peer.device_id.clone(), peer);
+                        }
+                    }
+                }
+            }
+        })
+        .map_err(|e| VoiceError::Network(e.to_string()))?;
+
+    *guard = Some(DiscoveryHandle { daemon, peers });
+    Ok(())
+}
+
+/// Stop advertising and browsing. No-op if discovery isn't running.
+pub fn stop() {
+    if let Some(slot) = DISCOVERY.get() {
+        if let Ok(mut guard) = slot.lock() {
+            if let Some(handle) = guard.take() {
+                let _ = handle.daemon.shutdown();
+            }
+        }
+    }
+}
+
+/// Devices currently seen advertising on the LAN, keyed by `device_id`. Empty
+/// if discovery isn't running.
+pub fn discovered_peers() -> Vec<DiscoveredPeer> {
+    let Some(slot) = DISCOVERY.get() else {
+        return Vec::new();
+    };
+    let Ok(guard) = slot.lock() else {
+        return Vec::new();
+    };
+    let Some(handle) = guard.as_ref() else {
+        return Vec::new();
+    };
+    handle.peers.lock().map(|p| p.values().cloned().collect()).unwrap_or_default()
+}
+
+/// Find a previously discovered device by ID, e.g. so it can be promoted into
+/// `sync.peers`.
+pub fn discovered_peer(device_id: &str) -> Option<DiscoveredPeer> {
+    discovered_peers().into_iter().find(|p| p.device_id == device_id)
+}
+
+/// Build a [`DiscoveredPeer`] from a resolved mDNS service, or `None` if it's our
+/// own advertisement or is missing the `device_id` TXT record it needs to be useful.
+fn discovered_peer_from_info(info: &ServiceInfo, local_device_id: &str) -> Option<DiscoveredPeer> {
+    let properties = info.get_properties();
+    let device_id = properties.get_property_val_str("device_id")?.to_string();
+    if device_id == local_device_id {
+        return None;
+    }
+
+    let device_name = properties
+        .get_property_val_str("device_name")
+        .unwrap_or(&device_id)
+        .to_string();
+    let port = properties
+        .get_property_val_str("server_port")
+        .and_then(|p| p.parse::<u16>().ok())
+        .unwrap_or_else(|| info.get_port());
+    let certificate_fingerprint = properties.get_property_val_str("cert_fingerprint").map(str::to_string);
+
+    let host = info
+        .get_addresses()
+        .iter()
+        .next()
+        .map(|addr| addr.to_string())
+        .unwrap_or_else(|| info.get_hostname().trim_end_matches('.').to_string());
+
+    Some(DiscoveredPeer {
+        device_id,
+        device_name,
+        peer_url: format!("https://{host}:{port}"),
+        certificate_fingerprint,
+    })
+}
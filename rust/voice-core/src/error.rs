@@ -3,11 +3,17 @@
 //! This module defines all error types used throughout the library,
 //! with Python exception mappings for PyO3 integration.
 
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
 use pyo3::exceptions::{PyException, PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::create_exception;
+use serde::Serialize;
 use thiserror::Error;
 
+use crate::database::json_value_to_pyobject;
+
 /// Result type alias for Voice operations
 pub type VoiceResult<T> = Result<T, VoiceError>;
 
@@ -20,11 +26,19 @@ pub enum VoiceError {
     #[error("Database error: {0}")]
     Database(#[from] rusqlite::Error),
 
-    #[error("Database operation failed: {0}")]
-    DatabaseOperation(String),
+    #[error("Database operation failed: {message}")]
+    DatabaseOperation {
+        message: String,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
 
-    #[error("Sync error: {0}")]
-    Sync(String),
+    #[error("Sync error: {message}")]
+    Sync {
+        message: String,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
 
     #[error("Network error: {0}")]
     Network(String),
@@ -50,10 +64,43 @@ pub enum VoiceError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+
     #[error("{0}")]
-    Other(String),
+    ValidationErrors(ValidationErrors),
+
+    #[error("{message}")]
+    Other {
+        message: String,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// Any of the above, annotated with arbitrary diagnostic key→value context
+    /// (the offending record UUID, the sync peer, the attempted SQL, an HTTP
+    /// status, ...) attached via [`VoiceError::with_context`], and/or a
+    /// lightweight propagation trace attached via [`VoiceError::traced`].
+    /// Modeled on async-graphql's `ErrorExtensionValues` and pydantic's
+    /// `PydanticCustomError` context, so the sync/network layers can emit
+    /// machine-parseable diagnostics that round-trip to Python callers for
+    /// retry/telemetry decisions.
+    #[error("{source}")]
+    Context {
+        #[source]
+        source: Box<VoiceError>,
+        context: BTreeMap<String, serde_json::Value>,
+        trace: Vec<TraceRecord>,
+    },
 }
 
+/// A progress/cancellation hook invoked periodically during a long-running scan or sync
+/// pass (see [`crate::search::execute_search`], [`crate::sync_client::sync_with_peer`]).
+/// Called with a phase name, items scanned so far, and the total when known (0 if not).
+/// Returning `false` requests cancellation: the caller should unwind with
+/// [`VoiceError::Cancelled`] rather than completing.
+pub type ProgressCallback<'a> = &'a mut dyn FnMut(&str, usize, usize) -> bool;
+
 impl VoiceError {
     /// Create a new validation error
     pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
@@ -65,12 +112,395 @@ impl VoiceError {
 
     /// Create a new sync error
     pub fn sync(message: impl Into<String>) -> Self {
-        VoiceError::Sync(message.into())
+        VoiceError::Sync { message: message.into(), source: None }
     }
 
     /// Create a new database operation error
     pub fn database_op(message: impl Into<String>) -> Self {
-        VoiceError::DatabaseOperation(message.into())
+        VoiceError::DatabaseOperation { message: message.into(), source: None }
+    }
+
+    /// Attach an underlying cause to a `Sync`/`DatabaseOperation`/`Other` error,
+    /// e.g. `VoiceError::sync("push failed").with_source(reqwest_err)`. A
+    /// no-op on variants with no source slot.
+    pub fn with_source(self, source: impl std::error::Error + Send + Sync + 'static) -> Self {
+        self.set_source(Some(Box::new(source)))
+    }
+
+    /// As [`VoiceError::with_source`], but takes an already-boxed source (or
+    /// `None` to clear it).
+    pub fn set_source(self, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        match self {
+            VoiceError::Sync { message, .. } => VoiceError::Sync { message, source },
+            VoiceError::DatabaseOperation { message, .. } => VoiceError::DatabaseOperation { message, source },
+            VoiceError::Other { message, .. } => VoiceError::Other { message, source },
+            other => other,
+        }
+    }
+
+    /// Attach (or add to) a diagnostic context entry, e.g.
+    /// `err.with_context("peer", peer_id).with_context("attempted_sql", sql)`.
+    /// Chained calls accumulate into the same map rather than nesting wrappers.
+    pub fn with_context(self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        match self {
+            VoiceError::Context { source, mut context, trace } => {
+                context.insert(key.into(), value.into());
+                VoiceError::Context { source, context, trace }
+            }
+            other => {
+                let mut context = BTreeMap::new();
+                context.insert(key.into(), value.into());
+                VoiceError::Context { source: Box::new(other), context, trace: Vec::new() }
+            }
+        }
+    }
+
+    /// The diagnostic context attached via [`VoiceError::with_context`], or an
+    /// empty map if none was attached.
+    pub fn context(&self) -> &BTreeMap<String, serde_json::Value> {
+        static EMPTY: OnceLock<BTreeMap<String, serde_json::Value>> = OnceLock::new();
+        match self {
+            VoiceError::Context { context, .. } => context,
+            _ => EMPTY.get_or_init(BTreeMap::new),
+        }
+    }
+
+    /// Record one more `file:line (function)` propagation point, building up
+    /// a lightweight trace as the error is passed up the call stack —
+    /// modeled on the `err` crate's `Trace`, without a full backtrace capture.
+    #[track_caller]
+    pub fn traced(self, function: &'static str) -> Self {
+        let record = TraceRecord::here(function);
+        match self {
+            VoiceError::Context { source, context, mut trace } => {
+                trace.push(record);
+                VoiceError::Context { source, context, trace }
+            }
+            other => VoiceError::Context { source: Box::new(other), context: BTreeMap::new(), trace: vec![record] },
+        }
+    }
+
+    /// The propagation trace recorded via [`VoiceError::traced`], oldest first.
+    pub fn trace(&self) -> &[TraceRecord] {
+        match self {
+            VoiceError::Context { trace, .. } => trace,
+            _ => &[],
+        }
+    }
+
+    /// Coarse severity, borrowed from Postgres's PANIC/FATAL/ERROR/WARNING/NOTICE
+    /// error-response levels and the kind split in Polar's `ErrorKind`. A
+    /// retry-with-backoff loop around sync can branch on this (or on
+    /// [`VoiceError::is_retryable`]) instead of string-matching messages.
+    pub fn severity(&self) -> Severity {
+        match self {
+            VoiceError::Validation { .. }
+            | VoiceError::ValidationErrors(_)
+            | VoiceError::Conflict(_)
+            | VoiceError::Config(_) => Severity::Fatal,
+            VoiceError::Network(msg) | VoiceError::Tls(msg) if is_transient_message(msg) => Severity::Warning,
+            VoiceError::Sync { message, .. } if is_transient_message(message) => Severity::Warning,
+            VoiceError::Database(e) if is_retryable_sqlite_error(e) => Severity::Warning,
+            VoiceError::Context { source, .. } => source.severity(),
+            _ => Severity::Error,
+        }
+    }
+
+    /// True if retrying the same operation after a backoff stands a chance of
+    /// succeeding (transient network/TLS timeouts, `SQLITE_BUSY`/`SQLITE_LOCKED`).
+    /// `Validation`, `Conflict`, and `Config` are never retryable.
+    pub fn is_retryable(&self) -> bool {
+        self.severity() == Severity::Warning
+    }
+
+    /// Stable, machine-readable classification, modeled on Postgres's
+    /// SQLSTATE classes and Polar's `FormattedPolarError`. Unlike the enum
+    /// discriminant this is part of the public contract: it is what crosses
+    /// the sync wire (see [`VoiceError::to_wire`]) and what Python callers
+    /// get as `.code` on the raised exception, so a peer or a caller can
+    /// branch on it without depending on Rust-side variant names.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            VoiceError::Validation { .. } | VoiceError::ValidationErrors(_) => ErrorCode::Validation,
+            VoiceError::Database(e) if is_retryable_sqlite_error(e) => ErrorCode::DbBusy,
+            VoiceError::Database(e) if is_constraint_sqlite_error(e) => ErrorCode::DbConstraint,
+            VoiceError::Database(_) | VoiceError::DatabaseOperation { .. } => ErrorCode::Database,
+            VoiceError::NotFound(_) => ErrorCode::NotFound,
+            VoiceError::Conflict(_) => ErrorCode::Conflict,
+            VoiceError::Tls(_) => ErrorCode::TlsHandshake,
+            VoiceError::Network(msg) if is_transient_message(msg) => ErrorCode::NetworkTimeout,
+            VoiceError::Network(_) => ErrorCode::Network,
+            VoiceError::Sync { message, .. } if is_transient_message(message) => ErrorCode::NetworkTimeout,
+            VoiceError::Sync { .. } => ErrorCode::Sync,
+            VoiceError::Config(_) => ErrorCode::Config,
+            VoiceError::Io(_) => ErrorCode::Io,
+            VoiceError::Json(_) => ErrorCode::Json,
+            VoiceError::Uuid(_) => ErrorCode::Uuid,
+            VoiceError::Cancelled(_) => ErrorCode::Cancelled,
+            VoiceError::Other { .. } => ErrorCode::Other,
+            VoiceError::Context { source, .. } => source.code(),
+        }
+    }
+
+    /// Render as the canonical wire format for the sync protocol:
+    /// `{ "code", "message", "field"?, "context"? }`. The companion of
+    /// [`VoiceError::from_wire`], which reconstructs the local variant (and,
+    /// via `code`, the correct Python exception class) from this value on
+    /// the receiving end.
+    pub fn to_wire(&self) -> serde_json::Value {
+        if let VoiceError::Context { source, context, .. } = self {
+            let mut value = source.to_wire();
+            if !context.is_empty() {
+                value["context"] = serde_json::json!(context);
+            }
+            return value;
+        }
+        let message = match self {
+            VoiceError::Validation { message, .. }
+            | VoiceError::DatabaseOperation { message, .. }
+            | VoiceError::Sync { message, .. }
+            | VoiceError::Other { message, .. } => message.clone(),
+            VoiceError::Network(msg)
+            | VoiceError::Tls(msg)
+            | VoiceError::Config(msg)
+            | VoiceError::NotFound(msg)
+            | VoiceError::Conflict(msg)
+            | VoiceError::Cancelled(msg) => msg.clone(),
+            other => other.to_string(),
+        };
+        let mut value = serde_json::json!({
+            "code": self.code(),
+            "message": message,
+        });
+        if let VoiceError::Validation { field, .. } = self {
+            value["field"] = serde_json::json!(field);
+        }
+        value
+    }
+
+    /// Reconstruct a [`VoiceError`] from [`VoiceError::to_wire`]'s JSON,
+    /// e.g. after receiving a failure response from a sync peer. Unknown or
+    /// missing `code`s fall back to [`VoiceError::Other`] rather than
+    /// failing the reconstruction itself.
+    pub fn from_wire(value: serde_json::Value) -> VoiceError {
+        let code = value.get("code").and_then(|c| c.as_str()).unwrap_or("OTHER");
+        let message = value
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("unknown error")
+            .to_string();
+        let err = match code {
+            "VALIDATION" => {
+                let field = value.get("field").and_then(|f| f.as_str()).unwrap_or("unknown").to_string();
+                VoiceError::Validation { field, message }
+            }
+            "DB_BUSY" | "DB_CONSTRAINT" | "DATABASE" => VoiceError::DatabaseOperation { message, source: None },
+            "NOT_FOUND" => VoiceError::NotFound(message),
+            "CONFLICT" => VoiceError::Conflict(message),
+            "TLS_HANDSHAKE" => VoiceError::Tls(message),
+            "NETWORK_TIMEOUT" | "NETWORK" => VoiceError::Network(message),
+            "SYNC" => VoiceError::Sync { message, source: None },
+            "CONFIG" => VoiceError::Config(message),
+            "CANCELLED" => VoiceError::Cancelled(message),
+            _ => VoiceError::Other { message, source: None },
+        };
+        match value.get("context").and_then(|c| c.as_object()) {
+            Some(context) if !context.is_empty() => VoiceError::Context {
+                source: Box::new(err),
+                context: context.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+                trace: Vec::new(),
+            },
+            _ => err,
+        }
+    }
+}
+
+/// Coarse severity level for a [`VoiceError`], modeled on Postgres's error
+/// response levels. Exposed to Python as a read-only `.severity` attribute
+/// (see [`attach_diagnostics`]) so retry loops don't have to parse messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Non-retryable: the caller's input or configuration is wrong.
+    Fatal,
+    /// Default: an unclassified failure; retrying is not known to help.
+    Error,
+    /// Transient: the same operation may succeed after a backoff.
+    Warning,
+}
+
+impl Severity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Severity::Fatal => "fatal",
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A message is treated as a transient failure (network/TLS handshake
+/// timeouts, dropped connections) when it looks like one; these errors are
+/// stored as plain strings, so this mirrors the substring classification
+/// [`validation_error_code`] already does for validation messages.
+fn is_transient_message(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    lower.contains("timeout")
+        || lower.contains("timed out")
+        || lower.contains("connection reset")
+        || lower.contains("connection refused")
+}
+
+/// `SQLITE_BUSY`/`SQLITE_LOCKED` indicate another connection is holding the
+/// lock right now, not that the query itself is wrong — safe to retry.
+fn is_retryable_sqlite_error(err: &rusqlite::Error) -> bool {
+    matches!(
+        err,
+        rusqlite::Error::SqliteFailure(ffi_err, _)
+            if matches!(ffi_err.code, rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+    )
+}
+
+/// A `UNIQUE`/`FOREIGN KEY`/`CHECK` violation — the query is well-formed but
+/// the data conflicts with a schema constraint, distinct from `DB_BUSY`.
+fn is_constraint_sqlite_error(err: &rusqlite::Error) -> bool {
+    matches!(
+        err,
+        rusqlite::Error::SqliteFailure(ffi_err, _) if ffi_err.code == rusqlite::ErrorCode::ConstraintViolation
+    )
+}
+
+/// Stable, machine-readable classification of a [`VoiceError`], modeled on
+/// Postgres's SQLSTATE classes and Polar's `FormattedPolarError`. Part of the
+/// public contract: this is what [`VoiceError::to_wire`] sends across the
+/// sync protocol and what every generated Python exception exposes as
+/// `.code`, so callers on either side can branch on it without matching on
+/// message text or Rust-side variant names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    Validation,
+    DbBusy,
+    DbConstraint,
+    Database,
+    NotFound,
+    Conflict,
+    TlsHandshake,
+    NetworkTimeout,
+    Network,
+    Sync,
+    Config,
+    Io,
+    Json,
+    Uuid,
+    Cancelled,
+    Other,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::Validation => "VALIDATION",
+            ErrorCode::DbBusy => "DB_BUSY",
+            ErrorCode::DbConstraint => "DB_CONSTRAINT",
+            ErrorCode::Database => "DATABASE",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::Conflict => "CONFLICT",
+            ErrorCode::TlsHandshake => "TLS_HANDSHAKE",
+            ErrorCode::NetworkTimeout => "NETWORK_TIMEOUT",
+            ErrorCode::Network => "NETWORK",
+            ErrorCode::Sync => "SYNC",
+            ErrorCode::Config => "CONFIG",
+            ErrorCode::Io => "IO",
+            ErrorCode::Json => "JSON",
+            ErrorCode::Uuid => "UUID",
+            ErrorCode::Cancelled => "CANCELLED",
+            ErrorCode::Other => "OTHER",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Hand-written rather than `#[derive(Serialize)]`: several variants wrap
+/// non-`Serialize` sources (`rusqlite::Error`, `std::io::Error`, ...), so we
+/// serialize the rendered message plus context instead — enough for the
+/// sync protocol's wire format and for telemetry.
+impl Serialize for VoiceError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("VoiceError", 3)?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("context", self.context())?;
+        state.serialize_field("trace", self.trace())?;
+        state.end()
+    }
+}
+
+/// A single `file:line (function)` propagation point recorded via
+/// [`VoiceError::traced`], mirroring the `err` crate's `Trace` — cheap to
+/// collect at each call site without a full backtrace capture.
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceRecord {
+    pub file: &'static str,
+    pub line: u32,
+    pub function: &'static str,
+}
+
+impl TraceRecord {
+    #[track_caller]
+    fn here(function: &'static str) -> Self {
+        let location = std::panic::Location::caller();
+        Self { file: location.file(), line: location.line(), function }
+    }
+}
+
+impl std::fmt::Display for TraceRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{} ({})", self.file, self.line, self.function)
+    }
+}
+
+/// Walks a [`VoiceError`]'s [`std::error::Error::source`] chain, printing
+/// each link on its own line as `"N: message"`, e.g.:
+///
+/// ```text
+/// 0: Sync error: push to peer failed
+///   caused by 1: connection timed out
+/// ```
+pub struct ErrorChain<'a>(pub &'a VoiceError);
+
+impl std::fmt::Display for ErrorChain<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // `Context`'s Display passes through to its wrapped source verbatim (it
+        // carries metadata, not its own message), so unwrap any of those
+        // before starting the chain to avoid printing the same line twice.
+        let mut root = self.0;
+        while let VoiceError::Context { source, .. } = root {
+            root = source;
+        }
+        write!(f, "0: {root}")?;
+        let mut depth = 1;
+        let mut cause: Option<&(dyn std::error::Error + 'static)> = std::error::Error::source(root);
+        while let Some(err) = cause {
+            write!(f, "\n  caused by {depth}: {err}")?;
+            cause = err.source();
+            depth += 1;
+        }
+        Ok(())
     }
 }
 
@@ -80,28 +510,194 @@ create_exception!(voice_core, PyDatabaseError, PyException);
 create_exception!(voice_core, PySyncError, PyException);
 create_exception!(voice_core, PyConfigError, PyException);
 create_exception!(voice_core, PyTlsError, PyException);
+create_exception!(voice_core, PyCancelledError, PyException, "Raised when a progress callback cancels a long-running operation.");
+create_exception!(voice_core, PyValidationErrors, PyValidationError, "Multiple validation errors collected together; see `.errors()`.");
+
+/// Classify a validation message into a coarse, machine-readable code so
+/// callers can branch on `e.code` instead of matching on message text.
+fn validation_error_code(message: &str) -> &'static str {
+    let lower = message.to_lowercase();
+    if lower.contains("cannot be empty") || lower.contains("required") {
+        "required"
+    } else if lower.contains("cannot exceed") || lower.contains("too long") {
+        "too_long"
+    } else if lower.contains("cannot be its own") || lower.contains("ambiguous") {
+        "invalid_reference"
+    } else if lower.contains("invalid") || lower.contains("format") {
+        "invalid_format"
+    } else {
+        "invalid"
+    }
+}
+
+/// Build a [`PyValidationError`] instance with `.field`, `.message`, and
+/// `.code` attributes attached directly to the exception object, rather
+/// than packing them into the formatted message string.
+fn new_py_validation_error(py: Python<'_>, field: &str, message: &str) -> PyErr {
+    let exc_type = py.get_type::<PyValidationError>();
+    let instance = exc_type
+        .call1((message,))
+        .unwrap_or_else(|_| exc_type.call0().expect("exception type must be constructible"));
+    let _ = instance.setattr("field", field);
+    let _ = instance.setattr("message", message);
+    let _ = instance.setattr("code", validation_error_code(message));
+    PyErr::from_value(instance)
+}
+
+/// Build a [`PyValidationErrors`] instance whose `.errors()` method returns the collected
+/// failures as a list of `{"field": ..., "message": ..., "type": ...}` dicts, mirroring
+/// pydantic-core's `ValidationError.errors()`. The instance's `__str__` falls back to the
+/// default `BaseException` behavior over the flat message we construct it with.
+fn new_py_validation_errors(py: Python<'_>, errs: &ValidationErrors) -> PyErr {
+    let exc_type = py.get_type::<PyValidationErrors>();
+    let instance = exc_type
+        .call1((errs.to_string(),))
+        .unwrap_or_else(|_| exc_type.call0().expect("exception type must be constructible"));
+    let _ = instance.setattr("title", &errs.title);
+
+    let error_dicts: Vec<(String, String, &'static str)> = errs
+        .errors
+        .iter()
+        .map(|e| (e.field.clone(), e.message.clone(), validation_error_code(&e.message)))
+        .collect();
+
+    let errors_method = pyo3::types::PyCFunction::new_closure(
+        py,
+        None,
+        None,
+        move |_args: &Bound<'_, pyo3::types::PyTuple>, _kwargs: Option<&Bound<'_, pyo3::types::PyDict>>| -> PyResult<PyObject> {
+            let py = _args.py();
+            let list = pyo3::types::PyList::empty(py);
+            for (field, message, error_type) in &error_dicts {
+                let dict = pyo3::types::PyDict::new(py);
+                dict.set_item("field", field)?;
+                dict.set_item("message", message)?;
+                dict.set_item("type", error_type)?;
+                list.append(dict)?;
+            }
+            Ok(list.into_any().unbind())
+        },
+    );
+    if let Ok(errors_method) = errors_method {
+        let _ = instance.setattr("errors", errors_method);
+    }
+
+    PyErr::from_value(instance)
+}
+
+/// Build a [`PyDatabaseError`] or [`PySyncError`] instance with `.kind` and,
+/// when known, the offending `.id` attached as attributes.
+fn new_py_kind_error(py: Python<'_>, exc_type: &Bound<'_, pyo3::types::PyType>, kind: &str, message: &str, id: Option<&str>) -> PyErr {
+    let instance = exc_type
+        .call1((message,))
+        .unwrap_or_else(|_| exc_type.call0().expect("exception type must be constructible"));
+    let _ = instance.setattr("kind", kind);
+    let _ = instance.setattr("message", message);
+    if let Some(id) = id {
+        let _ = instance.setattr("id", id);
+    }
+    PyErr::from_value(instance)
+}
+
+/// Build a Python dict from a context map, converting each `serde_json::Value`
+/// with [`json_value_to_pyobject`].
+fn context_to_pydict(py: Python<'_>, context: &BTreeMap<String, serde_json::Value>) -> PyResult<Py<pyo3::types::PyDict>> {
+    let dict = pyo3::types::PyDict::new(py);
+    for (key, value) in context {
+        dict.set_item(key, json_value_to_pyobject(py, value)?)?;
+    }
+    Ok(dict.unbind())
+}
+
+/// Attach read-only `.severity` and `.is_retryable` attributes to a `PyErr`
+/// so a retry-with-backoff loop around sync can be written in either
+/// language without string-matching error messages.
+fn attach_diagnostics(py: Python<'_>, py_err: &PyErr, severity: Severity) {
+    let _ = py_err.value(py).setattr("severity", severity.as_str());
+    let _ = py_err.value(py).setattr("is_retryable", severity == Severity::Warning);
+}
+
+/// Build a Python list of `{"file": ..., "line": ..., "function": ...}` dicts
+/// from a trace recorded via [`VoiceError::traced`].
+fn trace_to_pylist(py: Python<'_>, trace: &[TraceRecord]) -> PyResult<Py<pyo3::types::PyList>> {
+    let list = pyo3::types::PyList::empty(py);
+    for record in trace {
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("file", record.file)?;
+        dict.set_item("line", record.line)?;
+        dict.set_item("function", record.function)?;
+        list.append(dict)?;
+    }
+    Ok(list.unbind())
+}
+
+/// Populate `__cause__` from a `Sync`/`DatabaseOperation`/`Other` variant's
+/// attached source, if any, so `traceback.format_exception` on the Python
+/// side shows the full chain without needing [`ErrorChain`].
+fn attach_cause(py: Python<'_>, py_err: &PyErr, source: &Option<Box<dyn std::error::Error + Send + Sync>>) {
+    if let Some(source) = source {
+        py_err.set_cause(py, Some(PyRuntimeError::new_err(source.to_string())));
+    }
+}
 
 impl From<VoiceError> for PyErr {
     fn from(err: VoiceError) -> PyErr {
-        match err {
-            VoiceError::Validation { field, message } => {
-                // Store field and message in the exception args for extraction
-                let err_msg = format!("{}:{}", field, message);
-                PyValidationError::new_err(err_msg)
+        if let VoiceError::Context { source, context, trace } = err {
+            // `source.into()` below attaches severity/is_retryable/code/__cause__
+            // for us, since `Context`'s severity and code are defined to match
+            // its wrapped source and its Display passes through to it verbatim.
+            let py_err: PyErr = (*source).into();
+            Python::with_gil(|py| {
+                if !context.is_empty() {
+                    if let Ok(dict) = context_to_pydict(py, &context) {
+                        let _ = py_err.value(py).setattr("context", dict);
+                    }
+                }
+                if !trace.is_empty() {
+                    if let Ok(list) = trace_to_pylist(py, &trace) {
+                        let _ = py_err.value(py).setattr("trace", list);
+                    }
+                }
+            });
+            return py_err;
+        }
+        let severity = err.severity();
+        let code = err.code();
+        let py_err = Python::with_gil(|py| match &err {
+            VoiceError::Validation { field, message } => new_py_validation_error(py, field, message),
+            VoiceError::Database(e) => new_py_kind_error(py, &py.get_type::<PyDatabaseError>(), "database", &e.to_string(), None),
+            VoiceError::DatabaseOperation { message, source } => {
+                let py_err = new_py_kind_error(py, &py.get_type::<PyDatabaseError>(), "operation", message, None);
+                attach_cause(py, &py_err, source);
+                py_err
             }
-            VoiceError::Database(e) => PyDatabaseError::new_err(e.to_string()),
-            VoiceError::DatabaseOperation(msg) => PyDatabaseError::new_err(msg),
-            VoiceError::Sync(msg) => PySyncError::new_err(msg),
-            VoiceError::Network(msg) => PySyncError::new_err(format!("Network: {}", msg)),
-            VoiceError::Tls(msg) => PyTlsError::new_err(msg),
-            VoiceError::Config(msg) => PyConfigError::new_err(msg),
+            VoiceError::Sync { message, source } => {
+                let py_err = new_py_kind_error(py, &py.get_type::<PySyncError>(), "sync", message, None);
+                attach_cause(py, &py_err, source);
+                py_err
+            }
+            VoiceError::Network(msg) => new_py_kind_error(py, &py.get_type::<PySyncError>(), "network", msg, None),
+            VoiceError::Tls(msg) => PyTlsError::new_err(msg.clone()),
+            VoiceError::Config(msg) => PyConfigError::new_err(msg.clone()),
             VoiceError::Io(e) => PyRuntimeError::new_err(e.to_string()),
             VoiceError::Json(e) => PyValueError::new_err(e.to_string()),
-            VoiceError::Uuid(e) => PyValidationError::new_err(format!("uuid:{}", e)),
-            VoiceError::NotFound(msg) => PyValueError::new_err(format!("Not found: {}", msg)),
-            VoiceError::Conflict(msg) => PyRuntimeError::new_err(format!("Conflict: {}", msg)),
-            VoiceError::Other(msg) => PyRuntimeError::new_err(msg),
-        }
+            VoiceError::Uuid(e) => new_py_validation_error(py, "uuid", &e.to_string()),
+            VoiceError::NotFound(msg) => new_py_kind_error(py, &py.get_type::<PyDatabaseError>(), "not_found", msg, Some(msg)),
+            VoiceError::Conflict(msg) => new_py_kind_error(py, &py.get_type::<PyDatabaseError>(), "conflict", msg, Some(msg)),
+            VoiceError::Cancelled(msg) => PyCancelledError::new_err(msg.clone()),
+            VoiceError::ValidationErrors(errs) => new_py_validation_errors(py, errs),
+            VoiceError::Other { message, source } => {
+                let py_err = PyRuntimeError::new_err(message.clone());
+                attach_cause(py, &py_err, source);
+                py_err
+            }
+            VoiceError::Context { .. } => unreachable!("handled above"),
+        });
+        Python::with_gil(|py| {
+            attach_diagnostics(py, &py_err, severity);
+            let _ = py_err.value(py).setattr("code", code.as_str());
+        });
+        py_err
     }
 }
 
@@ -140,7 +736,67 @@ impl From<ValidationError> for VoiceError {
 
 impl From<ValidationError> for PyErr {
     fn from(err: ValidationError) -> PyErr {
-        PyValidationError::new_err(format!("{}: {}", err.field, err.message))
+        Python::with_gil(|py| {
+            let py_err = new_py_validation_error(py, &err.field, &err.message);
+            let _ = py_err.value(py).setattr("code", ErrorCode::Validation.as_str());
+            py_err
+        })
+    }
+}
+
+/// A collection of [`ValidationError`]s gathered from a single validation pass
+/// (e.g. validating every field of a note before insert), plus an overall
+/// `title` describing what was being validated. Mirrors the shape of
+/// pydantic-core's `ValidationError`: many individual field errors surfaced
+/// together instead of bailing out on the first one.
+#[derive(Debug, Clone)]
+pub struct ValidationErrors {
+    pub title: String,
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationErrors {
+    /// Start an empty error collection for the given title (e.g. "Note").
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Record one more field failure.
+    pub fn push(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(ValidationError::new(field, message));
+    }
+
+    /// Append all errors from another collection, discarding its title.
+    pub fn extend(&mut self, other: ValidationErrors) {
+        self.errors.extend(other.errors);
+    }
+
+    /// True if no failures have been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Resolve a validation pass: `Ok(value)` if nothing failed, otherwise
+    /// `Err(VoiceError::ValidationErrors(self))` with every collected failure.
+    pub fn into_result<T>(self, value: T) -> VoiceResult<T> {
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(VoiceError::ValidationErrors(self))
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} validation error(s) for {}", self.errors.len(), self.title)?;
+        for err in &self.errors {
+            write!(f, "\n  {err}")?;
+        }
+        Ok(())
     }
 }
 
@@ -159,4 +815,149 @@ mod tests {
         let err = VoiceError::validation("field", "message");
         assert!(matches!(err, VoiceError::Validation { .. }));
     }
+
+    #[test]
+    fn test_validation_errors_into_result() {
+        let errs = ValidationErrors::new("Note");
+        assert!(errs.into_result(42).is_ok());
+    }
+
+    #[test]
+    fn test_with_context_accumulates() {
+        let err = VoiceError::sync("peer unreachable")
+            .with_context("peer", "device-42")
+            .with_context("attempt", 3);
+        assert_eq!(err.context().get("peer").unwrap(), "device-42");
+        assert_eq!(err.context().get("attempt").unwrap(), 3);
+        assert_eq!(err.to_string(), "Sync error: peer unreachable");
+    }
+
+    #[test]
+    fn test_context_empty_by_default() {
+        let err = VoiceError::sync("peer unreachable");
+        assert!(err.context().is_empty());
+    }
+
+    #[test]
+    fn test_validation_errors_accumulate() {
+        let mut errs = ValidationErrors::new("Note");
+        errs.push("title", "cannot be empty");
+        errs.push("body", "too long");
+        assert_eq!(errs.errors.len(), 2);
+        match errs.into_result(()) {
+            Err(VoiceError::ValidationErrors(errs)) => assert_eq!(errs.errors.len(), 2),
+            _ => panic!("expected aggregated validation error"),
+        }
+    }
+
+    #[test]
+    fn test_severity_fatal_not_retryable() {
+        let err = VoiceError::validation("field", "message");
+        assert_eq!(err.severity(), Severity::Fatal);
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_severity_transient_network_is_retryable() {
+        let err = VoiceError::Network("connection to peer timed out".to_string());
+        assert_eq!(err.severity(), Severity::Warning);
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn test_severity_default_is_error() {
+        let err = VoiceError::NotFound("note".to_string());
+        assert_eq!(err.severity(), Severity::Error);
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_severity_propagates_through_context() {
+        let err = VoiceError::Network("timeout".to_string()).with_context("peer", "device-42");
+        assert_eq!(err.severity(), Severity::Warning);
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn test_source_reports_via_std_error() {
+        let cause = std::io::Error::new(std::io::ErrorKind::TimedOut, "deadline exceeded");
+        let err = VoiceError::sync("push to peer failed").with_source(cause);
+        let source = std::error::Error::source(&err).expect("source should be attached");
+        assert_eq!(source.to_string(), "deadline exceeded");
+    }
+
+    #[test]
+    fn test_with_source_is_noop_on_unsupported_variant() {
+        let cause = std::io::Error::new(std::io::ErrorKind::Other, "ignored");
+        let err = VoiceError::NotFound("note".to_string()).with_source(cause);
+        assert!(std::error::Error::source(&err).is_none());
+    }
+
+    #[test]
+    fn test_error_chain_walks_source() {
+        let cause = std::io::Error::new(std::io::ErrorKind::TimedOut, "deadline exceeded");
+        let err = VoiceError::sync("push to peer failed").with_source(cause);
+        let chain = ErrorChain(&err).to_string();
+        assert_eq!(chain, "0: Sync error: push to peer failed\n  caused by 1: deadline exceeded");
+    }
+
+    #[test]
+    fn test_error_chain_skips_context_wrapper() {
+        let err = VoiceError::sync("push to peer failed").with_context("peer", "device-42");
+        let chain = ErrorChain(&err).to_string();
+        assert_eq!(chain, "0: Sync error: push to peer failed");
+    }
+
+    #[test]
+    fn test_traced_accumulates_records() {
+        let err = VoiceError::sync("push failed").traced("push_changes").traced("sync_with_peer");
+        assert_eq!(err.trace().len(), 2);
+        assert_eq!(err.trace()[0].function, "push_changes");
+        assert_eq!(err.trace()[1].function, "sync_with_peer");
+    }
+
+    #[test]
+    fn test_code_matches_variant() {
+        assert_eq!(VoiceError::validation("field", "message").code(), ErrorCode::Validation);
+        assert_eq!(VoiceError::NotFound("note".to_string()).code(), ErrorCode::NotFound);
+        assert_eq!(VoiceError::Network("connection refused".to_string()).code(), ErrorCode::NetworkTimeout);
+        assert_eq!(VoiceError::Network("bad gateway".to_string()).code(), ErrorCode::Network);
+    }
+
+    #[test]
+    fn test_code_propagates_through_context() {
+        let err = VoiceError::NotFound("note".to_string()).with_context("id", "abc123");
+        assert_eq!(err.code(), ErrorCode::NotFound);
+    }
+
+    #[test]
+    fn test_to_wire_round_trips_through_from_wire() {
+        let err = VoiceError::validation("title", "cannot be empty");
+        let wire = err.to_wire();
+        assert_eq!(wire["code"], "VALIDATION");
+        assert_eq!(wire["field"], "title");
+
+        let restored = VoiceError::from_wire(wire);
+        assert_eq!(restored.code(), ErrorCode::Validation);
+        assert_eq!(restored.to_string(), "Validation error in title: cannot be empty");
+    }
+
+    #[test]
+    fn test_to_wire_carries_context() {
+        let err = VoiceError::NotFound("note".to_string()).with_context("id", "abc123");
+        let wire = err.to_wire();
+        assert_eq!(wire["code"], "NOT_FOUND");
+        assert_eq!(wire["context"]["id"], "abc123");
+
+        let restored = VoiceError::from_wire(wire);
+        assert_eq!(restored.context().get("id").unwrap(), "abc123");
+    }
+
+    #[test]
+    fn test_from_wire_unknown_code_falls_back_to_other() {
+        let wire = serde_json::json!({ "code": "SOMETHING_NEW", "message": "unexpected" });
+        let restored = VoiceError::from_wire(wire);
+        assert_eq!(restored.code(), ErrorCode::Other);
+        assert_eq!(restored.to_string(), "unexpected");
+    }
 }
@@ -0,0 +1,161 @@
+//! Fractional indexing for ordering siblings under a parent note (see
+//! [`crate::models::Note::position`]), so inserting a note between two existing ones is a
+//! single row write instead of renumbering every sibling that follows it.
+//!
+//! A position is a string over a fixed 62-character digit alphabet (`0-9A-Za-z`, already in
+//! ascending ASCII order so plain string comparison sorts positions correctly). To insert
+//! between two keys, [`key_between`] copies their shared prefix, then picks a digit strictly
+//! between the two keys' next digit - appending an extra digit first when those digits are
+//! adjacent and there's no room for one in between.
+
+use pyo3::prelude::*;
+
+use crate::error::{VoiceError, VoiceResult};
+
+const ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const BASE: usize = 62;
+
+fn digit_value(c: char) -> usize {
+    ALPHABET
+        .iter()
+        .position(|&b| b == c as u8)
+        .expect("position string contained a character outside the fractional-index alphabet")
+}
+
+fn digit_char(value: usize) -> char {
+    ALPHABET[value] as char
+}
+
+/// The digit at `index` in `s`, or `0` (the lowest digit) if `s` is too short - a string is
+/// the same fractional value as itself with trailing zero digits, so padding like this is
+/// how comparisons against a longer key are meant to work.
+fn digit_at(s: &str, index: usize) -> usize {
+    s.chars().nth(index).map(digit_value).unwrap_or(0)
+}
+
+/// Generate a key between `lower` (digit-by-digit, or an unbounded ceiling if `upper` is
+/// `None`) and `upper`. Returns an error if `lower >= upper`, since there's no key that
+/// sorts strictly between two equal (or out-of-order) bounds - without this check, the
+/// digit-matching loop never finds a gap and runs forever once both strings are exhausted.
+fn build_key(lower: &str, upper: Option<&str>) -> VoiceResult<String> {
+    if let Some(upper) = upper {
+        if lower >= upper {
+            return Err(VoiceError::validation(
+                "position",
+                format!("lower bound {lower:?} must sort strictly before upper bound {upper:?}"),
+            ));
+        }
+    }
+
+    let mut result = String::new();
+    let mut unconstrained = upper.is_none();
+    let mut index = 0;
+    loop {
+        let l = digit_at(lower, index);
+        let u = if unconstrained { BASE } else { digit_at(upper.expect("checked above"), index) };
+        if u > l + 1 {
+            let mid = l + (u - l) / 2;
+            result.push(digit_char(mid));
+            return Ok(result);
+        } else if u == l + 1 {
+            result.push(digit_char(l));
+            unconstrained = true;
+            index += 1;
+        } else {
+            debug_assert_eq!(u, l, "build_key called with lower >= upper");
+            result.push(digit_char(l));
+            index += 1;
+        }
+    }
+}
+
+/// Generate a position key that sorts strictly between `lower` and `upper`.
+///
+/// `lower: None` means "insert at the head" (nothing comes before); `upper: None` means
+/// "insert at the tail" (nothing comes after, including when the sibling list is empty).
+/// Passing both as `None` is the first note ever under a parent. Returns an error if
+/// `lower` and `upper` are both given and `lower` does not sort strictly before `upper`.
+pub fn key_between(lower: Option<&str>, upper: Option<&str>) -> VoiceResult<String> {
+    build_key(lower.unwrap_or(""), upper)
+}
+
+// ============================================================================
+// Python bindings
+// ============================================================================
+
+#[pyfunction]
+#[pyo3(name = "key_between", signature = (lower=None, upper=None))]
+pub fn py_key_between(lower: Option<&str>, upper: Option<&str>) -> PyResult<String> {
+    Ok(key_between(lower, upper)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_into_empty_list() {
+        let key = key_between(None, None).unwrap();
+        assert!(!key.is_empty());
+    }
+
+    #[test]
+    fn test_head_insert() {
+        let first = key_between(None, None).unwrap();
+        let head = key_between(None, Some(&first)).unwrap();
+        assert!(head.as_str() < first.as_str());
+    }
+
+    #[test]
+    fn test_tail_insert() {
+        let first = key_between(None, None).unwrap();
+        let tail = key_between(Some(&first), None).unwrap();
+        assert!(tail.as_str() > first.as_str());
+    }
+
+    #[test]
+    fn test_insert_between_two_keys() {
+        let a = key_between(None, None).unwrap();
+        let c = key_between(Some(&a), None).unwrap();
+        let b = key_between(Some(&a), Some(&c)).unwrap();
+        assert!(a.as_str() < b.as_str());
+        assert!(b.as_str() < c.as_str());
+    }
+
+    #[test]
+    fn test_repeated_subdivision_stays_ordered() {
+        let mut keys = vec![key_between(None, None).unwrap()];
+        for _ in 0..20 {
+            let k = key_between(None, Some(&keys[0])).unwrap();
+            keys.insert(0, k);
+        }
+        for pair in keys.windows(2) {
+            assert!(pair[0].as_str() < pair[1].as_str());
+        }
+    }
+
+    #[test]
+    fn test_adjacent_digits_extend_rather_than_collide() {
+        // "5" and "6" are adjacent single characters - there's no digit strictly between
+        // them, so the result must be longer than either input.
+        let lower = digit_char(5).to_string();
+        let upper = digit_char(6).to_string();
+        let mid = key_between(Some(&lower), Some(&upper)).unwrap();
+        assert!(mid.as_str() > lower.as_str());
+        assert!(mid.as_str() < upper.as_str());
+        assert!(mid.len() > 1);
+    }
+
+    #[test]
+    fn test_equal_bounds_is_rejected_instead_of_hanging() {
+        let key = key_between(None, None).unwrap();
+        assert!(key_between(Some(&key), Some(&key)).is_err());
+    }
+
+    #[test]
+    fn test_out_of_order_bounds_is_rejected() {
+        let a = key_between(None, None).unwrap();
+        let b = key_between(Some(&a), None).unwrap();
+        assert!(key_between(Some(&b), Some(&a)).is_err());
+    }
+}
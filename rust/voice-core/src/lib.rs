@@ -1,7 +1,7 @@
 //! Voice Core - Rust implementation of the Voice note-taking application core.
 //!
 //! This library provides the core functionality for Voice:
-//! - Data models (Note, Tag, NoteTag)
+//! - Data models (Note, Tag, NoteTag, NoteLink)
 //! - Database operations (SQLite)
 //! - Sync protocol (client and server)
 //! - Conflict resolution
@@ -11,23 +11,29 @@
 //! enabling the existing Python UI (Qt, Textual, CLI) to use Rust for
 //! all business logic.
 
+pub mod acme;
+pub mod chunking;
 pub mod config;
 pub mod conflicts;
 pub mod database;
+pub mod discovery;
 pub mod error;
+pub mod fractional_index;
 pub mod merge;
+pub mod merkle;
 pub mod models;
 pub mod search;
 pub mod sync_client;
 pub mod sync_server;
 pub mod tls;
+pub mod trust_graph;
 pub mod validation;
 
 // Re-export commonly used types
 pub use config::Config;
 pub use database::Database;
 pub use error::{VoiceError, VoiceResult};
-pub use models::{Note, NoteTag, Tag};
+pub use models::{Note, NoteLink, NoteTag, Tag};
 pub use error::ValidationError;
 
 use pyo3::prelude::*;
@@ -39,14 +45,18 @@ fn voice_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("ValidationError", m.py().get_type::<error::PyValidationError>())?;
     m.add("DatabaseError", m.py().get_type::<error::PyDatabaseError>())?;
     m.add("SyncError", m.py().get_type::<error::PySyncError>())?;
+    m.add("CancelledError", m.py().get_type::<error::PyCancelledError>())?;
 
     // Register model classes
     m.add_class::<models::PyNote>()?;
     m.add_class::<models::PyTag>()?;
     m.add_class::<models::PyNoteTag>()?;
+    m.add_class::<models::PyNoteLink>()?;
+    m.add_function(wrap_pyfunction!(models::py_set_default_time_format, m)?)?;
 
     // Register database class
     m.add_class::<database::PyDatabase>()?;
+    m.add_class::<database::PyTransaction>()?;
 
     // Register config class
     m.add_class::<config::PyConfig>()?;
@@ -56,11 +66,16 @@ fn voice_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<search::PyParsedSearch>()?;
     m.add_function(wrap_pyfunction!(search::py_parse_search_input, m)?)?;
     m.add_function(wrap_pyfunction!(search::py_execute_search, m)?)?;
+    m.add_function(wrap_pyfunction!(search::py_upsert_note_embedding, m)?)?;
+    m.add_function(wrap_pyfunction!(search::py_execute_semantic_search, m)?)?;
 
     // Register merge functions
     m.add_class::<merge::PyMergeResult>()?;
     m.add_function(wrap_pyfunction!(merge::py_merge_content, m)?)?;
 
+    // Register fractional-indexing helper
+    m.add_function(wrap_pyfunction!(fractional_index::py_key_between, m)?)?;
+
     // Register conflict manager
     m.add_class::<conflicts::PyConflictManager>()?;
     m.add_class::<conflicts::PyNoteContentConflict>()?;
@@ -77,6 +92,8 @@ fn voice_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(validation::py_validate_note_id, m)?)?;
     m.add_function(wrap_pyfunction!(validation::py_validate_tag_id, m)?)?;
     m.add_function(wrap_pyfunction!(validation::py_validate_tag_name, m)?)?;
+    m.add_function(wrap_pyfunction!(validation::py_validate_and_normalize_tag_name, m)?)?;
+    m.add_function(wrap_pyfunction!(validation::py_validate_and_normalize_tag_path, m)?)?;
     m.add_function(wrap_pyfunction!(validation::py_validate_note_content, m)?)?;
     m.add_function(wrap_pyfunction!(validation::py_validate_search_query, m)?)?;
 
@@ -0,0 +1,141 @@
+//! Simple two-way line-based content merge, used to reconcile a note's content when both
+//! sides have edited it since the last sync (see [`crate::conflicts`] for how the result
+//! of a non-clean merge is recorded as a conflict for the user to resolve), plus an RFC
+//! 7386 JSON Merge Patch applier used to apply field-level sync diffs (see
+//! [`crate::sync_server`]'s `"patch"` change encoding).
+
+use pyo3::prelude::*;
+use serde_json::Value;
+
+/// Outcome of merging two versions of a note's content.
+#[derive(Debug, Clone)]
+pub struct MergeResult {
+    pub content: String,
+    pub has_conflicts: bool,
+    pub conflict_count: usize,
+}
+
+/// Merge `local` and `remote` content line-by-line.
+///
+/// Lines shared as a common prefix or suffix are kept as-is. The differing middle section
+/// is wrapped in git-style conflict markers (`<<<<<<< {local_label}` / `=======` /
+/// `>>>>>>> {remote_label}`) when both sides actually changed it; if only one side changed
+/// the middle (the other is empty there), that side's version is taken without a conflict.
+pub fn merge_content(local: &str, remote: &str, local_label: &str, remote_label: &str) -> MergeResult {
+    let local_lines: Vec<&str> = local.lines().collect();
+    let remote_lines: Vec<&str> = remote.lines().collect();
+
+    let mut prefix_len = 0;
+    while prefix_len < local_lines.len()
+        && prefix_len < remote_lines.len()
+        && local_lines[prefix_len] == remote_lines[prefix_len]
+    {
+        prefix_len += 1;
+    }
+
+    let mut suffix_len = 0;
+    while suffix_len < local_lines.len() - prefix_len
+        && suffix_len < remote_lines.len() - prefix_len
+        && local_lines[local_lines.len() - 1 - suffix_len] == remote_lines[remote_lines.len() - 1 - suffix_len]
+    {
+        suffix_len += 1;
+    }
+
+    let local_middle = &local_lines[prefix_len..local_lines.len() - suffix_len];
+    let remote_middle = &remote_lines[prefix_len..remote_lines.len() - suffix_len];
+
+    let mut lines: Vec<String> = local_lines[..prefix_len].iter().map(|s| s.to_string()).collect();
+    let has_conflicts;
+    let conflict_count;
+
+    if local_middle.is_empty() && remote_middle.is_empty() {
+        has_conflicts = false;
+        conflict_count = 0;
+    } else if local_middle.is_empty() {
+        lines.extend(remote_middle.iter().map(|s| s.to_string()));
+        has_conflicts = false;
+        conflict_count = 0;
+    } else if remote_middle.is_empty() {
+        lines.extend(local_middle.iter().map(|s| s.to_string()));
+        has_conflicts = false;
+        conflict_count = 0;
+    } else if local_middle == remote_middle {
+        lines.extend(local_middle.iter().map(|s| s.to_string()));
+        has_conflicts = false;
+        conflict_count = 0;
+    } else {
+        lines.push(format!("<<<<<<< {local_label}"));
+        lines.extend(local_middle.iter().map(|s| s.to_string()));
+        lines.push("=======".to_string());
+        lines.extend(remote_middle.iter().map(|s| s.to_string()));
+        lines.push(format!(">>>>>>> {remote_label}"));
+        has_conflicts = true;
+        conflict_count = 1;
+    }
+
+    lines.extend(local_lines[local_lines.len() - suffix_len..].iter().map(|s| s.to_string()));
+
+    MergeResult {
+        content: lines.join("\n"),
+        has_conflicts,
+        conflict_count,
+    }
+}
+
+/// Apply an RFC 7386 JSON Merge Patch to `target` in place: an object in `patch` recurses
+/// key by key, a `null` value deletes the corresponding key from `target`, and any other
+/// value (including arrays and scalars) replaces whatever was there wholesale. A non-object
+/// `patch` replaces `target` outright, same as the spec's top-level case.
+pub fn apply_json_merge_patch(target: &mut Value, patch: &Value) {
+    let Some(patch_obj) = patch.as_object() else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+    let target_obj = target.as_object_mut().expect("just ensured target is an object");
+    for (key, value) in patch_obj {
+        if value.is_null() {
+            target_obj.remove(key);
+        } else {
+            let entry = target_obj.entry(key.clone()).or_insert(Value::Null);
+            apply_json_merge_patch(entry, value);
+        }
+    }
+}
+
+// ============================================================================
+// Python bindings
+// ============================================================================
+
+#[pyclass(name = "MergeResult")]
+pub struct PyMergeResult {
+    inner: MergeResult,
+}
+
+#[pymethods]
+impl PyMergeResult {
+    #[getter]
+    fn content(&self) -> &str {
+        &self.inner.content
+    }
+
+    #[getter]
+    fn has_conflicts(&self) -> bool {
+        self.inner.has_conflicts
+    }
+
+    #[getter]
+    fn conflict_count(&self) -> usize {
+        self.inner.conflict_count
+    }
+}
+
+#[pyfunction]
+#[pyo3(name = "merge_content")]
+pub fn py_merge_content(local: &str, remote: &str, local_label: &str, remote_label: &str) -> PyMergeResult {
+    PyMergeResult {
+        inner: merge_content(local, remote, local_label, remote_label),
+    }
+}
@@ -0,0 +1,82 @@
+//! Hashing helpers for the `/sync/merkle` anti-entropy tree.
+//!
+//! Entities are partitioned by a prefix of their ID bytes: a leaf bucket covers
+//! every entity whose ID starts with the same [`LEAF_PREFIX_LEN`] bytes, and a
+//! branch node above it covers every leaf sharing the branch's single-byte
+//! prefix. [`crate::database::Database::merkle_touch`] keeps the persisted leaf
+//! and branch hashes for one entity's prefix up to date after every write, so
+//! two peers comparing a root hash can walk down to exactly the bucket that
+//! diverged in a couple of round trips instead of scanning the whole dataset.
+
+use sha2::{Digest, Sha256};
+
+use crate::database::HlcStamp;
+
+/// Byte length of a leaf bucket's prefix.
+pub const LEAF_PREFIX_LEN: usize = 2;
+
+/// Hash of one leaf bucket: the sorted `(entity_id, hlc)` pairs of every entity
+/// whose ID falls in that bucket. Sorting first makes the hash independent of
+/// insertion order, so two peers with the same logical contents always agree.
+pub fn leaf_hash(entries: &mut [(String, HlcStamp)]) -> [u8; 32] {
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut hasher = Sha256::new();
+    for (entity_id, hlc) in entries.iter() {
+        hasher.update(entity_id.as_bytes());
+        hasher.update(hlc.to_json().as_bytes());
+    }
+    hasher.finalize().into()
+}
+
+/// Hash of a branch node: the sorted `(child_byte, child_hash)` pairs of
+/// whichever children currently exist below it. A child with no entities below
+/// it simply has no row and contributes nothing, so two empty subtrees still
+/// compare equal.
+pub fn branch_hash(mut children: Vec<(u8, [u8; 32])>) -> [u8; 32] {
+    children.sort_by_key(|(byte, _)| *byte);
+    let mut hasher = Sha256::new();
+    for (byte, hash) in children {
+        hasher.update([byte]);
+        hasher.update(hash);
+    }
+    hasher.finalize().into()
+}
+
+/// Render a hash (or any byte slice) as a lowercase hex string for the wire format.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hex_nibble(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Parse a hex string of any length (e.g. a hash) back into bytes. Works over raw bytes
+/// rather than slicing the `&str` by character count, so arbitrary (including non-ASCII)
+/// input is rejected with `None` instead of panicking on a byte index that splits a
+/// multi-byte UTF-8 character.
+pub fn from_hex(raw: &str) -> Option<Vec<u8>> {
+    let bytes = raw.as_bytes();
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    bytes
+        .chunks(2)
+        .map(|pair| Some((hex_nibble(pair[0])? << 4) | hex_nibble(pair[1])?))
+        .collect()
+}
+
+/// Parse a hex-encoded prefix, rejecting anything longer than [`LEAF_PREFIX_LEN`] bytes.
+pub fn parse_prefix_hex(raw: &str) -> Option<Vec<u8>> {
+    let bytes = from_hex(raw)?;
+    if bytes.len() > LEAF_PREFIX_LEN {
+        None
+    } else {
+        Some(bytes)
+    }
+}
@@ -1,13 +1,156 @@
 //! Data models for Voice.
 //!
-//! This module defines the core entities: Note, Tag, and NoteTag.
+//! This module defines the core entities: Note, Tag, NoteTag, and NoteLink.
 //! All IDs are UUID7 stored as 16 bytes internally, converted to hex strings for JSON/Python.
 
-use chrono::{DateTime, Utc};
+use std::sync::{Mutex, OnceLock};
+
+use chrono::{DateTime, TimeZone, Utc};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Output format used by the Python getters when converting a timestamp to a value Python
+/// can consume. Constructors accept either an RFC 3339 string or a Unix timestamp (`int`)
+/// regardless of this setting - it only controls what the getters hand back, via
+/// [`set_default_time_format`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// `"2024-01-02T03:04:05+00:00"` - the default, and what every getter returned before
+    /// this setting existed.
+    Rfc3339,
+    /// Whole seconds since the Unix epoch, as an `int`.
+    UnixSeconds,
+    /// Whole milliseconds since the Unix epoch, as an `int`.
+    UnixMillis,
+    /// A `chrono` strftime pattern, for callers that need a specific on-the-wire shape.
+    Custom(String),
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        TimeFormat::Rfc3339
+    }
+}
+
+fn default_time_format_cell() -> &'static Mutex<TimeFormat> {
+    static DEFAULT_TIME_FORMAT: OnceLock<Mutex<TimeFormat>> = OnceLock::new();
+    DEFAULT_TIME_FORMAT.get_or_init(|| Mutex::new(TimeFormat::default()))
+}
+
+/// Read the module-wide default timestamp output format (see [`set_default_time_format`]).
+pub fn default_time_format() -> TimeFormat {
+    default_time_format_cell().lock().unwrap().clone()
+}
+
+/// Set the module-wide default timestamp output format used by every `created_at` /
+/// `modified_at` / `deleted_at` getter that doesn't specify one of its own.
+pub fn set_default_time_format(format: TimeFormat) {
+    *default_time_format_cell().lock().unwrap() = format;
+}
+
+/// Render `dt` using `format`, as a Python object - a `str` for every format except
+/// [`TimeFormat::UnixSeconds`]/[`TimeFormat::UnixMillis`], which come back as `int` so
+/// callers doing arithmetic on them don't need to parse a string first.
+fn format_timestamp(py: Python<'_>, dt: DateTime<Utc>, format: &TimeFormat) -> PyResult<PyObject> {
+    match format {
+        TimeFormat::Rfc3339 => Ok(dt.to_rfc3339().into_pyobject(py)?.into_any().unbind()),
+        TimeFormat::UnixSeconds => Ok(dt.timestamp().into_pyobject(py)?.into_any().unbind()),
+        TimeFormat::UnixMillis => Ok(dt.timestamp_millis().into_pyobject(py)?.into_any().unbind()),
+        TimeFormat::Custom(pattern) => Ok(dt.format(pattern).to_string().into_pyobject(py)?.into_any().unbind()),
+    }
+}
+
+/// Parse a timestamp handed in from Python: an RFC 3339 string, or a Unix timestamp (`int`)
+/// in seconds or milliseconds - the scale is inferred from magnitude, since a row pulled
+/// from storage may use either and forcing callers to specify which would just move the bug
+/// from "wrong timestamp" to "wrong scale argument".
+fn parse_timestamp(value: &Bound<'_, PyAny>) -> PyResult<DateTime<Utc>> {
+    if let Ok(s) = value.extract::<String>() {
+        return DateTime::parse_from_rfc3339(&s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()));
+    }
+    if let Ok(n) = value.extract::<i64>() {
+        // A seconds-scale Unix timestamp this large would land in the year ~5138; real rows
+        // never get there, so treat anything past it as milliseconds instead.
+        const SECONDS_MAGNITUDE_CUTOFF: i64 = 100_000_000_000;
+        let parsed = if n.abs() >= SECONDS_MAGNITUDE_CUTOFF {
+            Utc.timestamp_millis_opt(n).single()
+        } else {
+            Utc.timestamp_opt(n, 0).single()
+        };
+        return parsed
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("timestamp {n} is out of range")));
+    }
+    Err(pyo3::exceptions::PyValueError::new_err(
+        "timestamp must be a str (RFC 3339) or int (Unix seconds/milliseconds)",
+    ))
+}
+
+fn parse_optional_timestamp(value: Option<&Bound<'_, PyAny>>) -> PyResult<Option<DateTime<Utc>>> {
+    value.map(parse_timestamp).transpose()
+}
+
+/// Set the module-wide default timestamp output format. `format` is one of `"rfc3339"`,
+/// `"unix_seconds"`, `"unix_millis"`, or `"custom"` (in which case `pattern` is required
+/// and must be a `chrono` strftime pattern).
+#[pyfunction]
+#[pyo3(name = "set_default_time_format", signature = (format, pattern=None))]
+pub fn py_set_default_time_format(format: &str, pattern: Option<String>) -> PyResult<()> {
+    let parsed = match format {
+        "rfc3339" => TimeFormat::Rfc3339,
+        "unix_seconds" => TimeFormat::UnixSeconds,
+        "unix_millis" => TimeFormat::UnixMillis,
+        "custom" => TimeFormat::Custom(pattern.ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err("custom time format requires a pattern")
+        })?),
+        other => return Err(pyo3::exceptions::PyValueError::new_err(format!("unknown time format '{other}'"))),
+    };
+    set_default_time_format(parsed);
+    Ok(())
+}
+
+/// What kind of content a [`Note`] holds, so a UI can pick a renderer instead of always
+/// treating it as plain text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteKind {
+    PlainText,
+    Markdown,
+    Checklist,
+    Code,
+}
+
+impl Default for NoteKind {
+    /// Rows written before this field existed had no kind information, so they
+    /// deserialize as plain text rather than guessing at their content.
+    fn default() -> Self {
+        NoteKind::PlainText
+    }
+}
+
+impl NoteKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NoteKind::PlainText => "plain_text",
+            NoteKind::Markdown => "markdown",
+            NoteKind::Checklist => "checklist",
+            NoteKind::Code => "code",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "plain_text" => Some(NoteKind::PlainText),
+            "markdown" => Some(NoteKind::Markdown),
+            "checklist" => Some(NoteKind::Checklist),
+            "code" => Some(NoteKind::Code),
+            _ => None,
+        }
+    }
+}
+
 /// Represents a note in the system.
 ///
 /// Notes contain text content and metadata about creation, modification,
@@ -26,10 +169,23 @@ pub struct Note {
     pub modified_at: Option<DateTime<Utc>>,
     /// When the note was deleted (None if not deleted, soft delete)
     pub deleted_at: Option<DateTime<Utc>>,
+    /// UUID7 of the note this one is nested under, for arranging notes into a tree
+    /// (None for a top-level note)
+    #[serde(default)]
+    pub parent_note_id: Option<Uuid>,
+    /// Fractional index (see [`crate::fractional_index`]) ordering this note among its
+    /// siblings under `parent_note_id` - lower sorts first. `None` until the note is
+    /// placed into an ordered list.
+    #[serde(default)]
+    pub position: Option<String>,
+    /// What kind of content `content` holds (see [`NoteKind`])
+    #[serde(default)]
+    pub kind: NoteKind,
 }
 
 impl Note {
-    /// Create a new note with the given content
+    /// Create a new plain-text note with the given content. Use [`Note::with_kind`] to
+    /// mark it as Markdown, a checklist, or code instead.
     pub fn new(content: String, device_id: Uuid) -> Self {
         Self {
             id: Uuid::now_v7(),
@@ -38,9 +194,18 @@ impl Note {
             device_id,
             modified_at: None,
             deleted_at: None,
+            parent_note_id: None,
+            position: None,
+            kind: NoteKind::default(),
         }
     }
 
+    /// Set this note's kind, for chaining onto [`Note::new`]
+    pub fn with_kind(mut self, kind: NoteKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
     /// Get the note ID as a hex string
     pub fn id_hex(&self) -> String {
         self.id.simple().to_string()
@@ -51,10 +216,41 @@ impl Note {
         self.device_id.simple().to_string()
     }
 
+    /// Get the parent note ID as a hex string (if present)
+    pub fn parent_note_id_hex(&self) -> Option<String> {
+        self.parent_note_id.map(|id| id.simple().to_string())
+    }
+
     /// Check if the note is deleted
     pub fn is_deleted(&self) -> bool {
         self.deleted_at.is_some()
     }
+
+    /// A short, kind-aware summary of `content` for UI listings and `__repr__`: Markdown
+    /// markup is stripped so the preview reads as prose instead of raw syntax; other
+    /// kinds are previewed as-is. Truncated to at most `max` characters, with a trailing
+    /// `...` only when something was actually cut off.
+    pub fn content_preview(&self, max: usize) -> String {
+        let cleaned = match self.kind {
+            NoteKind::Markdown => strip_markdown_markers(&self.content),
+            NoteKind::PlainText | NoteKind::Checklist | NoteKind::Code => self.content.clone(),
+        };
+        let cleaned = cleaned.trim();
+
+        if cleaned.chars().count() <= max {
+            cleaned.to_string()
+        } else {
+            let mut preview: String = cleaned.chars().take(max).collect();
+            preview.push_str("...");
+            preview
+        }
+    }
+}
+
+/// Strip the common Markdown markup characters (headers, emphasis, inline code) so a
+/// preview reads like plain prose rather than raw syntax.
+fn strip_markdown_markers(content: &str) -> String {
+    content.chars().filter(|c| !matches!(c, '#' | '*' | '_' | '`')).collect()
 }
 
 /// Represents a tag in the hierarchical tag system.
@@ -104,6 +300,64 @@ impl Tag {
     pub fn parent_id_hex(&self) -> Option<String> {
         self.parent_id.map(|id| id.simple().to_string())
     }
+
+    /// Walk `parent_id` up to the root, building a `Grandparent/Parent/Name` materialized
+    /// path. `resolver` looks up a tag by ID (e.g. backed by a [`crate::database::Database`]
+    /// or an in-memory map), so this doesn't need a live DB connection. Bails out after
+    /// `MAX_PATH_DEPTH` ancestors rather than looping forever if `resolver` returns a cycle.
+    /// A literal `/` inside a name is escaped as `\/` so it can't be confused with the path
+    /// separator (`validation::validate_tag_name` already forbids `/` in names, so this is
+    /// a defensive backstop rather than something reachable through validated input today).
+    pub fn full_path(&self, resolver: impl Fn(Uuid) -> Option<Tag>) -> String {
+        const MAX_PATH_DEPTH: usize = 64;
+        let mut segments = vec![escape_path_segment(&self.name)];
+        let mut current = self.parent_id;
+        let mut depth = 0;
+        while let Some(parent_id) = current {
+            if depth >= MAX_PATH_DEPTH {
+                break;
+            }
+            let Some(parent) = resolver(parent_id) else {
+                break;
+            };
+            segments.push(escape_path_segment(&parent.name));
+            current = parent.parent_id;
+            depth += 1;
+        }
+        segments.reverse();
+        segments.join("/")
+    }
+
+    /// Given the materialized path a tag had before a rename and its new name, compute the
+    /// updated full path for that tag and every descendant under `all_paths` whose path
+    /// starts under the old one, so the sync layer can rewrite path references in one pass.
+    pub fn rename(old_path: &str, new_name: &str, all_paths: &[(Uuid, String)]) -> Vec<(Uuid, String)> {
+        let new_escaped = escape_path_segment(new_name);
+        let new_path = match old_path.rfind('/') {
+            Some(idx) => format!("{}/{}", &old_path[..idx], new_escaped),
+            None => new_escaped,
+        };
+        let old_prefix = format!("{old_path}/");
+
+        all_paths
+            .iter()
+            .filter_map(|(id, path)| {
+                if path == old_path {
+                    Some((*id, new_path.clone()))
+                } else if let Some(rest) = path.strip_prefix(&old_prefix) {
+                    Some((*id, format!("{new_path}/{rest}")))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Escape a literal `/` within a single path segment so it can't be mistaken for the
+/// separator between tag names in a materialized path.
+fn escape_path_segment(name: &str) -> String {
+    name.replace('/', "\\/")
 }
 
 /// Represents the association between a note and a tag.
@@ -159,6 +413,134 @@ impl NoteTag {
     }
 }
 
+/// Semantic meaning of a [`NoteLink`] edge, so clients can build trees and ordered
+/// lists out of notes (`Child`/`Sibling`) while also tracking plain references and
+/// mentions, all over one uniform synced edge table. The wire format is a stable
+/// string rather than the derived enum tag: known kinds serialize as their lowercase
+/// name, and `Custom` serializes as `custom:<name>` so the escape hatch can't be
+/// confused with an unrecognized built-in kind (see [`RelationshipKind::parse`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RelationshipKind {
+    Reference,
+    Child,
+    Sibling,
+    Mention,
+    Duplicate,
+    Custom(String),
+}
+
+impl RelationshipKind {
+    /// Render the stable wire representation.
+    pub fn as_str(&self) -> std::borrow::Cow<'_, str> {
+        match self {
+            RelationshipKind::Reference => "reference".into(),
+            RelationshipKind::Child => "child".into(),
+            RelationshipKind::Sibling => "sibling".into(),
+            RelationshipKind::Mention => "mention".into(),
+            RelationshipKind::Duplicate => "duplicate".into(),
+            RelationshipKind::Custom(name) => format!("custom:{name}").into(),
+        }
+    }
+
+    /// Parse the stable wire representation, `None` for anything that's neither a
+    /// known kind nor a `custom:<name>` escape hatch.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "reference" => Some(RelationshipKind::Reference),
+            "child" => Some(RelationshipKind::Child),
+            "sibling" => Some(RelationshipKind::Sibling),
+            "mention" => Some(RelationshipKind::Mention),
+            "duplicate" => Some(RelationshipKind::Duplicate),
+            other => other.strip_prefix("custom:").map(|name| RelationshipKind::Custom(name.to_string())),
+        }
+    }
+}
+
+impl Serialize for RelationshipKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for RelationshipKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        RelationshipKind::parse(&raw).ok_or_else(|| serde::de::Error::custom(format!("invalid relationship kind: {raw}")))
+    }
+}
+
+/// Represents a wiki-style link from one note to another.
+///
+/// Links are a first-class, soft-deletable, device-stamped edge so they can merge
+/// through the same sync code paths as notes and tags. Given a target note, the set of
+/// links pointing at it are its backlinks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteLink {
+    /// Unique identifier for the link (UUID7 as bytes)
+    pub id: Uuid,
+    /// UUID7 of the note the link points from
+    pub source_note_id: Uuid,
+    /// UUID7 of the note the link points to
+    pub target_note_id: Uuid,
+    /// Semantic meaning of this edge
+    pub kind: RelationshipKind,
+    /// When the link was created
+    pub created_at: DateTime<Utc>,
+    /// UUID7 of the device that created this link
+    pub device_id: Uuid,
+    /// When the link was last modified (None if never modified)
+    pub modified_at: Option<DateTime<Utc>>,
+    /// When the link was deleted (None if not deleted, soft delete)
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl NoteLink {
+    /// Create a new link from `source_note_id` to `target_note_id`
+    pub fn new(source_note_id: Uuid, target_note_id: Uuid, device_id: Uuid, kind: RelationshipKind) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            source_note_id,
+            target_note_id,
+            kind,
+            created_at: Utc::now(),
+            device_id,
+            modified_at: None,
+            deleted_at: None,
+        }
+    }
+
+    /// Get the link ID as a hex string
+    pub fn id_hex(&self) -> String {
+        self.id.simple().to_string()
+    }
+
+    /// Get the source note ID as a hex string
+    pub fn source_note_id_hex(&self) -> String {
+        self.source_note_id.simple().to_string()
+    }
+
+    /// Get the target note ID as a hex string
+    pub fn target_note_id_hex(&self) -> String {
+        self.target_note_id.simple().to_string()
+    }
+
+    /// Get the device ID as a hex string
+    pub fn device_id_hex(&self) -> String {
+        self.device_id.simple().to_string()
+    }
+
+    /// Check if the link is deleted
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
+
 // ============================================================================
 // Python bindings
 // ============================================================================
@@ -173,16 +555,31 @@ pub struct PyNote {
 #[pymethods]
 impl PyNote {
     #[new]
-    #[pyo3(signature = (content, device_id=None))]
-    fn new(content: String, device_id: Option<String>) -> PyResult<Self> {
+    #[pyo3(signature = (content, device_id=None, kind=None, created_at=None, modified_at=None, deleted_at=None))]
+    fn new(
+        content: String,
+        device_id: Option<String>,
+        kind: Option<String>,
+        created_at: Option<Bound<'_, PyAny>>,
+        modified_at: Option<Bound<'_, PyAny>>,
+        deleted_at: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
         let device_uuid = match device_id {
             Some(id) => Uuid::parse_str(&id)
                 .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?,
             None => Uuid::now_v7(),
         };
-        Ok(Self {
-            inner: Note::new(content, device_uuid),
-        })
+        let kind = match kind {
+            Some(k) => NoteKind::parse(&k).ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("invalid note kind: {k}")))?,
+            None => NoteKind::default(),
+        };
+        let mut note = Note::new(content, device_uuid).with_kind(kind);
+        if let Some(ts) = created_at {
+            note.created_at = parse_timestamp(&ts)?;
+        }
+        note.modified_at = parse_optional_timestamp(modified_at.as_ref())?;
+        note.deleted_at = parse_optional_timestamp(deleted_at.as_ref())?;
+        Ok(Self { inner: note })
     }
 
     #[getter]
@@ -191,8 +588,8 @@ impl PyNote {
     }
 
     #[getter]
-    fn created_at(&self) -> String {
-        self.inner.created_at.to_rfc3339()
+    fn created_at(&self, py: Python<'_>) -> PyResult<PyObject> {
+        format_timestamp(py, self.inner.created_at, &default_time_format())
     }
 
     #[getter]
@@ -206,24 +603,63 @@ impl PyNote {
     }
 
     #[getter]
-    fn modified_at(&self) -> Option<String> {
-        self.inner.modified_at.map(|dt| dt.to_rfc3339())
+    fn modified_at(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.inner.modified_at.map(|dt| format_timestamp(py, dt, &default_time_format())).transpose()
+    }
+
+    #[getter]
+    fn deleted_at(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.inner.deleted_at.map(|dt| format_timestamp(py, dt, &default_time_format())).transpose()
     }
 
     #[getter]
-    fn deleted_at(&self) -> Option<String> {
-        self.inner.deleted_at.map(|dt| dt.to_rfc3339())
+    fn parent_note_id(&self) -> Option<String> {
+        self.inner.parent_note_id_hex()
+    }
+
+    #[setter]
+    fn set_parent_note_id(&mut self, parent_note_id: Option<String>) -> PyResult<()> {
+        self.inner.parent_note_id = match parent_note_id {
+            Some(id) => Some(Uuid::parse_str(&id).map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?),
+            None => None,
+        };
+        Ok(())
+    }
+
+    #[getter]
+    fn position(&self) -> Option<String> {
+        self.inner.position.clone()
+    }
+
+    #[setter]
+    fn set_position(&mut self, position: Option<String>) {
+        self.inner.position = position;
+    }
+
+    #[getter]
+    fn kind(&self) -> String {
+        self.inner.kind.as_str().to_string()
+    }
+
+    #[setter]
+    fn set_kind(&mut self, kind: String) -> PyResult<()> {
+        self.inner.kind = NoteKind::parse(&kind).ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("invalid note kind: {kind}")))?;
+        Ok(())
     }
 
     fn is_deleted(&self) -> bool {
         self.inner.is_deleted()
     }
 
+    fn content_preview(&self, max: usize) -> String {
+        self.inner.content_preview(max)
+    }
+
     fn __repr__(&self) -> String {
         format!(
-            "Note(id='{}', content='{:.30}...', created_at='{}')",
+            "Note(id='{}', content='{}', created_at='{}')",
             self.inner.id_hex(),
-            self.inner.content,
+            self.inner.content_preview(30),
             self.inner.created_at.to_rfc3339()
         )
     }
@@ -253,8 +689,14 @@ pub struct PyTag {
 #[pymethods]
 impl PyTag {
     #[new]
-    #[pyo3(signature = (name, device_id=None, parent_id=None))]
-    fn new(name: String, device_id: Option<String>, parent_id: Option<String>) -> PyResult<Self> {
+    #[pyo3(signature = (name, device_id=None, parent_id=None, created_at=None, modified_at=None))]
+    fn new(
+        name: String,
+        device_id: Option<String>,
+        parent_id: Option<String>,
+        created_at: Option<Bound<'_, PyAny>>,
+        modified_at: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
         let device_uuid = match device_id {
             Some(id) => Uuid::parse_str(&id)
                 .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?,
@@ -267,9 +709,12 @@ impl PyTag {
             ),
             None => None,
         };
-        Ok(Self {
-            inner: Tag::new(name, device_uuid, parent_uuid),
-        })
+        let mut tag = Tag::new(name, device_uuid, parent_uuid);
+        if let Some(ts) = created_at {
+            tag.created_at = Some(parse_timestamp(&ts)?);
+        }
+        tag.modified_at = parse_optional_timestamp(modified_at.as_ref())?;
+        Ok(Self { inner: tag })
     }
 
     #[getter]
@@ -293,13 +738,25 @@ impl PyTag {
     }
 
     #[getter]
-    fn created_at(&self) -> Option<String> {
-        self.inner.created_at.map(|dt| dt.to_rfc3339())
+    fn created_at(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.inner.created_at.map(|dt| format_timestamp(py, dt, &default_time_format())).transpose()
     }
 
     #[getter]
-    fn modified_at(&self) -> Option<String> {
-        self.inner.modified_at.map(|dt| dt.to_rfc3339())
+    fn modified_at(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.inner.modified_at.map(|dt| format_timestamp(py, dt, &default_time_format())).transpose()
+    }
+
+    /// Build this tag's materialized path (e.g. `Grandparent/Parent/Name`). `ancestor_names`
+    /// must be the names of this tag's ancestors ordered nearest-parent-first up to the root
+    /// (Python already has the connection needed to walk `parent_id`, so it resolves
+    /// ancestors itself rather than handing Rust a callback across the FFI boundary).
+    #[pyo3(signature = (ancestor_names=Vec::new()))]
+    fn full_path(&self, ancestor_names: Vec<String>) -> String {
+        let mut segments: Vec<String> =
+            ancestor_names.iter().rev().map(|name| escape_path_segment(name)).collect();
+        segments.push(escape_path_segment(&self.inner.name));
+        segments.join("/")
     }
 
     fn __repr__(&self) -> String {
@@ -336,8 +793,15 @@ pub struct PyNoteTag {
 #[pymethods]
 impl PyNoteTag {
     #[new]
-    #[pyo3(signature = (note_id, tag_id, device_id=None))]
-    fn new(note_id: String, tag_id: String, device_id: Option<String>) -> PyResult<Self> {
+    #[pyo3(signature = (note_id, tag_id, device_id=None, created_at=None, modified_at=None, deleted_at=None))]
+    fn new(
+        note_id: String,
+        tag_id: String,
+        device_id: Option<String>,
+        created_at: Option<Bound<'_, PyAny>>,
+        modified_at: Option<Bound<'_, PyAny>>,
+        deleted_at: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
         let note_uuid = Uuid::parse_str(&note_id)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
         let tag_uuid = Uuid::parse_str(&tag_id)
@@ -347,9 +811,13 @@ impl PyNoteTag {
                 .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?,
             None => Uuid::now_v7(),
         };
-        Ok(Self {
-            inner: NoteTag::new(note_uuid, tag_uuid, device_uuid),
-        })
+        let mut note_tag = NoteTag::new(note_uuid, tag_uuid, device_uuid);
+        if let Some(ts) = created_at {
+            note_tag.created_at = parse_timestamp(&ts)?;
+        }
+        note_tag.modified_at = parse_optional_timestamp(modified_at.as_ref())?;
+        note_tag.deleted_at = parse_optional_timestamp(deleted_at.as_ref())?;
+        Ok(Self { inner: note_tag })
     }
 
     #[getter]
@@ -363,8 +831,8 @@ impl PyNoteTag {
     }
 
     #[getter]
-    fn created_at(&self) -> String {
-        self.inner.created_at.to_rfc3339()
+    fn created_at(&self, py: Python<'_>) -> PyResult<PyObject> {
+        format_timestamp(py, self.inner.created_at, &default_time_format())
     }
 
     #[getter]
@@ -373,13 +841,13 @@ impl PyNoteTag {
     }
 
     #[getter]
-    fn modified_at(&self) -> Option<String> {
-        self.inner.modified_at.map(|dt| dt.to_rfc3339())
+    fn modified_at(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.inner.modified_at.map(|dt| format_timestamp(py, dt, &default_time_format())).transpose()
     }
 
     #[getter]
-    fn deleted_at(&self) -> Option<String> {
-        self.inner.deleted_at.map(|dt| dt.to_rfc3339())
+    fn deleted_at(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.inner.deleted_at.map(|dt| format_timestamp(py, dt, &default_time_format())).transpose()
     }
 
     fn is_deleted(&self) -> bool {
@@ -410,6 +878,125 @@ impl PyNoteTag {
     }
 }
 
+/// Python wrapper for NoteLink
+#[pyclass(name = "NoteLink")]
+#[derive(Clone)]
+pub struct PyNoteLink {
+    inner: NoteLink,
+}
+
+#[pymethods]
+impl PyNoteLink {
+    #[new]
+    #[pyo3(signature = (source_note_id, target_note_id, device_id=None, kind=None, created_at=None, modified_at=None, deleted_at=None))]
+    fn new(
+        source_note_id: String,
+        target_note_id: String,
+        device_id: Option<String>,
+        kind: Option<String>,
+        created_at: Option<Bound<'_, PyAny>>,
+        modified_at: Option<Bound<'_, PyAny>>,
+        deleted_at: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        let source_uuid = Uuid::parse_str(&source_note_id)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let target_uuid = Uuid::parse_str(&target_note_id)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let device_uuid = match device_id {
+            Some(id) => Uuid::parse_str(&id)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?,
+            None => Uuid::now_v7(),
+        };
+        let kind = match kind {
+            Some(k) => RelationshipKind::parse(&k)
+                .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("invalid relationship kind: {k}")))?,
+            None => RelationshipKind::Reference,
+        };
+        let mut link = NoteLink::new(source_uuid, target_uuid, device_uuid, kind);
+        if let Some(ts) = created_at {
+            link.created_at = parse_timestamp(&ts)?;
+        }
+        link.modified_at = parse_optional_timestamp(modified_at.as_ref())?;
+        link.deleted_at = parse_optional_timestamp(deleted_at.as_ref())?;
+        Ok(Self { inner: link })
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id_hex()
+    }
+
+    #[getter]
+    fn source_note_id(&self) -> String {
+        self.inner.source_note_id_hex()
+    }
+
+    #[getter]
+    fn target_note_id(&self) -> String {
+        self.inner.target_note_id_hex()
+    }
+
+    #[getter]
+    fn kind(&self) -> String {
+        self.inner.kind.as_str().into_owned()
+    }
+
+    #[setter]
+    fn set_kind(&mut self, kind: String) -> PyResult<()> {
+        self.inner.kind = RelationshipKind::parse(&kind)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("invalid relationship kind: {kind}")))?;
+        Ok(())
+    }
+
+    #[getter]
+    fn created_at(&self, py: Python<'_>) -> PyResult<PyObject> {
+        format_timestamp(py, self.inner.created_at, &default_time_format())
+    }
+
+    #[getter]
+    fn device_id(&self) -> String {
+        self.inner.device_id_hex()
+    }
+
+    #[getter]
+    fn modified_at(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.inner.modified_at.map(|dt| format_timestamp(py, dt, &default_time_format())).transpose()
+    }
+
+    #[getter]
+    fn deleted_at(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.inner.deleted_at.map(|dt| format_timestamp(py, dt, &default_time_format())).transpose()
+    }
+
+    fn is_deleted(&self) -> bool {
+        self.inner.is_deleted()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "NoteLink(source_note_id='{}', target_note_id='{}', kind='{}', created_at='{}')",
+            self.inner.source_note_id_hex(),
+            self.inner.target_note_id_hex(),
+            self.inner.kind.as_str(),
+            self.inner.created_at.to_rfc3339()
+        )
+    }
+}
+
+impl PyNoteLink {
+    pub fn from_note_link(note_link: NoteLink) -> Self {
+        Self { inner: note_link }
+    }
+
+    pub fn into_inner(self) -> NoteLink {
+        self.inner
+    }
+
+    pub fn inner(&self) -> &NoteLink {
+        &self.inner
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -425,6 +1012,96 @@ mod tests {
         assert!(note.modified_at.is_none());
         assert!(note.deleted_at.is_none());
         assert!(!note.is_deleted());
+        assert_eq!(note.kind, NoteKind::PlainText);
+    }
+
+    #[test]
+    fn test_parse_timestamp_rfc3339_round_trips_through_format() {
+        Python::with_gil(|py| {
+            let dt = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+            let formatted = format_timestamp(py, dt, &TimeFormat::Rfc3339).unwrap();
+            let bound = formatted.bind(py);
+            let parsed = parse_timestamp(bound).unwrap();
+            assert_eq!(parsed, dt);
+        });
+    }
+
+    #[test]
+    fn test_parse_timestamp_infers_unix_seconds_vs_millis() {
+        Python::with_gil(|py| {
+            let seconds = 1_700_000_000i64;
+            let bound = seconds.into_pyobject(py).unwrap();
+            let parsed = parse_timestamp(&bound.into_any()).unwrap();
+            assert_eq!(parsed.timestamp(), seconds);
+
+            let millis = 1_700_000_000_000i64;
+            let bound = millis.into_pyobject(py).unwrap();
+            let parsed = parse_timestamp(&bound.into_any()).unwrap();
+            assert_eq!(parsed.timestamp_millis(), millis);
+        });
+    }
+
+    #[test]
+    fn test_parse_timestamp_rejects_garbage() {
+        Python::with_gil(|py| {
+            let bound = "not a timestamp".into_pyobject(py).unwrap();
+            assert!(parse_timestamp(&bound.into_any()).is_err());
+        });
+    }
+
+    #[test]
+    fn test_format_timestamp_respects_requested_format() {
+        Python::with_gil(|py| {
+            let dt = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+            let seconds: i64 = format_timestamp(py, dt, &TimeFormat::UnixSeconds).unwrap().extract(py).unwrap();
+            assert_eq!(seconds, dt.timestamp());
+
+            let millis: i64 = format_timestamp(py, dt, &TimeFormat::UnixMillis).unwrap().extract(py).unwrap();
+            assert_eq!(millis, dt.timestamp_millis());
+
+            let custom: String = format_timestamp(py, dt, &TimeFormat::Custom("%Y-%m-%d".to_string()))
+                .unwrap()
+                .extract(py)
+                .unwrap();
+            assert_eq!(custom, "2024-01-02");
+        });
+    }
+
+    #[test]
+    fn test_note_kind_round_trip() {
+        for kind in [NoteKind::PlainText, NoteKind::Markdown, NoteKind::Checklist, NoteKind::Code] {
+            assert_eq!(NoteKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(NoteKind::parse("not-a-kind"), None);
+    }
+
+    #[test]
+    fn test_content_preview_truncates_with_ellipsis() {
+        let device_id = Uuid::now_v7();
+        let note = Note::new("a".repeat(40), device_id);
+
+        let preview = note.content_preview(10);
+        assert_eq!(preview, format!("{}...", "a".repeat(10)));
+    }
+
+    #[test]
+    fn test_content_preview_no_ellipsis_when_it_fits() {
+        let device_id = Uuid::now_v7();
+        let note = Note::new("short".to_string(), device_id);
+
+        assert_eq!(note.content_preview(30), "short");
+    }
+
+    #[test]
+    fn test_content_preview_strips_markdown_markers() {
+        let device_id = Uuid::now_v7();
+        let note = Note::new("# Heading\n**bold** _text_ `code`".to_string(), device_id).with_kind(NoteKind::Markdown);
+
+        let preview = note.content_preview(100);
+        assert!(!preview.contains('#'));
+        assert!(!preview.contains('*'));
+        assert!(!preview.contains('_'));
+        assert!(!preview.contains('`'));
     }
 
     #[test]
@@ -446,6 +1123,66 @@ mod tests {
         assert_eq!(child.parent_id, Some(parent.id));
     }
 
+    #[test]
+    fn test_tag_full_path_walks_ancestors() {
+        let device_id = Uuid::now_v7();
+        let root = Tag::new("Work".to_string(), device_id, None);
+        let mid = Tag::new("Projects".to_string(), device_id, Some(root.id));
+        let leaf = Tag::new("Voice".to_string(), device_id, Some(mid.id));
+
+        let tags = [root.clone(), mid.clone(), leaf.clone()];
+        let resolver = |id: Uuid| tags.iter().find(|t| t.id == id).cloned();
+
+        assert_eq!(root.full_path(resolver), "Work");
+        assert_eq!(mid.full_path(resolver), "Work/Projects");
+        assert_eq!(leaf.full_path(resolver), "Work/Projects/Voice");
+    }
+
+    #[test]
+    fn test_tag_full_path_escapes_slash_in_name() {
+        let device_id = Uuid::now_v7();
+        let tag = Tag::new("Q1/Q2".to_string(), device_id, None);
+        assert_eq!(tag.full_path(|_| None), "Q1\\/Q2");
+    }
+
+    #[test]
+    fn test_tag_full_path_bails_out_on_cycle() {
+        let device_id = Uuid::now_v7();
+        let a = Tag::new("A".to_string(), device_id, None);
+        let mut b = Tag::new("B".to_string(), device_id, Some(a.id));
+        let mut a = a;
+        // Force a cycle: a's parent is b, b's parent is a.
+        a.parent_id = Some(b.id);
+        b.parent_id = Some(a.id);
+        let tags = [a.clone(), b.clone()];
+        let resolver = |id: Uuid| tags.iter().find(|t| t.id == id).cloned();
+
+        // Should terminate instead of looping forever, bounded by MAX_PATH_DEPTH.
+        let path = a.full_path(resolver);
+        assert!(path.starts_with("A"));
+    }
+
+    #[test]
+    fn test_tag_rename_rewrites_descendant_paths() {
+        let all_paths = vec![
+            (Uuid::now_v7(), "Work/Projects".to_string()),
+            (Uuid::now_v7(), "Work/Projects/Voice".to_string()),
+            (Uuid::now_v7(), "Work/Projects/Voice/Sync".to_string()),
+            (Uuid::now_v7(), "Work/Personal".to_string()),
+        ];
+        let renamed_id = all_paths[0].0;
+        let child_id = all_paths[1].0;
+        let grandchild_id = all_paths[2].0;
+
+        let updated = Tag::rename("Work/Projects", "Initiatives", &all_paths);
+
+        let find = |id: Uuid| updated.iter().find(|(i, _)| *i == id).map(|(_, p)| p.clone());
+        assert_eq!(find(renamed_id), Some("Work/Initiatives".to_string()));
+        assert_eq!(find(child_id), Some("Work/Initiatives/Voice".to_string()));
+        assert_eq!(find(grandchild_id), Some("Work/Initiatives/Voice/Sync".to_string()));
+        assert_eq!(updated.len(), 3, "unrelated sibling path must not be touched");
+    }
+
     #[test]
     fn test_note_tag_creation() {
         let device_id = Uuid::now_v7();
@@ -458,6 +1195,36 @@ mod tests {
         assert!(!note_tag.is_deleted());
     }
 
+    #[test]
+    fn test_note_link_creation() {
+        let device_id = Uuid::now_v7();
+        let source = Note::new("Source".to_string(), device_id);
+        let target = Note::new("Target".to_string(), device_id);
+        let link = NoteLink::new(source.id, target.id, device_id, RelationshipKind::Reference);
+
+        assert_eq!(link.source_note_id, source.id);
+        assert_eq!(link.target_note_id, target.id);
+        assert_eq!(link.kind, RelationshipKind::Reference);
+        assert!(!link.is_deleted());
+    }
+
+    #[test]
+    fn test_relationship_kind_round_trip() {
+        for kind in [
+            RelationshipKind::Reference,
+            RelationshipKind::Child,
+            RelationshipKind::Sibling,
+            RelationshipKind::Mention,
+            RelationshipKind::Duplicate,
+            RelationshipKind::Custom("blocks".to_string()),
+        ] {
+            let wire = kind.as_str().into_owned();
+            assert_eq!(RelationshipKind::parse(&wire), Some(kind));
+        }
+
+        assert_eq!(RelationshipKind::parse("not-a-kind"), None);
+    }
+
     #[test]
     fn test_id_hex_format() {
         let device_id = Uuid::now_v7();
@@ -0,0 +1,418 @@
+//! Search query parsing and execution.
+//!
+//! A search input is free-form text that may mix plain words with `tag:name` (or
+//! `tag:"quoted name"`) terms. Parsing splits the two apart; execution resolves each
+//! tag term against the database and combines the result with [`Database::search_notes`].
+
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::database::{note_row_to_dict, Database, NoteRow, PyDatabase, TagRow};
+use crate::error::{ProgressCallback, VoiceError, VoiceResult};
+
+/// Maximum Levenshtein edit distance a tag name may be from an unmatched term and still
+/// be suggested.
+const SUGGESTION_MAX_DISTANCE: usize = 2;
+
+/// How many suggestions to return per unmatched term.
+const SUGGESTION_LIMIT: usize = 5;
+
+/// Suggest known tag names close to `term`, for use when a `tag:` term was ambiguous or
+/// matched nothing.
+///
+/// Two passes: first, a cheap case-insensitive prefix/substring match (distance treated
+/// as 0, so these always sort first); then, over the remaining tags, a bounded
+/// Levenshtein scan, skipping any whose length differs from `term` by more than
+/// [`SUGGESTION_MAX_DISTANCE`] before paying for the full edit-distance computation.
+/// Results are sorted by ascending distance, then alphabetically, and capped at
+/// [`SUGGESTION_LIMIT`].
+fn suggest_tags(term: &str, all_tags: &[TagRow]) -> Vec<String> {
+    let term_lower = term.to_lowercase();
+    let mut matched = std::collections::HashSet::new();
+    let mut scored: Vec<(usize, String)> = Vec::new();
+
+    for tag in all_tags {
+        let name_lower = tag.name.to_lowercase();
+        if name_lower.starts_with(&term_lower) || name_lower.contains(&term_lower) {
+            matched.insert(tag.name.clone());
+            scored.push((0, tag.name.clone()));
+        }
+    }
+
+    for tag in all_tags {
+        if matched.contains(&tag.name) {
+            continue;
+        }
+        let name_lower = tag.name.to_lowercase();
+        if name_lower.len().abs_diff(term_lower.len()) > SUGGESTION_MAX_DISTANCE {
+            continue;
+        }
+        let distance = levenshtein_distance(&term_lower, &name_lower);
+        if distance <= SUGGESTION_MAX_DISTANCE {
+            scored.push((distance, tag.name.clone()));
+        }
+    }
+
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+    scored.into_iter().take(SUGGESTION_LIMIT).map(|(_, name)| name).collect()
+}
+
+/// Classic dynamic-programming Levenshtein edit distance between two strings.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev_row: Vec<usize> = (0..=b.len()).collect();
+    let mut cur_row = vec![0; b.len() + 1];
+
+    for (i, &ca) in a.iter().enumerate() {
+        cur_row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            cur_row[j + 1] = (cur_row[j] + 1).min(prev_row[j + 1] + 1).min(prev_row[j] + cost);
+        }
+        std::mem::swap(&mut prev_row, &mut cur_row);
+    }
+
+    prev_row[b.len()]
+}
+
+/// A search input split into its `tag:` terms and remaining free text.
+#[derive(Debug, Clone)]
+pub struct ParsedSearch {
+    pub tag_terms: Vec<String>,
+    pub free_text: String,
+}
+
+impl ParsedSearch {
+    pub fn is_empty(&self) -> bool {
+        self.tag_terms.is_empty() && self.free_text.is_empty()
+    }
+}
+
+/// Split `search_input` into `tag:` terms and free text.
+///
+/// A term is recognized as `tag:name`, with the tag name optionally wrapped in double
+/// quotes to allow spaces (`tag:"project ideas"`). Everything else is whitespace-joined
+/// back into `free_text`.
+pub fn parse_search_input(search_input: &str) -> ParsedSearch {
+    let mut tag_terms = Vec::new();
+    let mut free_words = Vec::new();
+
+    let mut chars = search_input.chars().peekable();
+    let mut word = String::new();
+    let mut flush_word = |word: &mut String, free_words: &mut Vec<String>, tag_terms: &mut Vec<String>| {
+        if word.is_empty() {
+            return;
+        }
+        if let Some(name) = word.strip_prefix("tag:") {
+            let trimmed = name.trim_matches('"');
+            if !trimmed.is_empty() {
+                tag_terms.push(trimmed.to_string());
+            }
+        } else {
+            free_words.push(word.clone());
+        }
+        word.clear();
+    };
+
+    while let Some(c) = chars.next() {
+        if c == '"' && word.starts_with("tag:") {
+            // Consume a quoted tag name verbatim, including internal whitespace.
+            word.push(c);
+            for c in chars.by_ref() {
+                word.push(c);
+                if c == '"' {
+                    break;
+                }
+            }
+            continue;
+        }
+        if c.is_whitespace() {
+            flush_word(&mut word, &mut free_words, &mut tag_terms);
+        } else {
+            word.push(c);
+        }
+    }
+    flush_word(&mut word, &mut free_words, &mut tag_terms);
+
+    ParsedSearch {
+        tag_terms,
+        free_text: free_words.join(" "),
+    }
+}
+
+/// Outcome of executing a parsed search against the database.
+///
+/// `scores` is parallel to `notes` (same length, same order) and is only populated by
+/// [`execute_semantic_search`]; tag/free-text searches leave it empty.
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub notes: Vec<NoteRow>,
+    pub ambiguous_tags: Vec<String>,
+    pub not_found_tags: Vec<String>,
+    pub scores: Vec<f64>,
+    /// "Did you mean" suggestions for each term in `ambiguous_tags`/`not_found_tags`,
+    /// keyed by the original term. Empty when there was nothing to suggest for.
+    pub suggestions: HashMap<String, Vec<String>>,
+}
+
+/// Resolve `search_input`'s `tag:` terms against `db` and return the matching notes.
+///
+/// Each `tag:` term is resolved by name via [`Database::get_tags_by_name`]. A name that
+/// matches more than one tag is reported in `ambiguous_tags`; a name that matches none is
+/// reported in `not_found_tags`. If either list is non-empty, `notes` is left empty rather
+/// than guessing which tag the caller meant.
+pub fn execute_search(db: &Database, search_input: &str) -> VoiceResult<SearchResult> {
+    execute_search_with_progress(db, search_input, None)
+}
+
+/// Same as [`execute_search`], but periodically invokes `progress` with the phase name
+/// and how many tag terms have been resolved so far (total is the term count). Returns
+/// [`VoiceError::Cancelled`] as soon as `progress` returns `false`.
+pub fn execute_search_with_progress(
+    db: &Database,
+    search_input: &str,
+    mut progress: Option<ProgressCallback<'_>>,
+) -> VoiceResult<SearchResult> {
+    let parsed = parse_search_input(search_input);
+
+    let mut tag_ids = Vec::new();
+    let mut ambiguous_tags = Vec::new();
+    let mut not_found_tags = Vec::new();
+
+    let total_terms = parsed.tag_terms.len();
+    for (scanned, term) in parsed.tag_terms.iter().enumerate() {
+        if let Some(progress) = progress.as_deref_mut() {
+            if !progress("resolving_tags", scanned, total_terms) {
+                return Err(VoiceError::Cancelled("search cancelled while resolving tags".to_string()));
+            }
+        }
+        let matches = db.get_tags_by_name(term)?;
+        match matches.len() {
+            0 => not_found_tags.push(term.clone()),
+            1 => tag_ids.push(matches[0].id.clone()),
+            _ => ambiguous_tags.push(term.clone()),
+        }
+    }
+
+    if !ambiguous_tags.is_empty() || !not_found_tags.is_empty() {
+        let all_tags = db.get_all_tags()?;
+        let mut suggestions = HashMap::new();
+        for term in ambiguous_tags.iter().chain(not_found_tags.iter()) {
+            suggestions.insert(term.clone(), suggest_tags(term, &all_tags));
+        }
+        return Ok(SearchResult {
+            notes: Vec::new(),
+            ambiguous_tags,
+            not_found_tags,
+            scores: Vec::new(),
+            suggestions,
+        });
+    }
+
+    if let Some(progress) = progress.as_deref_mut() {
+        if !progress("searching", total_terms, total_terms) {
+            return Err(VoiceError::Cancelled("search cancelled before scanning notes".to_string()));
+        }
+    }
+
+    let free_text = if parsed.free_text.is_empty() { None } else { Some(parsed.free_text.as_str()) };
+    let tag_id_groups = if tag_ids.is_empty() { None } else { Some(vec![tag_ids]) };
+    let notes = db.search_notes(free_text, tag_id_groups.as_ref())?;
+
+    Ok(SearchResult {
+        notes,
+        ambiguous_tags,
+        not_found_tags,
+        scores: Vec::new(),
+        suggestions: HashMap::new(),
+    })
+}
+
+/// Run a semantic (embedding) search: rank notes by cosine similarity of their stored
+/// embedding against `query_embedding`, returning at most `top_k` results with a score
+/// of at least `min_score`. Unlike tag/free-text search there is no ambiguity to report,
+/// so `ambiguous_tags`/`not_found_tags` are always empty; `scores` is parallel to `notes`.
+pub fn execute_semantic_search(
+    db: &Database,
+    query_embedding: &[f32],
+    top_k: usize,
+    min_score: f64,
+) -> VoiceResult<SearchResult> {
+    let ranked = db.semantic_search_notes(query_embedding, top_k, min_score)?;
+    let mut notes = Vec::with_capacity(ranked.len());
+    let mut scores = Vec::with_capacity(ranked.len());
+    for (note, score) in ranked {
+        notes.push(note);
+        scores.push(score);
+    }
+    Ok(SearchResult {
+        notes,
+        ambiguous_tags: Vec::new(),
+        not_found_tags: Vec::new(),
+        scores,
+        suggestions: HashMap::new(),
+    })
+}
+
+// ============================================================================
+// Python bindings
+// ============================================================================
+
+#[pyclass(name = "ParsedSearch")]
+pub struct PyParsedSearch {
+    inner: ParsedSearch,
+}
+
+#[pymethods]
+impl PyParsedSearch {
+    #[getter]
+    fn tag_terms(&self) -> Vec<String> {
+        self.inner.tag_terms.clone()
+    }
+
+    #[getter]
+    fn free_text(&self) -> &str {
+        &self.inner.free_text
+    }
+
+    #[getter]
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_search_input")]
+pub fn py_parse_search_input(search_input: &str) -> PyParsedSearch {
+    PyParsedSearch {
+        inner: parse_search_input(search_input),
+    }
+}
+
+#[pyclass(name = "SearchResult")]
+pub struct PySearchResult {
+    notes: Vec<NoteRow>,
+    ambiguous_tags: Vec<String>,
+    not_found_tags: Vec<String>,
+    scores: Vec<f64>,
+    suggestions: HashMap<String, Vec<String>>,
+}
+
+#[pymethods]
+impl PySearchResult {
+    #[getter]
+    fn notes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let list = PyList::empty(py);
+        for (i, note) in self.notes.iter().enumerate() {
+            let dict = note_row_to_dict(py, note)?;
+            if let Some(score) = self.scores.get(i) {
+                dict.set_item("score", score)?;
+            }
+            list.append(dict)?;
+        }
+        Ok(list)
+    }
+
+    #[getter]
+    fn ambiguous_tags(&self) -> Vec<String> {
+        self.ambiguous_tags.clone()
+    }
+
+    #[getter]
+    fn not_found_tags(&self) -> Vec<String> {
+        self.not_found_tags.clone()
+    }
+
+    #[getter]
+    fn scores(&self) -> Vec<f64> {
+        self.scores.clone()
+    }
+
+    /// "Did you mean" suggestions for each unmatched/ambiguous term, as
+    /// `{original_term: [candidate_tag, ...]}`.
+    #[getter]
+    fn suggestions<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for (term, candidates) in &self.suggestions {
+            dict.set_item(term, candidates)?;
+        }
+        Ok(dict)
+    }
+}
+
+/// Execute a search, optionally reporting progress through `progress_callback` as
+/// `(phase, scanned, total)`. The callback is invoked with the GIL held; returning
+/// `False` from it cancels the search with a [`crate::error::PyCancelledError`]. The
+/// heavy lifting runs with the GIL released, so the callback must not touch `db` or any
+/// other `PyDatabase` handle to the same connection, since sqlite access is not
+/// re-entrant across threads.
+#[pyfunction]
+#[pyo3(name = "execute_search")]
+#[pyo3(signature = (db, search_input, progress_callback=None))]
+pub fn py_execute_search(
+    py: Python<'_>,
+    db: &PyDatabase,
+    search_input: &str,
+    progress_callback: Option<Py<PyAny>>,
+) -> PyResult<PySearchResult> {
+    let db_ref = db.inner_ref()?;
+    let result = py.allow_threads(|| -> VoiceResult<SearchResult> {
+        match progress_callback.as_ref() {
+            Some(callback) => {
+                let mut cb = |phase: &str, scanned: usize, total: usize| -> bool {
+                    Python::with_gil(|py| {
+                        callback
+                            .call1(py, (phase, scanned, total))
+                            .map(|ret| ret.is_truthy(py).unwrap_or(true))
+                            .unwrap_or(true)
+                    })
+                };
+                execute_search_with_progress(db_ref, search_input, Some(&mut cb))
+            }
+            None => execute_search(db_ref, search_input),
+        }
+    })?;
+    Ok(PySearchResult {
+        notes: result.notes,
+        ambiguous_tags: result.ambiguous_tags,
+        not_found_tags: result.not_found_tags,
+        scores: result.scores,
+        suggestions: result.suggestions,
+    })
+}
+
+/// Store (or replace) the embedding for `note_id`. The model that produced the embedding
+/// is an internal detail; callers just supply the vector.
+#[pyfunction]
+#[pyo3(name = "upsert_note_embedding")]
+pub fn py_upsert_note_embedding(db: &PyDatabase, note_id: &str, embedding: Vec<f32>) -> PyResult<()> {
+    db.inner_ref()?.upsert_note_embedding(note_id, &embedding)?;
+    Ok(())
+}
+
+/// Run a semantic search for notes whose stored embedding is closest to `query_embedding`
+/// by cosine similarity, returning the shape as [`py_execute_search`] with a `score` key
+/// on each note dict so callers can reuse the same rendering code.
+#[pyfunction]
+#[pyo3(name = "execute_semantic_search")]
+#[pyo3(signature = (db, query_embedding, top_k=10, min_score=0.0))]
+pub fn py_execute_semantic_search(
+    py: Python<'_>,
+    db: &PyDatabase,
+    query_embedding: Vec<f32>,
+    top_k: usize,
+    min_score: f64,
+) -> PyResult<PySearchResult> {
+    let db_ref = db.inner_ref()?;
+    let result = py.allow_threads(|| execute_semantic_search(db_ref, &query_embedding, top_k, min_score))?;
+    Ok(PySearchResult {
+        notes: result.notes,
+        ambiguous_tags: result.ambiguous_tags,
+        not_found_tags: result.not_found_tags,
+        scores: result.scores,
+        suggestions: result.suggestions,
+    })
+}
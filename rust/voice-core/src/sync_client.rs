@@ -0,0 +1,649 @@
+//! Sync client implementation: the counterpart to [`crate::sync_server`].
+//!
+//! A `SyncClient` talks to a single peer's `/sync/*` endpoints over HTTPS, pulling
+//! its changes since the last sync and pushing back whatever changed locally. Every
+//! connection terminates TLS through [`crate::tls::build_peer_tls_config`], which verifies
+//! the peer's certificate via [`crate::tls::TOFUVerifier`] rather than the system's CA
+//! roots, and every handshake/apply request is signed with this device's identity key (see
+//! [`crate::config::Config::sign`]) so the peer can tell the envelope actually came from us.
+
+use std::collections::{HashMap, HashSet};
+
+use base64::Engine;
+use chrono::Utc;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Config, PeerConfig};
+use crate::database::Database;
+use crate::error::{ProgressCallback, VoiceError, VoiceResult};
+use crate::merkle;
+
+/// A single entity mutation exchanged between peers during sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncChange {
+    pub entity_type: String,
+    pub entity_id: String,
+    /// `"create"`, `"update"`, or `"delete"` carry the entity's full current fields in
+    /// `data`. `"patch"` (notes only) carries an RFC 7386 JSON Merge Patch instead -
+    /// see `crate::sync_server::effective_note_data` - so disjoint concurrent edits to
+    /// the same note don't have to clobber each other's fields on apply. We don't
+    /// generate `"patch"` changes ourselves yet (that needs a prior-version baseline to
+    /// diff against, which nothing here keeps), but apply supports receiving one.
+    pub operation: String,
+    pub data: serde_json::Value,
+    pub timestamp: String,
+    pub device_id: String,
+    pub device_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HandshakeResponse {
+    device_id: String,
+    device_name: String,
+    last_sync_timestamp: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChangesResponse {
+    changes: Vec<SyncChange>,
+    to_timestamp: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MerkleNodeResponse {
+    hash: String,
+    children: Vec<MerkleChildHashResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MerkleChildHashResponse {
+    byte: String,
+    hash: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ApplyRequestBody<'a> {
+    device_id: &'a str,
+    device_name: &'a str,
+    changes: &'a [SyncChange],
+    /// Hex-encoded Ed25519 signature over [`apply_signing_message`].
+    signature: &'a str,
+}
+
+/// Canonical bytes signed over an `/sync/apply` request: the sender's device id plus the
+/// changes themselves, built identically by the sender (to produce the signature) and the
+/// receiver (to verify it), so a signature can't be replayed onto a different device id or
+/// a different set of changes.
+pub(crate) fn apply_signing_message(device_id: &str, changes: &[SyncChange]) -> VoiceResult<Vec<u8>> {
+    let mut message = device_id.as_bytes().to_vec();
+    message.extend_from_slice(&serde_json::to_vec(changes)?);
+    Ok(message)
+}
+
+/// Canonical bytes signed over an `/sync/handshake` request.
+pub(crate) fn handshake_signing_message(device_id: &str, device_name: &str, protocol_version: &str) -> Vec<u8> {
+    format!("{device_id}:{device_name}:{protocol_version}").into_bytes()
+}
+
+/// Canonical bytes signed over a `/sync/changes` request, built identically by
+/// [`sync_with_peer_with_progress`]/[`reconcile_with_peer`] (to produce the signature) and
+/// [`crate::sync_server::get_changes`] (to verify it). `limit` is the value actually applied
+/// server-side (after its own default/cap), not whatever the caller omitted, so both sides
+/// agree on what was signed.
+pub(crate) fn changes_signing_message(device_id: &str, since: Option<&str>, limit: i64, prefix: Option<&str>) -> Vec<u8> {
+    format!("{device_id}:{}:{limit}:{}", since.unwrap_or(""), prefix.unwrap_or("")).into_bytes()
+}
+
+/// Canonical bytes signed over a `/sync/merkle` request.
+pub(crate) fn merkle_signing_message(device_id: &str, prefix_hex: &str) -> Vec<u8> {
+    format!("{device_id}:{prefix_hex}").into_bytes()
+}
+
+/// Canonical bytes signed over a `/sync/chunks` request: `device_id` followed by the
+/// JSON-serialized hash list, built identically by [`fetch_missing_chunks`] (to produce the
+/// signature) and [`crate::sync_server::get_chunks`] (to verify it).
+pub(crate) fn chunks_signing_message(device_id: &str, hashes: &[String]) -> VoiceResult<Vec<u8>> {
+    let mut message = device_id.as_bytes().to_vec();
+    message.extend(serde_json::to_vec(hashes)?);
+    Ok(message)
+}
+
+/// Hex-encode `config`'s signature over `message`, for embedding in a signed query string or
+/// request body alongside `config.device_id_hex()`.
+fn sign_hex(config: &Config, message: &[u8]) -> VoiceResult<String> {
+    Ok(crate::config::hex_encode(&config.sign(message)?))
+}
+
+/// Build a `ureq` agent that terminates TLS to `peer_id` via [`crate::tls::build_peer_tls_config`]
+/// instead of trusting the system's CA roots, which self-signed Voice certificates never chain to.
+fn peer_agent(config: &Config, peer_id: &str) -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .tls_config(crate::tls::build_peer_tls_config(config, peer_id, None))
+        .build()
+}
+
+/// The `limit` a pull request always signs and sends explicitly (rather than omitting it
+/// and relying on the server's own default), so the signed message matches exactly what
+/// [`crate::sync_server::get_changes`] resolves it to.
+const CHANGES_PAGE_LIMIT: i64 = 1000;
+
+/// Build a signed `/sync/changes` URL, authenticated the same way as `/sync/handshake`/
+/// `/sync/apply` - see [`changes_signing_message`].
+fn changes_request_url(peer_url: &str, config: &Config, since: Option<&str>, prefix: Option<&str>) -> VoiceResult<String> {
+    let message = changes_signing_message(config.device_id_hex(), since, CHANGES_PAGE_LIMIT, prefix);
+    let signature = sign_hex(config, &message)?;
+    let mut url = format!(
+        "{peer_url}/sync/changes?device_id={}&limit={CHANGES_PAGE_LIMIT}&signature={signature}",
+        config.device_id_hex()
+    );
+    if let Some(since) = since {
+        url.push_str(&format!("&since={since}"));
+    }
+    if let Some(prefix) = prefix {
+        url.push_str(&format!("&prefix={prefix}"));
+    }
+    Ok(url)
+}
+
+#[derive(Debug, Deserialize)]
+struct ApplyResponseBody {
+    applied: i64,
+    conflicts: i64,
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChunksRequestBody<'a> {
+    device_id: &'a str,
+    hashes: &'a [String],
+    /// Hex-encoded Ed25519 signature over [`chunks_signing_message`].
+    signature: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunksResponseBody {
+    /// Base64-encoded bytes for every requested hash the peer had, keyed by the same
+    /// hex-encoded hash we sent.
+    chunks: HashMap<String, String>,
+}
+
+/// Fetch, from `peer`, the bytes behind every chunk hash `changes` references that
+/// [`Database::missing_chunks`] says we don't already have, and store them via
+/// [`Database::ingest_chunk`] so [`crate::sync_server::apply_note_change`] can reassemble
+/// their content. A no-op if `changes` references no chunks we're missing - the common case
+/// once most of a peer's content has already been pulled once.
+fn fetch_missing_chunks(agent: &ureq::Agent, config: &Config, peer: &PeerConfig, db: &Database, changes: &[SyncChange]) -> VoiceResult<()> {
+    let mut hashes: Vec<String> = changes
+        .iter()
+        .filter_map(|change| change.data.get("chunk_hashes"))
+        .filter_map(|value| serde_json::from_value::<Vec<String>>(value.clone()).ok())
+        .flatten()
+        .collect();
+    hashes.sort_unstable();
+    hashes.dedup();
+
+    let missing = db.missing_chunks(&hashes)?;
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let message = chunks_signing_message(config.device_id_hex(), &missing)?;
+    let signature = sign_hex(config, &message)?;
+    let body = ChunksRequestBody {
+        device_id: config.device_id_hex(),
+        hashes: &missing,
+        signature: &signature,
+    };
+    let url = format!("{}/sync/chunks", peer.peer_url);
+    let resp: ChunksResponseBody = agent
+        .post(&url)
+        .send_json(&body)
+        .map_err(|e| VoiceError::sync(format!("failed to fetch chunks from {}: {}", peer.peer_url, e)))?
+        .into_json()
+        .map_err(|e| VoiceError::sync(format!("invalid chunks response from {}: {}", peer.peer_url, e)))?;
+
+    for (hash_hex, data) in resp.chunks {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&data)
+            .map_err(|e| VoiceError::sync(format!("invalid chunk data from {}: {}", peer.peer_url, e)))?;
+        db.ingest_chunk(&hash_hex, &bytes)?;
+    }
+    Ok(())
+}
+
+/// Outcome of syncing with a single peer.
+#[derive(Debug, Clone)]
+pub struct SyncResult {
+    pub pulled: i64,
+    pub pushed: i64,
+    pub conflicts: i64,
+    pub errors: Vec<String>,
+}
+
+/// Sync with `peer`, pulling its changes since the last recorded sync and pushing
+/// everything that changed locally since then. Uses a plain synchronous HTTP client
+/// to match the rest of the TLS/ACME stack rather than pulling in an async runtime here.
+pub fn sync_with_peer(db: &Database, config: &Config, peer: &PeerConfig) -> VoiceResult<SyncResult> {
+    sync_with_peer_with_progress(db, config, peer, None)
+}
+
+/// Same as [`sync_with_peer`], but periodically invokes `progress` with the phase name,
+/// changes applied so far, and the total pulled from the peer. Returns
+/// [`VoiceError::Cancelled`] as soon as `progress` returns `false`, leaving the sync
+/// watermark untouched so the next attempt resumes from the same point.
+pub fn sync_with_progress(db: &Database, config: &Config, peer: &PeerConfig, progress: ProgressCallback<'_>) -> VoiceResult<SyncResult> {
+    sync_with_peer_with_progress(db, config, peer, Some(progress))
+}
+
+fn sync_with_peer_with_progress(
+    db: &Database,
+    config: &Config,
+    peer: &PeerConfig,
+    mut progress: Option<ProgressCallback<'_>>,
+) -> VoiceResult<SyncResult> {
+    let agent = peer_agent(config, &peer.peer_id);
+    let last_sync = db.get_peer_last_sync(&peer.peer_id)?;
+
+    // Pull remote changes
+    let changes_url = changes_request_url(&peer.peer_url, config, last_sync.as_deref(), None)?;
+    let changes_resp: ChangesResponse = agent
+        .get(&changes_url)
+        .call()
+        .map_err(|e| VoiceError::sync(format!("failed to fetch changes from {}: {}", peer.peer_url, e)))?
+        .into_json()
+        .map_err(|e| VoiceError::sync(format!("invalid changes response from {}: {}", peer.peer_url, e)))?;
+    fetch_missing_chunks(&agent, config, peer, db, &changes_resp.changes)?;
+
+    let mut errors = Vec::new();
+    let mut pulled = 0i64;
+    let mut conflicts = 0i64;
+    let total_changes = changes_resp.changes.len();
+    for (scanned, change) in changes_resp.changes.iter().enumerate() {
+        if let Some(progress) = progress.as_deref_mut() {
+            if !progress("pulling", scanned, total_changes) {
+                return Err(VoiceError::Cancelled(format!("sync with {} cancelled while pulling changes", peer.peer_url)));
+            }
+        }
+        match crate::sync_server::apply_incoming_change(db, change, last_sync.as_deref()) {
+            Ok(crate::sync_server::ApplyOutcome::Applied) => pulled += 1,
+            Ok(crate::sync_server::ApplyOutcome::Conflict) => conflicts += 1,
+            Ok(crate::sync_server::ApplyOutcome::Skipped) => {}
+            Err(e) => errors.push(format!("{} {}: {}", change.entity_type, change.entity_id, e)),
+        }
+    }
+
+    if let Some(progress) = progress.as_deref_mut() {
+        if !progress("pushing", 0, 0) {
+            return Err(VoiceError::Cancelled(format!("sync with {} cancelled before pushing changes", peer.peer_url)));
+        }
+    }
+
+    // Push local changes
+    let (local_changes, _latest) = db.get_changes_since(last_sync.as_deref(), 10_000, None)?;
+    let pushed = local_changes.len() as i64;
+    if !local_changes.is_empty() {
+        let message = apply_signing_message(config.device_id_hex(), &local_changes)?;
+        let signature = crate::config::hex_encode(&config.sign(&message)?);
+        let body = ApplyRequestBody {
+            device_id: config.device_id_hex(),
+            device_name: config.device_name(),
+            changes: &local_changes,
+            signature: &signature,
+        };
+        let apply_url = format!("{}/sync/apply", peer.peer_url);
+        let apply_resp: ApplyResponseBody = agent
+            .post(&apply_url)
+            .send_json(&body)
+            .map_err(|e| VoiceError::sync(format!("failed to push changes to {}: {}", peer.peer_url, e)))?
+            .into_json()
+            .map_err(|e| VoiceError::sync(format!("invalid apply response from {}: {}", peer.peer_url, e)))?;
+        conflicts += apply_resp.conflicts;
+        errors.extend(apply_resp.errors);
+        let _ = apply_resp.applied;
+    }
+
+    let new_cursor = changes_resp.to_timestamp.unwrap_or_else(|| Utc::now().to_rfc3339());
+    let _ = new_cursor;
+    db.update_peer_sync_time(&peer.peer_id, Some(&peer.peer_name))?;
+
+    Ok(SyncResult {
+        pulled,
+        pushed,
+        conflicts,
+        errors,
+    })
+}
+
+/// Walk the Merkle anti-entropy tree (see [`crate::merkle`]), comparing our hash at the
+/// root and each branch against `peer`'s and descending only where they differ, and
+/// return the leaf prefixes that actually diverge. This costs O(log n) round trips
+/// rather than the O(dataset) scan that [`sync_with_peer`]'s `since`-cursor needs, which
+/// matters once a peer has been offline long enough (or had its clock skew) that the
+/// cursor can no longer be trusted to pick out exactly what changed.
+pub fn merkle_diverging_prefixes(db: &Database, config: &Config, peer: &PeerConfig) -> VoiceResult<Vec<Vec<u8>>> {
+    let agent = peer_agent(config, &peer.peer_id);
+    let mut to_visit = vec![Vec::new()];
+    let mut diverging = Vec::new();
+
+    while let Some(prefix) = to_visit.pop() {
+        let (local_hash, local_children) = db.merkle_node(&prefix)?;
+        let remote = fetch_merkle_node(&agent, config, peer, &prefix)?;
+        let remote_hash = merkle::from_hex(&remote.hash)
+            .ok_or_else(|| VoiceError::sync(format!("invalid merkle hash from {}", peer.peer_url)))?;
+        if local_hash == remote_hash {
+            continue;
+        }
+        if prefix.len() >= merkle::LEAF_PREFIX_LEN {
+            diverging.push(prefix);
+            continue;
+        }
+
+        let mut remote_children: HashMap<u8, Vec<u8>> = HashMap::new();
+        for child in &remote.children {
+            let byte = u8::from_str_radix(&child.byte, 16)
+                .map_err(|_| VoiceError::sync(format!("invalid merkle child byte from {}", peer.peer_url)))?;
+            let hash = merkle::from_hex(&child.hash)
+                .ok_or_else(|| VoiceError::sync(format!("invalid merkle hash from {}", peer.peer_url)))?;
+            remote_children.insert(byte, hash);
+        }
+
+        let mut seen = HashSet::new();
+        for (byte, local_child_hash) in &local_children {
+            seen.insert(*byte);
+            if remote_children.get(byte) != Some(local_child_hash) {
+                let mut child_prefix = prefix.clone();
+                child_prefix.push(*byte);
+                to_visit.push(child_prefix);
+            }
+        }
+        for byte in remote_children.keys() {
+            if !seen.contains(byte) {
+                let mut child_prefix = prefix.clone();
+                child_prefix.push(*byte);
+                to_visit.push(child_prefix);
+            }
+        }
+    }
+
+    Ok(diverging)
+}
+
+fn fetch_merkle_node(agent: &ureq::Agent, config: &Config, peer: &PeerConfig, prefix: &[u8]) -> VoiceResult<MerkleNodeResponse> {
+    let prefix_hex = merkle::to_hex(prefix);
+    let message = merkle_signing_message(config.device_id_hex(), &prefix_hex);
+    let signature = sign_hex(config, &message)?;
+    let url = format!(
+        "{}/sync/merkle?prefix={prefix_hex}&device_id={}&signature={signature}",
+        peer.peer_url,
+        config.device_id_hex()
+    );
+    agent
+        .get(&url)
+        .call()
+        .map_err(|e| VoiceError::sync(format!("failed to fetch merkle node from {}: {}", peer.peer_url, e)))?
+        .into_json()
+        .map_err(|e| VoiceError::sync(format!("invalid merkle response from {}: {}", peer.peer_url, e)))
+}
+
+/// Catch up with `peer` by diffing Merkle trees instead of trusting the `since` cursor:
+/// find the leaf buckets that actually diverge via [`merkle_diverging_prefixes`], then
+/// pull and push only those buckets through the same prefix-scoped `/sync/changes` and
+/// `/sync/apply` endpoints [`sync_with_peer`] uses. Suited to a peer that has been
+/// offline long enough, or whose clock has skewed far enough, that a linear cursor scan
+/// would be wasteful or couldn't be trusted to find every change.
+pub fn reconcile_with_peer(db: &Database, config: &Config, peer: &PeerConfig) -> VoiceResult<SyncResult> {
+    let agent = peer_agent(config, &peer.peer_id);
+    let diverging = merkle_diverging_prefixes(db, config, peer)?;
+
+    let mut errors = Vec::new();
+    let mut pulled = 0i64;
+    let mut conflicts = 0i64;
+    let mut local_changes = Vec::new();
+
+    for prefix in &diverging {
+        let changes_url = changes_request_url(&peer.peer_url, config, None, Some(&merkle::to_hex(prefix)))?;
+        let changes_resp: ChangesResponse = agent
+            .get(&changes_url)
+            .call()
+            .map_err(|e| VoiceError::sync(format!("failed to fetch changes from {}: {}", peer.peer_url, e)))?
+            .into_json()
+            .map_err(|e| VoiceError::sync(format!("invalid changes response from {}: {}", peer.peer_url, e)))?;
+        fetch_missing_chunks(&agent, config, peer, db, &changes_resp.changes)?;
+        for change in &changes_resp.changes {
+            match crate::sync_server::apply_incoming_change(db, change, None) {
+                Ok(crate::sync_server::ApplyOutcome::Applied) => pulled += 1,
+                Ok(crate::sync_server::ApplyOutcome::Conflict) => conflicts += 1,
+                Ok(crate::sync_server::ApplyOutcome::Skipped) => {}
+                Err(e) => errors.push(format!("{} {}: {}", change.entity_type, change.entity_id, e)),
+            }
+        }
+
+        let (prefix_changes, _latest) = db.get_changes_since(None, 10_000, Some(prefix))?;
+        local_changes.extend(prefix_changes);
+    }
+
+    let pushed = local_changes.len() as i64;
+    if !local_changes.is_empty() {
+        let message = apply_signing_message(config.device_id_hex(), &local_changes)?;
+        let signature = crate::config::hex_encode(&config.sign(&message)?);
+        let body = ApplyRequestBody {
+            device_id: config.device_id_hex(),
+            device_name: config.device_name(),
+            changes: &local_changes,
+            signature: &signature,
+        };
+        let apply_url = format!("{}/sync/apply", peer.peer_url);
+        let apply_resp: ApplyResponseBody = agent
+            .post(&apply_url)
+            .send_json(&body)
+            .map_err(|e| VoiceError::sync(format!("failed to push changes to {}: {}", peer.peer_url, e)))?
+            .into_json()
+            .map_err(|e| VoiceError::sync(format!("invalid apply response from {}: {}", peer.peer_url, e)))?;
+        conflicts += apply_resp.conflicts;
+        errors.extend(apply_resp.errors);
+        let _ = apply_resp.applied;
+    }
+
+    db.update_peer_sync_time(&peer.peer_id, Some(&peer.peer_name))?;
+
+    Ok(SyncResult {
+        pulled,
+        pushed,
+        conflicts,
+        errors,
+    })
+}
+
+/// Exchange handshakes with `peer` over `agent`, signing the request with `config`'s identity
+/// key, and report its device id, device name, and last known sync timestamp with us.
+fn handshake_with_peer(agent: &ureq::Agent, config: &Config, peer: &PeerConfig) -> VoiceResult<(String, String, Option<String>)> {
+    let protocol_version = "1.0";
+    let message = handshake_signing_message(config.device_id_hex(), config.device_name(), protocol_version);
+    let signature = crate::config::hex_encode(&config.sign(&message)?);
+    let url = format!("{}/sync/handshake", peer.peer_url);
+    let resp: HandshakeResponse = agent
+        .post(&url)
+        .send_json(serde_json::json!({
+            "device_id": config.device_id_hex(),
+            "device_name": config.device_name(),
+            "protocol_version": protocol_version,
+            "signature": signature,
+        }))
+        .map_err(|e| VoiceError::sync(format!("handshake with {} failed: {}", peer.peer_url, e)))?
+        .into_json()
+        .map_err(|e| VoiceError::sync(format!("invalid handshake response from {}: {}", peer.peer_url, e)))?;
+    Ok((resp.device_id, resp.device_name, resp.last_sync_timestamp))
+}
+
+/// Handshake with a peer reachable only by URL (no pre-registered `peer_id`) and sync with
+/// it in one call: since there's no `peer_id` yet to pin a fingerprint under, the handshake
+/// itself runs over [`crate::tls::build_discovery_tls_config`]'s unauthenticated (but still
+/// encrypted) transport, recording whatever certificate the peer presented. Once the handshake
+/// reveals the peer's real device id, that fingerprint is pinned via
+/// [`crate::config::Config::add_peer`] *before* we hand off to [`sync_with_peer`] for the
+/// pull/push - so only the very first contact with a given peer is unauthenticated, and every
+/// sync after this one goes through [`crate::tls::build_peer_tls_config`]'s strict TOFU check.
+pub fn connect_and_sync(db: &Database, config: &mut Config, peer_url: &str) -> VoiceResult<SyncResult> {
+    connect_and_sync_with_progress(db, config, peer_url, None)
+}
+
+/// Same as [`connect_and_sync`], but forwards `progress` to [`sync_with_progress`] for the
+/// pull/push phase (the handshake itself is not reported, since it has no meaningful
+/// scanned/total count).
+pub fn connect_and_sync_with_progress(
+    db: &Database,
+    config: &mut Config,
+    peer_url: &str,
+    progress: Option<ProgressCallback<'_>>,
+) -> VoiceResult<SyncResult> {
+    let (discovery_tls, discovered_fingerprint) = crate::tls::build_discovery_tls_config();
+    let discovery_agent = ureq::AgentBuilder::new().tls_config(discovery_tls).build();
+    let discovery_peer = PeerConfig {
+        peer_id: String::new(),
+        peer_name: String::new(),
+        peer_url: peer_url.to_string(),
+        certificate_fingerprints: Vec::new(),
+        public_key: None,
+        trust_state: crate::config::TrustState::Trusted,
+        pinned_fingerprint: None,
+        conflicting_fingerprint: None,
+    };
+    let (peer_device_id, peer_device_name, _) = handshake_with_peer(&discovery_agent, config, &discovery_peer)?;
+    let fingerprint = discovered_fingerprint
+        .get()
+        .ok_or_else(|| VoiceError::Tls(format!("{peer_url} presented no certificate during handshake")))?;
+
+    config.add_peer(&peer_device_id, &peer_device_name, peer_url, Some(&fingerprint), true)?;
+    let peer = config
+        .get_peer(&peer_device_id)
+        .cloned()
+        .ok_or_else(|| VoiceError::sync(format!("peer {peer_device_id} vanished immediately after registration")))?;
+
+    match progress {
+        Some(progress) => sync_with_progress(db, config, &peer, progress),
+        None => sync_with_peer(db, config, &peer),
+    }
+}
+
+// ============================================================================
+// Python bindings
+// ============================================================================
+
+/// Result of a Python-initiated sync, exposed as getters.
+#[pyclass(name = "SyncResult")]
+#[derive(Clone)]
+pub struct PySyncResult {
+    #[pyo3(get)]
+    pub pulled: i64,
+    #[pyo3(get)]
+    pub pushed: i64,
+    #[pyo3(get)]
+    pub conflicts: i64,
+    #[pyo3(get)]
+    pub errors: Vec<String>,
+}
+
+impl From<SyncResult> for PySyncResult {
+    fn from(result: SyncResult) -> Self {
+        Self {
+            pulled: result.pulled,
+            pushed: result.pushed,
+            conflicts: result.conflicts,
+            errors: result.errors,
+        }
+    }
+}
+
+/// Python entry point for syncing the local database with one configured peer.
+#[pyclass(name = "SyncClient", unsendable)]
+pub struct PySyncClient {
+    db_path: String,
+    config: Config,
+}
+
+#[pymethods]
+impl PySyncClient {
+    #[new]
+    fn new(db_path: &str, config: &crate::config::PyConfig) -> Self {
+        Self {
+            db_path: db_path.to_string(),
+            config: config.inner_clone(),
+        }
+    }
+
+    /// Sync with a pre-registered peer, optionally reporting progress through
+    /// `progress_callback` as `(phase, scanned, total)`. The callback is invoked with the
+    /// GIL held; the pull/push work itself runs with the GIL released, so the callback
+    /// must not touch this or any other `PyDatabase`/`SyncClient` handle to the same
+    /// database file. Returning `False` from the callback cancels the sync with a
+    /// [`crate::error::PyCancelledError`].
+    #[pyo3(signature = (peer_id, peer_name, peer_url, progress_callback=None))]
+    fn sync_peer(
+        &self,
+        py: Python<'_>,
+        peer_id: &str,
+        peer_name: &str,
+        peer_url: &str,
+        progress_callback: Option<Py<PyAny>>,
+    ) -> PyResult<PySyncResult> {
+        let result = py.allow_threads(|| -> VoiceResult<SyncResult> {
+            let db = Database::new(&self.db_path)?;
+            let peer = PeerConfig {
+                peer_id: peer_id.to_string(),
+                peer_name: peer_name.to_string(),
+                peer_url: peer_url.to_string(),
+                certificate_fingerprints: Vec::new(),
+                public_key: None,
+                trust_state: crate::config::TrustState::Trusted,
+                pinned_fingerprint: None,
+                conflicting_fingerprint: None,
+            };
+            match progress_callback.as_ref() {
+                Some(callback) => {
+                    let mut cb = make_progress_callback(callback);
+                    sync_with_progress(&db, &self.config, &peer, &mut cb)
+                }
+                None => sync_with_peer(&db, &self.config, &peer),
+            }
+        })?;
+        Ok(result.into())
+    }
+
+    /// Handshake with a peer reachable only by URL and sync with it in one call, without
+    /// requiring it to already be registered via [`crate::config::PyConfig::add_peer`] (the
+    /// peer is registered automatically, pinned to whatever certificate it presents on first
+    /// contact - see [`connect_and_sync`]). Releases the GIL for the duration of the handshake
+    /// and sync I/O; see [`Self::sync_peer`] for the `progress_callback` contract.
+    #[pyo3(signature = (peer_addr, progress_callback=None))]
+    fn connect_and_sync(&mut self, py: Python<'_>, peer_addr: &str, progress_callback: Option<Py<PyAny>>) -> PyResult<PySyncResult> {
+        let config = &mut self.config;
+        let db_path = &self.db_path;
+        let result = py.allow_threads(|| -> VoiceResult<SyncResult> {
+            let db = Database::new(db_path)?;
+            match progress_callback.as_ref() {
+                Some(callback) => {
+                    let mut cb = make_progress_callback(callback);
+                    connect_and_sync_with_progress(&db, config, peer_addr, Some(&mut cb))
+                }
+                None => connect_and_sync(&db, config, peer_addr),
+            }
+        })?;
+        Ok(result.into())
+    }
+}
+
+/// Build a [`ProgressCallback`]-compatible closure that re-acquires the GIL only for the
+/// duration of invoking `callback`, so the heavy sync work around it stays GIL-free.
+/// An exception raised by the callback is swallowed rather than propagated, same as
+/// [`crate::search::py_execute_search`]'s progress callback.
+fn make_progress_callback(callback: &Py<PyAny>) -> impl FnMut(&str, usize, usize) -> bool + '_ {
+    move |phase: &str, scanned: usize, total: usize| -> bool {
+        Python::with_gil(|py| {
+            callback
+                .call1(py, (phase, scanned, total))
+                .map(|ret| ret.is_truthy(py).unwrap_or(true))
+                .unwrap_or(true)
+        })
+    }
+}
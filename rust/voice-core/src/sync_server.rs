@@ -2,11 +2,27 @@
 //!
 //! This module provides the server side of the sync protocol:
 //! - /sync/handshake - Exchange device info
-//! - /sync/changes - Get changes since timestamp
-//! - /sync/apply - Apply changes from peer
+//! - /sync/changes - Get changes since timestamp, or scoped to a Merkle bucket
+//! - /sync/apply - Apply changes from peer (a note change may be a `"patch"` carrying a
+//!   JSON Merge Patch instead of full content - see [`effective_note_data`])
 //! - /sync/full - Get full dataset for initial sync
+//! - /sync/merkle - Get a Merkle anti-entropy tree node's hash and children (see [`crate::merkle`])
+//! - /sync/chunks - Fetch chunk bytes by hash for the `chunk_hashes` a note change carries
+//!   (see [`crate::chunking`])
+//! - /sync/batch - Pipeline an ordered batch of the above operations in one round trip,
+//!   with all writes committed or rolled back together (see [`batch`])
+//! - /sync/conflicts - List unresolved note content conflicts
+//! - /sync/conflicts/resolve - Resolve one by keeping local, keeping remote, or forking
+//!   the losing side into a new note
 //! - /sync/status - Health check
+//!
+//! Every route but `/sync/status` carries a `device_id` and, for an enrolled peer with a
+//! registered public key, a signature over a canonical per-route message - see
+//! [`verify_signed_envelope`]. This is enforced regardless of TLS: a client can always
+//! choose not to verify the server's certificate, so authentication has to live at this
+//! layer rather than rely on the transport alone (see [`crate::tls::TOFUVerifier`]).
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::{Arc, Mutex, OnceLock};
 
@@ -17,6 +33,7 @@ use axum::{
     routing::{get, post},
     Json, Router,
 };
+use base64::Engine;
 use chrono::Utc;
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -24,8 +41,10 @@ use tokio::sync::oneshot;
 use uuid::Uuid;
 
 use crate::config::Config;
-use crate::database::Database;
+use crate::database::{Database, HlcStamp, VersionVector, VectorOrdering};
 use crate::error::VoiceResult;
+use crate::merge;
+use crate::merkle;
 use crate::sync_client::SyncChange;
 
 /// Server shutdown handle
@@ -47,6 +66,11 @@ struct HandshakeRequest {
     device_id: String,
     device_name: String,
     protocol_version: String,
+    /// Hex-encoded Ed25519 signature (see [`crate::config::Config::sign`]) over
+    /// [`crate::sync_client::handshake_signing_message`]. Only checked if `device_id` is
+    /// already an enrolled peer with a registered public key - see [`verify_signed_envelope`].
+    #[serde(default)]
+    signature: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -62,6 +86,16 @@ struct HandshakeResponse {
 struct ChangesQuery {
     since: Option<String>,
     limit: Option<i64>,
+    /// Hex-encoded Merkle bucket prefix (see [`crate::merkle`]) to scope the scan
+    /// to, in place of (or alongside) `since` - what a client sends once
+    /// `/sync/merkle` has told it which bucket diverged.
+    prefix: Option<String>,
+    device_id: String,
+    /// Hex-encoded Ed25519 signature over
+    /// [`crate::sync_client::changes_signing_message`]. Only checked if `device_id` is
+    /// already an enrolled peer with a registered public key - see [`verify_signed_envelope`].
+    #[serde(default)]
+    signature: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -74,11 +108,44 @@ struct ChangesResponse {
     is_complete: bool,
 }
 
+#[derive(Debug, Deserialize)]
+struct MerkleQuery {
+    /// Hex-encoded prefix: empty for the root, 2 hex chars for a branch, 4 hex
+    /// chars for a leaf (see [`crate::merkle::LEAF_PREFIX_LEN`]).
+    #[serde(default)]
+    prefix: String,
+    device_id: String,
+    /// Hex-encoded Ed25519 signature over
+    /// [`crate::sync_client::merkle_signing_message`]. Only checked if `device_id` is
+    /// already an enrolled peer with a registered public key - see [`verify_signed_envelope`].
+    #[serde(default)]
+    signature: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct MerkleChildHash {
+    /// The next prefix byte, as 2 hex chars.
+    byte: String,
+    hash: String,
+}
+
+#[derive(Debug, Serialize)]
+struct MerkleResponse {
+    prefix: String,
+    hash: String,
+    children: Vec<MerkleChildHash>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ApplyRequest {
     device_id: String,
     device_name: String,
     changes: Vec<SyncChange>,
+    /// Hex-encoded Ed25519 signature over [`crate::sync_client::apply_signing_message`]. Only
+    /// checked if `device_id` is already an enrolled peer with a registered public key - see
+    /// [`verify_signed_envelope`].
+    #[serde(default)]
+    signature: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -88,6 +155,114 @@ struct ApplyResponse {
     errors: Vec<String>,
 }
 
+/// One operation within a `/sync/batch` request, tagged by `kind`. `device_id`/
+/// `device_name` for `Handshake` and `Apply` come from the enclosing [`BatchRequest`]
+/// rather than being repeated per-operation, since a batch always speaks for one peer.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum BatchOperation {
+    Handshake,
+    GetChanges {
+        since: Option<String>,
+        limit: Option<i64>,
+        prefix: Option<String>,
+    },
+    Apply {
+        changes: Vec<SyncChange>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchRequest {
+    device_id: String,
+    device_name: String,
+    operations: Vec<BatchOperation>,
+    /// Hex-encoded Ed25519 signature over [`batch_signing_message`]. Only checked if
+    /// `device_id` is already an enrolled peer with a registered public key - see
+    /// [`verify_signed_envelope`].
+    #[serde(default)]
+    signature: Option<String>,
+}
+
+/// Canonical bytes signed over a `/sync/batch` request: `device_id` followed by the
+/// JSON-serialized operation list, the same pattern
+/// [`crate::sync_client::apply_signing_message`] uses for `/sync/apply`.
+fn batch_signing_message(device_id: &str, operations: &[BatchOperation]) -> VoiceResult<Vec<u8>> {
+    let mut message = device_id.as_bytes().to_vec();
+    message.extend(serde_json::to_vec(operations).map_err(|e| crate::error::VoiceError::sync(e.to_string()))?);
+    Ok(message)
+}
+
+/// Result of one [`BatchOperation`], tagged by `kind` to match. `Error` covers both a
+/// malformed operation (e.g. a bad Merkle prefix) and a database failure partway through
+/// the batch - either way it causes the whole batch's writes to roll back rather than
+/// producing a partially-applied result, see [`batch`].
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum BatchOutcome {
+    Handshake(HandshakeResponse),
+    GetChanges(ChangesResponse),
+    Apply(ApplyResponse),
+    Error { error: String },
+}
+
+#[derive(Debug, Serialize)]
+struct BatchResponse {
+    /// Whether the batch's writes were committed as one SQLite transaction. `false`
+    /// means every write in the batch was rolled back - check `results` for which
+    /// operation caused it.
+    committed: bool,
+    results: Vec<BatchOutcome>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConflictsQuery {
+    #[serde(default)]
+    include_resolved: bool,
+    device_id: String,
+    /// Hex-encoded Ed25519 signature over [`conflicts_signing_message`]. Only checked if
+    /// `device_id` is already an enrolled peer with a registered public key - see
+    /// [`verify_signed_envelope`].
+    #[serde(default)]
+    signature: Option<String>,
+}
+
+/// Canonical bytes signed over a `/sync/conflicts` request.
+fn conflicts_signing_message(device_id: &str, include_resolved: bool) -> Vec<u8> {
+    format!("{device_id}:{include_resolved}").into_bytes()
+}
+
+#[derive(Debug, Serialize)]
+struct ConflictsResponse {
+    conflicts: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConflictResolveRequest {
+    conflict_id: String,
+    /// `"keep_local"`, `"keep_remote"`, or `"fork"` (create a new note for the losing
+    /// side instead of discarding it).
+    resolution: String,
+    device_id: String,
+    /// Hex-encoded Ed25519 signature over [`conflict_resolve_signing_message`]. Only
+    /// checked if `device_id` is already an enrolled peer with a registered public key -
+    /// see [`verify_signed_envelope`].
+    #[serde(default)]
+    signature: Option<String>,
+}
+
+/// Canonical bytes signed over a `/sync/conflicts/resolve` request.
+fn conflict_resolve_signing_message(device_id: &str, conflict_id: &str, resolution: &str) -> Vec<u8> {
+    format!("{device_id}:{conflict_id}:{resolution}").into_bytes()
+}
+
+#[derive(Debug, Serialize)]
+struct ConflictResolveResponse {
+    resolved: bool,
+    /// The new note's id, set only when `resolution` was `"fork"`.
+    forked_note_id: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct StatusResponse {
     device_id: String,
@@ -101,6 +276,42 @@ struct ErrorResponse {
     error: String,
 }
 
+/// Reject `signature` over `message` if `device_id` is already an enrolled peer (one
+/// registered via [`crate::config::Config::add_peer`] with a public key set via
+/// `set_peer_public_key`). An unenrolled or not-yet-keyed peer is let through unchecked,
+/// the same TOFU-first philosophy as [`crate::tls::TOFUVerifier`]: enrollment is what turns
+/// the check on, not a precondition for talking to a peer at all.
+fn verify_signed_envelope(config: &Arc<Mutex<Config>>, device_id: &str, message: &[u8], signature_hex: Option<&str>) -> Result<(), axum::response::Response> {
+    let config = config.lock().unwrap();
+    let Some(peer) = config.get_peer(device_id) else {
+        return Ok(());
+    };
+    if peer.public_key.is_none() {
+        return Ok(());
+    }
+    let unauthorized = |reason: &str| {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(ErrorResponse {
+                error: format!("signature verification failed: {reason}"),
+            }),
+        )
+            .into_response()
+    };
+    let Some(signature_hex) = signature_hex else {
+        return Err(unauthorized("missing signature from enrolled peer"));
+    };
+    let signature = match crate::config::hex_decode(signature_hex) {
+        Ok(bytes) => bytes,
+        Err(e) => return Err(unauthorized(&e)),
+    };
+    match config.verify_peer(device_id, message, &signature) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(unauthorized("signature does not match peer's registered public key")),
+        Err(e) => Err(unauthorized(&e.to_string())),
+    }
+}
+
 // Route handlers
 
 async fn handshake(
@@ -118,6 +329,11 @@ async fn handshake(
             .into_response();
     }
 
+    let message = crate::sync_client::handshake_signing_message(&request.device_id, &request.device_name, &request.protocol_version);
+    if let Err(response) = verify_signed_envelope(&state.config, &request.device_id, &message, request.signature.as_deref()) {
+        return response;
+    }
+
     // Get last sync timestamp for this peer
     let last_sync = get_peer_last_sync(&state.db, &request.device_id);
 
@@ -136,10 +352,44 @@ async fn get_changes(
     State(state): State<AppState>,
     Query(query): Query<ChangesQuery>,
 ) -> impl IntoResponse {
+    if query.device_id.len() != 32 || !query.device_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid device_id format".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
     let limit = query.limit.unwrap_or(1000).min(10000);
 
+    let message = crate::sync_client::changes_signing_message(&query.device_id, query.since.as_deref(), limit, query.prefix.as_deref());
+    if let Err(response) = verify_signed_envelope(&state.config, &query.device_id, &message, query.signature.as_deref()) {
+        return response;
+    }
+
+    let prefix = match query.prefix.as_deref().map(merkle::parse_prefix_hex) {
+        Some(Some(bytes)) => Some(bytes),
+        Some(None) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Invalid prefix format".to_string(),
+                }),
+            )
+                .into_response();
+        }
+        None => None,
+    };
+
     // Get changes from database
-    let (changes, latest_timestamp) = match get_changes_since(&state.db, query.since.as_deref(), limit) {
+    let (changes, latest_timestamp) = match state
+        .db
+        .lock()
+        .unwrap()
+        .get_changes_since(query.since.as_deref(), limit, prefix.as_deref())
+    {
         Ok(result) => result,
         Err(e) => {
             return (
@@ -179,6 +429,20 @@ async fn apply_changes(
             .into_response();
     }
 
+    let message = match crate::sync_client::apply_signing_message(&request.device_id, &request.changes) {
+        Ok(message) => message,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse { error: e.to_string() }),
+            )
+                .into_response();
+        }
+    };
+    if let Err(response) = verify_signed_envelope(&state.config, &request.device_id, &message, request.signature.as_deref()) {
+        return response;
+    }
+
     // Apply changes
     let (applied, conflicts, errors) = match apply_sync_changes(
         &state.db,
@@ -207,9 +471,167 @@ async fn apply_changes(
     Json(response).into_response()
 }
 
-async fn get_full_sync(State(state): State<AppState>) -> impl IntoResponse {
+/// Pipeline an ordered batch of handshake/get_changes/apply operations through one
+/// round trip, for high-latency links where the usual four-request handshake -> changes
+/// -> apply -> status dance dominates sync time. All writes run inside a single SQLite
+/// transaction (see [`Database::begin_transaction`]) so the batch is atomic: if any
+/// operation hard-fails, every write the batch made is rolled back and `committed` comes
+/// back `false`, even though `results` still reports what each operation would have done.
+async fn batch(
+    State(state): State<AppState>,
+    Json(request): Json<BatchRequest>,
+) -> impl IntoResponse {
+    if request.device_id.len() != 32 || !request.device_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid device_id format".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    let message = match batch_signing_message(&request.device_id, &request.operations) {
+        Ok(message) => message,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse { error: e.to_string() }),
+            )
+                .into_response();
+        }
+    };
+    if let Err(response) = verify_signed_envelope(&state.config, &request.device_id, &message, request.signature.as_deref()) {
+        return response;
+    }
+
+    let db = state.db.lock().unwrap();
+    if let Err(e) = db.begin_transaction() {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse { error: e.to_string() }),
+        )
+            .into_response();
+    }
+
+    let mut results = Vec::with_capacity(request.operations.len());
+    let mut failed = false;
+    for op in &request.operations {
+        let outcome = run_batch_operation(&db, &state, &request, op);
+        if matches!(outcome, BatchOutcome::Error { .. }) {
+            failed = true;
+        }
+        results.push(outcome);
+    }
+
+    let committed = !failed;
+    let end_result = if committed { db.commit_transaction() } else { db.rollback_transaction() };
+    if let Err(e) = end_result {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse { error: e.to_string() }),
+        )
+            .into_response();
+    }
+
+    Json(BatchResponse { committed, results }).into_response()
+}
+
+fn run_batch_operation(db: &Database, state: &AppState, request: &BatchRequest, op: &BatchOperation) -> BatchOutcome {
+    match op {
+        BatchOperation::Handshake => match db.get_peer_last_sync(&request.device_id) {
+            Ok(last_sync) => BatchOutcome::Handshake(HandshakeResponse {
+                device_id: state.device_id.clone(),
+                device_name: state.device_name.clone(),
+                protocol_version: "1.0".to_string(),
+                last_sync_timestamp: last_sync,
+                server_timestamp: Utc::now().to_rfc3339(),
+            }),
+            Err(e) => BatchOutcome::Error { error: e.to_string() },
+        },
+        BatchOperation::GetChanges { since, limit, prefix } => {
+            let limit = limit.unwrap_or(1000).min(10000);
+            let prefix_bytes = match prefix.as_deref().map(merkle::parse_prefix_hex) {
+                Some(Some(bytes)) => Some(bytes),
+                Some(None) => {
+                    return BatchOutcome::Error {
+                        error: "Invalid prefix format".to_string(),
+                    }
+                }
+                None => None,
+            };
+            match db.get_changes_since(since.as_deref(), limit, prefix_bytes.as_deref()) {
+                Ok((changes, latest_timestamp)) => BatchOutcome::GetChanges(ChangesResponse {
+                    is_complete: (changes.len() as i64) < limit,
+                    changes,
+                    from_timestamp: since.clone(),
+                    to_timestamp: latest_timestamp,
+                    device_id: state.device_id.clone(),
+                    device_name: state.device_name.clone(),
+                }),
+                Err(e) => BatchOutcome::Error { error: e.to_string() },
+            }
+        }
+        BatchOperation::Apply { changes } => {
+            let last_sync_at = match db.get_peer_last_sync(&request.device_id) {
+                Ok(v) => v,
+                Err(e) => return BatchOutcome::Error { error: e.to_string() },
+            };
+
+            let mut applied = 0i64;
+            let mut conflicts = 0i64;
+            let mut errors = Vec::new();
+            for change in changes {
+                match apply_incoming_change(db, change, last_sync_at.as_deref()) {
+                    Ok(ApplyOutcome::Applied) => applied += 1,
+                    Ok(ApplyOutcome::Conflict) => conflicts += 1,
+                    Ok(ApplyOutcome::Skipped) => {}
+                    Err(e) => errors.push(format!("Error applying {} {}: {}", change.entity_type, change.entity_id, e)),
+                }
+            }
+
+            match db.update_peer_sync_time(&request.device_id, Some(&request.device_name)) {
+                Ok(()) => BatchOutcome::Apply(ApplyResponse { applied, conflicts, errors }),
+                Err(e) => BatchOutcome::Error { error: e.to_string() },
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FullSyncQuery {
+    device_id: String,
+    /// Hex-encoded Ed25519 signature over [`full_sync_signing_message`]. Only checked if
+    /// `device_id` is already an enrolled peer with a registered public key - see
+    /// [`verify_signed_envelope`].
+    #[serde(default)]
+    signature: Option<String>,
+}
+
+/// Canonical bytes signed over a `/sync/full` request: just `device_id`, since the
+/// request carries no other parameters to bind the signature to.
+fn full_sync_signing_message(device_id: &str) -> Vec<u8> {
+    device_id.as_bytes().to_vec()
+}
+
+async fn get_full_sync(State(state): State<AppState>, Query(query): Query<FullSyncQuery>) -> impl IntoResponse {
+    if query.device_id.len() != 32 || !query.device_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid device_id format".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    let message = full_sync_signing_message(&query.device_id);
+    if let Err(response) = verify_signed_envelope(&state.config, &query.device_id, &message, query.signature.as_deref()) {
+        return response;
+    }
+
     // Get all notes, tags, and note_tags
-    let data = match get_full_dataset(&state.db) {
+    let data = match state.db.lock().unwrap().get_full_dataset() {
         Ok(d) => d,
         Err(e) => {
             return (
@@ -225,260 +647,279 @@ async fn get_full_sync(State(state): State<AppState>) -> impl IntoResponse {
     Json(data).into_response()
 }
 
-async fn status(State(state): State<AppState>) -> impl IntoResponse {
-    Json(StatusResponse {
-        device_id: state.device_id.clone(),
-        device_name: state.device_name.clone(),
-        protocol_version: "1.0".to_string(),
-        status: "ok".to_string(),
-    })
-}
+async fn get_merkle_node(
+    State(state): State<AppState>,
+    Query(query): Query<MerkleQuery>,
+) -> impl IntoResponse {
+    if query.device_id.len() != 32 || !query.device_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid device_id format".to_string(),
+            }),
+        )
+            .into_response();
+    }
 
-// Helper functions
+    let Some(prefix) = merkle::parse_prefix_hex(&query.prefix) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid prefix format".to_string(),
+            }),
+        )
+            .into_response();
+    };
 
-fn get_peer_last_sync(db: &Arc<Mutex<Database>>, peer_id: &str) -> Option<String> {
-    let peer_uuid = Uuid::parse_str(peer_id).ok()?;
-    let peer_bytes = peer_uuid.as_bytes().to_vec();
+    let message = crate::sync_client::merkle_signing_message(&query.device_id, &query.prefix);
+    if let Err(response) = verify_signed_envelope(&state.config, &query.device_id, &message, query.signature.as_deref()) {
+        return response;
+    }
 
-    let db = db.lock().ok()?;
-    let conn = db.connection();
+    let (hash, children) = match state.db.lock().unwrap().merkle_node(&prefix) {
+        Ok(result) => result,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: e.to_string(),
+                }),
+            )
+                .into_response();
+        }
+    };
 
-    conn.query_row(
-        "SELECT last_sync_at FROM sync_peers WHERE peer_id = ?",
-        [peer_bytes],
-        |row| row.get(0),
-    )
-    .ok()
+    Json(MerkleResponse {
+        prefix: query.prefix,
+        hash: merkle::to_hex(&hash),
+        children: children
+            .into_iter()
+            .map(|(byte, hash)| MerkleChildHash {
+                byte: format!("{:02x}", byte),
+                hash: merkle::to_hex(&hash),
+            })
+            .collect(),
+    })
+    .into_response()
 }
 
-fn get_changes_since(
-    db: &Arc<Mutex<Database>>,
-    since: Option<&str>,
-    limit: i64,
-) -> VoiceResult<(Vec<SyncChange>, Option<String>)> {
-    let db = db.lock().unwrap();
-    let conn = db.connection();
-
-    let mut changes = Vec::new();
-    let mut latest_timestamp: Option<String> = None;
-
-    // Get notes changes
-    let notes_query = if since.is_some() {
-        "SELECT id, created_at, content, modified_at, deleted_at FROM notes \
-         WHERE modified_at > ? OR (modified_at IS NULL AND created_at > ?) \
-         ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
-    } else {
-        "SELECT id, created_at, content, modified_at, deleted_at FROM notes \
-         ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
-    };
-
-    let mut stmt = conn.prepare(notes_query)?;
-    let notes_rows: Vec<_> = if let Some(ts) = since {
-        stmt.query_map(rusqlite::params![ts, ts, limit], |row| {
-            let id_bytes: Vec<u8> = row.get(0)?;
-            let created_at: String = row.get(1)?;
-            let content: String = row.get(2)?;
-            let modified_at: Option<String> = row.get(3)?;
-            let deleted_at: Option<String> = row.get(4)?;
-
-            Ok((id_bytes, created_at, content, modified_at, deleted_at))
-        })?
-        .collect()
-    } else {
-        stmt.query_map(rusqlite::params![limit], |row| {
-            let id_bytes: Vec<u8> = row.get(0)?;
-            let created_at: String = row.get(1)?;
-            let content: String = row.get(2)?;
-            let modified_at: Option<String> = row.get(3)?;
-            let deleted_at: Option<String> = row.get(4)?;
-
-            Ok((id_bytes, created_at, content, modified_at, deleted_at))
-        })?
-        .collect()
-    };
+#[derive(Debug, Deserialize)]
+struct ChunksRequest {
+    /// Hex-encoded chunk hashes (see [`crate::chunking`]) the caller is missing.
+    hashes: Vec<String>,
+    device_id: String,
+    /// Hex-encoded Ed25519 signature over [`crate::sync_client::chunks_signing_message`]. Only checked if
+    /// `device_id` is already an enrolled peer with a registered public key - see
+    /// [`verify_signed_envelope`].
+    #[serde(default)]
+    signature: Option<String>,
+}
 
-    for row in notes_rows {
-        let (id_bytes, created_at, content, modified_at, deleted_at) = row?;
-        let id_hex = crate::validation::uuid_bytes_to_hex(&id_bytes)?;
+#[derive(Debug, Serialize)]
+struct ChunksResponse {
+    /// Base64-encoded bytes for every requested hash we have, keyed by the same
+    /// hex-encoded hash the caller sent.
+    chunks: HashMap<String, String>,
+    /// Hashes from the request we don't have either - shouldn't normally happen, since a
+    /// peer only asks for hashes it saw in a change it's applying, but surfaced rather than
+    /// silently dropped in case the two sides have drifted.
+    missing: Vec<String>,
+}
 
-        let operation = if deleted_at.is_some() {
-            "delete"
-        } else if modified_at.is_some() {
-            "update"
-        } else {
-            "create"
-        };
+/// Serve the raw bytes behind a set of content-defined chunk hashes (see
+/// [`crate::chunking`]) so a peer that received a note's `chunk_hashes` over
+/// `/sync/changes` can fetch only the chunks it doesn't already have.
+async fn get_chunks(State(state): State<AppState>, Json(request): Json<ChunksRequest>) -> impl IntoResponse {
+    if request.device_id.len() != 32 || !request.device_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid device_id format".to_string(),
+            }),
+        )
+            .into_response();
+    }
 
-        let timestamp = modified_at
-            .clone()
-            .or_else(|| deleted_at.clone())
-            .unwrap_or_else(|| created_at.clone());
+    let message = match crate::sync_client::chunks_signing_message(&request.device_id, &request.hashes) {
+        Ok(message) => message,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse { error: e.to_string() }),
+            )
+                .into_response();
+        }
+    };
+    if let Err(response) = verify_signed_envelope(&state.config, &request.device_id, &message, request.signature.as_deref()) {
+        return response;
+    }
 
-        if latest_timestamp.is_none() || latest_timestamp.as_ref() < Some(&timestamp) {
-            latest_timestamp = Some(timestamp.clone());
+    let db = state.db.lock().unwrap();
+    let mut chunks = HashMap::new();
+    let mut missing = Vec::new();
+    for hash_hex in request.hashes {
+        match db.get_chunk(&hash_hex) {
+            Ok(Some(bytes)) => {
+                chunks.insert(hash_hex, base64::engine::general_purpose::STANDARD.encode(bytes));
+            }
+            Ok(None) => missing.push(hash_hex),
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse { error: e.to_string() }),
+                )
+                    .into_response();
+            }
         }
+    }
+    Json(ChunksResponse { chunks, missing }).into_response()
+}
 
-        changes.push(SyncChange {
-            entity_type: "note".to_string(),
-            entity_id: id_hex.clone(),
-            operation: operation.to_string(),
-            data: serde_json::json!({
-                "id": id_hex,
-                "created_at": created_at,
-                "content": content,
-                "modified_at": modified_at,
-                "deleted_at": deleted_at,
+/// List unresolved note content conflicts (see [`crate::conflicts`]) so a client can
+/// surface them for the user to resolve, instead of the losing side sitting invisibly
+/// in the conflicts table forever.
+async fn list_conflicts(State(state): State<AppState>, Query(query): Query<ConflictsQuery>) -> impl IntoResponse {
+    if query.device_id.len() != 32 || !query.device_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid device_id format".to_string(),
             }),
-            timestamp,
-            device_id: String::new(),
-            device_name: None,
-        });
+        )
+            .into_response();
     }
 
-    // Get tag changes
-    let remaining = limit - changes.len() as i64;
-    if remaining > 0 {
-        let tags_query = if since.is_some() {
-            "SELECT id, name, parent_id, created_at, modified_at FROM tags \
-             WHERE modified_at > ? OR (modified_at IS NULL AND created_at > ?) \
-             ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
-        } else {
-            "SELECT id, name, parent_id, created_at, modified_at FROM tags \
-             ORDER BY COALESCE(modified_at, created_at) LIMIT ?"
-        };
-
-        let mut stmt = conn.prepare(tags_query)?;
-        let tag_rows: Vec<_> = if let Some(ts) = since {
-            stmt.query_map(rusqlite::params![ts, ts, remaining], |row| {
-                let id_bytes: Vec<u8> = row.get(0)?;
-                let name: String = row.get(1)?;
-                let parent_id_bytes: Option<Vec<u8>> = row.get(2)?;
-                let created_at: String = row.get(3)?;
-                let modified_at: Option<String> = row.get(4)?;
-                Ok((id_bytes, name, parent_id_bytes, created_at, modified_at))
-            })?
-            .collect()
-        } else {
-            stmt.query_map(rusqlite::params![remaining], |row| {
-                let id_bytes: Vec<u8> = row.get(0)?;
-                let name: String = row.get(1)?;
-                let parent_id_bytes: Option<Vec<u8>> = row.get(2)?;
-                let created_at: String = row.get(3)?;
-                let modified_at: Option<String> = row.get(4)?;
-                Ok((id_bytes, name, parent_id_bytes, created_at, modified_at))
-            })?
-            .collect()
-        };
+    let message = conflicts_signing_message(&query.device_id, query.include_resolved);
+    if let Err(response) = verify_signed_envelope(&state.config, &query.device_id, &message, query.signature.as_deref()) {
+        return response;
+    }
 
-        for row in tag_rows {
-            let (id_bytes, name, parent_id_bytes, created_at, modified_at) = row?;
-            let id_hex = crate::validation::uuid_bytes_to_hex(&id_bytes)?;
-            let parent_id_hex = parent_id_bytes
-                .map(|b| crate::validation::uuid_bytes_to_hex(&b))
-                .transpose()?;
+    match state.db.lock().unwrap().get_note_content_conflicts(query.include_resolved) {
+        Ok(conflicts) => Json(ConflictsResponse { conflicts }).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse { error: e.to_string() }),
+        )
+            .into_response(),
+    }
+}
 
-            let operation = if modified_at.is_some() { "update" } else { "create" };
-            let timestamp = modified_at.clone().unwrap_or_else(|| created_at.clone());
+/// Resolve a note content conflict by keeping the local side, the remote side, or both
+/// (forking the losing side into a new note via
+/// [`crate::database::Database::fork_note_content_conflict`]).
+async fn resolve_conflict(State(state): State<AppState>, Json(request): Json<ConflictResolveRequest>) -> impl IntoResponse {
+    if request.device_id.len() != 32 || !request.device_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid device_id format".to_string(),
+            }),
+        )
+            .into_response();
+    }
 
-            if latest_timestamp.is_none() || latest_timestamp.as_ref() < Some(&timestamp) {
-                latest_timestamp = Some(timestamp.clone());
-            }
+    let message = conflict_resolve_signing_message(&request.device_id, &request.conflict_id, &request.resolution);
+    if let Err(response) = verify_signed_envelope(&state.config, &request.device_id, &message, request.signature.as_deref()) {
+        return response;
+    }
 
-            changes.push(SyncChange {
-                entity_type: "tag".to_string(),
-                entity_id: id_hex.clone(),
-                operation: operation.to_string(),
-                data: serde_json::json!({
-                    "id": id_hex,
-                    "name": name,
-                    "parent_id": parent_id_hex,
-                    "created_at": created_at,
-                    "modified_at": modified_at,
+    let db = state.db.lock().unwrap();
+
+    if request.resolution == "fork" {
+        return match db.fork_note_content_conflict(&request.conflict_id) {
+            Ok(Some(forked_note_id)) => Json(ConflictResolveResponse {
+                resolved: true,
+                forked_note_id: Some(forked_note_id),
+            })
+            .into_response(),
+            Ok(None) => (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "conflict not found".to_string(),
                 }),
-                timestamp,
-                device_id: String::new(),
-                device_name: None,
-            });
-        }
+            )
+                .into_response(),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse { error: e.to_string() }),
+            )
+                .into_response(),
+        };
     }
 
-    // Get note_tag changes
-    let remaining = limit - changes.len() as i64;
-    if remaining > 0 {
-        let note_tags_query = if since.is_some() {
-            "SELECT note_id, tag_id, created_at, modified_at, deleted_at FROM note_tags \
-             WHERE created_at > ? OR deleted_at > ? OR modified_at > ? \
-             ORDER BY COALESCE(modified_at, deleted_at, created_at) LIMIT ?"
-        } else {
-            "SELECT note_id, tag_id, created_at, modified_at, deleted_at FROM note_tags \
-             ORDER BY COALESCE(modified_at, deleted_at, created_at) LIMIT ?"
-        };
+    let field = match request.resolution.as_str() {
+        "keep_local" => "local_content",
+        "keep_remote" => "remote_content",
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "resolution must be one of keep_local, keep_remote, fork".to_string(),
+                }),
+            )
+                .into_response()
+        }
+    };
 
-        let mut stmt = conn.prepare(note_tags_query)?;
-        let note_tag_rows: Vec<_> = if let Some(ts) = since {
-            stmt.query_map(rusqlite::params![ts, ts, ts, remaining], |row| {
-                let note_id_bytes: Vec<u8> = row.get(0)?;
-                let tag_id_bytes: Vec<u8> = row.get(1)?;
-                let created_at: String = row.get(2)?;
-                let modified_at: Option<String> = row.get(3)?;
-                let deleted_at: Option<String> = row.get(4)?;
-                Ok((note_id_bytes, tag_id_bytes, created_at, modified_at, deleted_at))
-            })?
-            .collect()
-        } else {
-            stmt.query_map(rusqlite::params![remaining], |row| {
-                let note_id_bytes: Vec<u8> = row.get(0)?;
-                let tag_id_bytes: Vec<u8> = row.get(1)?;
-                let created_at: String = row.get(2)?;
-                let modified_at: Option<String> = row.get(3)?;
-                let deleted_at: Option<String> = row.get(4)?;
-                Ok((note_id_bytes, tag_id_bytes, created_at, modified_at, deleted_at))
-            })?
-            .collect()
-        };
+    let payload = match db.note_content_conflict_payload(&request.conflict_id) {
+        Ok(Some(p)) => p,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "conflict not found".to_string(),
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse { error: e.to_string() }),
+            )
+                .into_response()
+        }
+    };
+    let content = payload[field].as_str().unwrap_or("");
 
-        for row in note_tag_rows {
-            let (note_id_bytes, tag_id_bytes, created_at, modified_at, deleted_at) = row?;
-            let note_id_hex = crate::validation::uuid_bytes_to_hex(&note_id_bytes)?;
-            let tag_id_hex = crate::validation::uuid_bytes_to_hex(&tag_id_bytes)?;
-            let entity_id = format!("{}:{}", note_id_hex, tag_id_hex);
+    match db.resolve_note_content_conflict(&request.conflict_id, content) {
+        Ok(resolved) => Json(ConflictResolveResponse {
+            resolved,
+            forked_note_id: None,
+        })
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse { error: e.to_string() }),
+        )
+            .into_response(),
+    }
+}
 
-            let operation = if deleted_at.is_some() {
-                "delete"
-            } else if modified_at.is_some() {
-                "update"
-            } else {
-                "create"
-            };
+async fn status(State(state): State<AppState>) -> impl IntoResponse {
+    Json(StatusResponse {
+        device_id: state.device_id.clone(),
+        device_name: state.device_name.clone(),
+        protocol_version: "1.0".to_string(),
+        status: "ok".to_string(),
+    })
+}
 
-            let timestamp = modified_at
-                .clone()
-                .or_else(|| deleted_at.clone())
-                .unwrap_or_else(|| created_at.clone());
+// Helper functions
 
-            if latest_timestamp.is_none() || latest_timestamp.as_ref() < Some(&timestamp) {
-                latest_timestamp = Some(timestamp.clone());
-            }
+fn get_peer_last_sync(db: &Arc<Mutex<Database>>, peer_id: &str) -> Option<String> {
+    let peer_uuid = Uuid::parse_str(peer_id).ok()?;
+    let peer_bytes = peer_uuid.as_bytes().to_vec();
 
-            changes.push(SyncChange {
-                entity_type: "note_tag".to_string(),
-                entity_id,
-                operation: operation.to_string(),
-                data: serde_json::json!({
-                    "note_id": note_id_hex,
-                    "tag_id": tag_id_hex,
-                    "created_at": created_at,
-                    "modified_at": modified_at,
-                    "deleted_at": deleted_at,
-                }),
-                timestamp,
-                device_id: String::new(),
-                device_name: None,
-            });
-        }
-    }
+    let db = db.lock().ok()?;
+    let conn = db.connection();
 
-    Ok((changes, latest_timestamp))
+    conn.query_row(
+        "SELECT last_sync_at FROM sync_peers WHERE peer_id = ?",
+        [peer_bytes],
+        |row| row.get(0),
+    )
+    .ok()
 }
 
 fn apply_sync_changes(
@@ -496,20 +937,10 @@ fn apply_sync_changes(
     let last_sync_at = db.get_peer_last_sync(peer_device_id)?;
 
     for change in changes {
-        let result = match change.entity_type.as_str() {
-            "note" => apply_note_change(&db, change, last_sync_at.as_deref()),
-            "tag" => apply_tag_change(&db, change, last_sync_at.as_deref()),
-            "note_tag" => apply_note_tag_change(&db, change, last_sync_at.as_deref()),
-            _ => {
-                errors.push(format!("Unknown entity type: {}", change.entity_type));
-                continue;
-            }
-        };
-
-        match result {
-            Ok(ApplyResult::Applied) => applied += 1,
-            Ok(ApplyResult::Conflict) => conflicts += 1,
-            Ok(ApplyResult::Skipped) => {}
+        match apply_incoming_change(&db, change, last_sync_at.as_deref()) {
+            Ok(ApplyOutcome::Applied) => applied += 1,
+            Ok(ApplyOutcome::Conflict) => conflicts += 1,
+            Ok(ApplyOutcome::Skipped) => {}
             Err(e) => errors.push(format!(
                 "Error applying {} {}: {}",
                 change.entity_type, change.entity_id, e
@@ -523,338 +954,330 @@ fn apply_sync_changes(
     Ok((applied, conflicts, errors))
 }
 
-enum ApplyResult {
+/// Outcome of applying one incoming [`SyncChange`] to the local database.
+pub(crate) enum ApplyOutcome {
+    /// The remote change was written (it dominated, or there was nothing local to conflict with).
     Applied,
+    /// Local and remote diverged concurrently; a conflict row was recorded instead of guessing.
     Conflict,
+    /// The remote change was a no-op: local already dominates or matches it.
     Skipped,
 }
 
-fn apply_note_change(
+/// Dispatch one incoming change to the entity-specific apply function, comparing
+/// version vectors (see [`crate::database::VersionVector`]) rather than wall-clock
+/// timestamps to tell a stale peer apart from a genuinely concurrent edit. Truly
+/// concurrent edits still produce an [`ApplyOutcome::Conflict`] (both sides are kept
+/// in `conflicts.rs` for review), but the entity's live value is settled
+/// deterministically by comparing [`crate::database::HlcStamp`]s rather than always
+/// favoring whichever side applied the change.
+pub(crate) fn apply_incoming_change(
     db: &Database,
     change: &SyncChange,
     last_sync_at: Option<&str>,
-) -> VoiceResult<ApplyResult> {
-    let note_id = &change.entity_id;
-    let data = &change.data;
+) -> VoiceResult<ApplyOutcome> {
+    match change.entity_type.as_str() {
+        "note" => apply_note_change(db, change, last_sync_at),
+        "tag" => apply_tag_change(db, change, last_sync_at),
+        "note_tag" => apply_note_tag_change(db, change, last_sync_at),
+        other => Err(crate::error::VoiceError::sync(format!("unknown entity type: {other}"))),
+    }
+}
+
+fn remote_vector_of(data: &serde_json::Value) -> VersionVector {
+    data.get("version_vector")
+        .and_then(|v| serde_json::from_value::<HashMap<String, u64>>(v.clone()).ok())
+        .map(VersionVector)
+        .unwrap_or_default()
+}
+
+fn stored_vector_of(row: &HashMap<String, serde_json::Value>) -> VersionVector {
+    row.get("version_vector")
+        .and_then(|v| serde_json::from_value::<HashMap<String, u64>>(v.clone()).ok())
+        .map(VersionVector)
+        .unwrap_or_default()
+}
 
+fn remote_hlc_of(data: &serde_json::Value) -> HlcStamp {
+    data.get("hlc")
+        .and_then(|v| serde_json::from_value::<HlcStamp>(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+fn stored_hlc_of(row: &HashMap<String, serde_json::Value>) -> HlcStamp {
+    row.get("hlc")
+        .and_then(|v| serde_json::from_value::<HlcStamp>(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Resolve a change's effective full-entity JSON: the `data` as-is for the usual
+/// `"create"`/`"update"`/`"delete"` operations, or `data` applied as an RFC 7386 JSON
+/// Merge Patch on top of `existing` for a `"patch"` operation (see
+/// [`merge::apply_json_merge_patch`]). With no `existing` row to patch against - the
+/// entity doesn't exist locally yet - the patch is applied to an empty object, so a
+/// `"patch"` change for a brand-new entity behaves like a `"create"` carrying only the
+/// fields the patch set.
+fn effective_note_data(change: &SyncChange, existing: Option<&HashMap<String, serde_json::Value>>) -> serde_json::Value {
+    if change.operation != "patch" {
+        return change.data.clone();
+    }
+    let mut merged = existing
+        .map(|row| serde_json::Value::Object(row.iter().map(|(k, v)| (k.clone(), v.clone())).collect()))
+        .unwrap_or_else(|| serde_json::json!({}));
+    merge::apply_json_merge_patch(&mut merged, &change.data);
+    merged
+}
+
+fn apply_note_change(db: &Database, change: &SyncChange, _last_sync_at: Option<&str>) -> VoiceResult<ApplyOutcome> {
+    let note_id = &change.entity_id;
     let existing = db.get_note_raw(note_id)?;
+    let data = effective_note_data(change, existing.as_ref());
+    let data = &data;
+    let remote_vector = remote_vector_of(data);
+    let remote_hlc = remote_hlc_of(data);
 
-    match change.operation.as_str() {
-        "create" => {
-            if existing.is_some() {
-                return Ok(ApplyResult::Skipped);
-            }
-            db.apply_sync_note(
-                note_id,
-                data["created_at"].as_str().unwrap_or(""),
-                data["content"].as_str().unwrap_or(""),
-                data["modified_at"].as_str(),
-                data["deleted_at"].as_str(),
-            )?;
-            Ok(ApplyResult::Applied)
+    let created_at = data["created_at"].as_str().unwrap_or("");
+    // A change from `get_changes_since` carries `chunk_hashes` instead of `content` - the
+    // caller is expected to have already fetched via `/sync/chunks` whichever hashes it was
+    // missing (see `crate::sync_client::sync_with_peer`) before handing the change here.
+    let content = match data.get("content").and_then(|v| v.as_str()) {
+        Some(content) => content.to_string(),
+        None => {
+            let chunk_hashes: Vec<String> = data
+                .get("chunk_hashes")
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or_default();
+            db.reassemble_chunks(&chunk_hashes)?
         }
-        "update" | "delete" => {
-            let created_at = data["created_at"].as_str().unwrap_or("");
-            let content = data["content"].as_str().unwrap_or("");
-            let modified_at = data["modified_at"].as_str();
-            let deleted_at = data["deleted_at"].as_str();
-
-            if existing.is_none() {
-                db.apply_sync_note(note_id, created_at, content, modified_at, deleted_at)?;
-                return Ok(ApplyResult::Applied);
-            }
+    };
+    let content = content.as_str();
+    let modified_at = data["modified_at"].as_str();
+    let deleted_at = data["deleted_at"].as_str();
 
-            let existing = existing.unwrap();
+    let Some(existing) = existing else {
+        // Nothing local to conflict with - take the remote state as-is.
+        db.apply_sync_note(note_id, created_at, content, modified_at, deleted_at, &remote_vector, &remote_hlc)?;
+        return Ok(ApplyOutcome::Applied);
+    };
 
-            // Check if local changed since last sync
-            let local_time = existing.get("modified_at")
+    let local_vector = stored_vector_of(&existing);
+    match local_vector.compare(&remote_vector) {
+        VectorOrdering::Equal | VectorOrdering::Dominates => Ok(ApplyOutcome::Skipped),
+        VectorOrdering::Dominated => {
+            db.apply_sync_note(note_id, created_at, content, modified_at, deleted_at, &remote_vector, &remote_hlc)?;
+            Ok(ApplyOutcome::Applied)
+        }
+        VectorOrdering::Concurrent => {
+            let local_hlc = stored_hlc_of(&existing);
+            let local_content = existing.get("content").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            let local_modified_at = existing
+                .get("modified_at")
                 .and_then(|v| v.as_str())
-                .or_else(|| existing.get("deleted_at").and_then(|v| v.as_str()));
-            let local_changed = last_sync_at.is_none()
-                || local_time.map_or(false, |lt| lt > last_sync_at.unwrap_or(""));
-
-            // Determine timestamp of incoming change
-            let incoming_time = modified_at.or(deleted_at);
-
-            // If incoming change is before or at last_sync, skip
-            if let (Some(last), Some(incoming)) = (last_sync_at, incoming_time) {
-                if incoming <= last {
-                    return Ok(ApplyResult::Skipped);
-                }
+                .unwrap_or(created_at)
+                .to_string();
+            let local_deleted_at = existing.get("deleted_at").and_then(|v| v.as_str()).map(str::to_string);
+
+            if deleted_at.is_some() && local_deleted_at.is_none() {
+                db.create_note_delete_conflict(
+                    note_id,
+                    &local_content,
+                    &local_modified_at,
+                    None,
+                    Some(content),
+                    deleted_at.unwrap_or(""),
+                    Some(&change.device_id),
+                    change.device_name.as_deref(),
+                )?;
+            } else if deleted_at.is_none() && local_deleted_at.is_some() {
+                db.create_note_delete_conflict(
+                    note_id,
+                    content,
+                    modified_at.unwrap_or(created_at),
+                    Some(&change.device_id),
+                    Some(&local_content),
+                    local_deleted_at.as_deref().unwrap_or(""),
+                    None,
+                    None,
+                )?;
+            } else {
+                db.create_note_content_conflict(
+                    note_id,
+                    &local_content,
+                    &local_modified_at,
+                    content,
+                    modified_at.unwrap_or(created_at),
+                    Some(&change.device_id),
+                    change.device_name.as_deref(),
+                )?;
             }
 
-            if local_changed {
-                // Both sides changed - for now, remote wins (could create conflict)
-                // TODO: Implement proper conflict detection
+            // Both sides are preserved in the conflict row above; which one survives as
+            // the entity's live value is decided by HLC rather than always favoring
+            // local, so a genuinely newer remote edit doesn't get thrown away just
+            // because this device happened to apply the sync.
+            if remote_hlc > local_hlc {
+                db.apply_sync_note(note_id, created_at, content, modified_at, deleted_at, &remote_vector, &remote_hlc)?;
+            } else {
+                db.apply_sync_note(
+                    note_id,
+                    created_at,
+                    &local_content,
+                    existing.get("modified_at").and_then(|v| v.as_str()),
+                    local_deleted_at.as_deref(),
+                    &remote_vector,
+                    &remote_hlc,
+                )?;
             }
-
-            db.apply_sync_note(note_id, created_at, content, modified_at, deleted_at)?;
-            Ok(ApplyResult::Applied)
+            Ok(ApplyOutcome::Conflict)
         }
-        _ => Ok(ApplyResult::Skipped),
     }
 }
 
-fn apply_tag_change(
-    db: &Database,
-    change: &SyncChange,
-    last_sync_at: Option<&str>,
-) -> VoiceResult<ApplyResult> {
+fn apply_tag_change(db: &Database, change: &SyncChange, _last_sync_at: Option<&str>) -> VoiceResult<ApplyOutcome> {
     let tag_id = &change.entity_id;
     let data = &change.data;
+    let remote_vector = remote_vector_of(data);
+    let remote_hlc = remote_hlc_of(data);
+
+    let name = data["name"].as_str().unwrap_or("");
+    let parent_id = data["parent_id"].as_str();
+    let created_at = data["created_at"].as_str().unwrap_or("");
+    let modified_at = data["modified_at"].as_str();
 
     let existing = db.get_tag_raw(tag_id)?;
+    let Some(existing) = existing else {
+        db.apply_sync_tag(tag_id, name, parent_id, created_at, modified_at, &remote_vector, &remote_hlc)?;
+        return Ok(ApplyOutcome::Applied);
+    };
 
-    match change.operation.as_str() {
-        "create" => {
-            if existing.is_some() {
-                return Ok(ApplyResult::Skipped);
-            }
-            db.apply_sync_tag(
-                tag_id,
-                data["name"].as_str().unwrap_or(""),
-                data["parent_id"].as_str(),
-                data["created_at"].as_str().unwrap_or(""),
-                data["modified_at"].as_str(),
-            )?;
-            Ok(ApplyResult::Applied)
+    let local_vector = stored_vector_of(&existing);
+    match local_vector.compare(&remote_vector) {
+        VectorOrdering::Equal | VectorOrdering::Dominates => Ok(ApplyOutcome::Skipped),
+        VectorOrdering::Dominated => {
+            db.apply_sync_tag(tag_id, name, parent_id, created_at, modified_at, &remote_vector, &remote_hlc)?;
+            Ok(ApplyOutcome::Applied)
         }
-        "update" => {
-            let name = data["name"].as_str().unwrap_or("");
-            let parent_id = data["parent_id"].as_str();
-            let created_at = data["created_at"].as_str().unwrap_or("");
-            let modified_at = data["modified_at"].as_str();
-
-            if existing.is_none() {
-                db.apply_sync_tag(tag_id, name, parent_id, created_at, modified_at)?;
-                return Ok(ApplyResult::Applied);
+        VectorOrdering::Concurrent => {
+            let local_hlc = stored_hlc_of(&existing);
+            let local_name = existing.get("name").and_then(|v| v.as_str()).unwrap_or(name).to_string();
+            let local_parent_id = existing.get("parent_id").and_then(|v| v.as_str()).map(str::to_string);
+            let local_modified_at = existing
+                .get("modified_at")
+                .and_then(|v| v.as_str())
+                .unwrap_or(created_at)
+                .to_string();
+
+            if local_name != name {
+                db.create_tag_rename_conflict(
+                    tag_id,
+                    &local_name,
+                    &local_modified_at,
+                    name,
+                    modified_at.unwrap_or(created_at),
+                    Some(&change.device_id),
+                    change.device_name.as_deref(),
+                )?;
             }
-
-            // Check timestamp
-            let incoming_time = modified_at;
-            if let (Some(last), Some(incoming)) = (last_sync_at, incoming_time) {
-                if incoming <= last {
-                    return Ok(ApplyResult::Skipped);
-                }
+            if local_parent_id.as_deref() != parent_id {
+                db.create_tag_parent_conflict(
+                    tag_id,
+                    local_parent_id.as_deref(),
+                    &local_modified_at,
+                    parent_id,
+                    modified_at.unwrap_or(created_at),
+                    Some(&change.device_id),
+                    change.device_name.as_deref(),
+                )?;
             }
 
-            db.apply_sync_tag(tag_id, name, parent_id, created_at, modified_at)?;
-            Ok(ApplyResult::Applied)
+            // Both sides are preserved in the conflict rows above; HLC decides which
+            // one becomes the tag's live name/parent rather than always keeping local.
+            if remote_hlc > local_hlc {
+                db.apply_sync_tag(tag_id, name, parent_id, created_at, modified_at, &remote_vector, &remote_hlc)?;
+            } else {
+                db.apply_sync_tag(
+                    tag_id,
+                    &local_name,
+                    local_parent_id.as_deref(),
+                    created_at,
+                    existing.get("modified_at").and_then(|v| v.as_str()),
+                    &remote_vector,
+                    &remote_hlc,
+                )?;
+            }
+            Ok(ApplyOutcome::Conflict)
         }
-        _ => Ok(ApplyResult::Skipped),
     }
 }
 
-fn apply_note_tag_change(
-    db: &Database,
-    change: &SyncChange,
-    last_sync_at: Option<&str>,
-) -> VoiceResult<ApplyResult> {
+fn apply_note_tag_change(db: &Database, change: &SyncChange, _last_sync_at: Option<&str>) -> VoiceResult<ApplyOutcome> {
     // Parse entity_id (format: "note_id:tag_id")
     let parts: Vec<&str> = change.entity_id.split(':').collect();
     if parts.len() != 2 {
-        return Ok(ApplyResult::Skipped);
+        return Ok(ApplyOutcome::Skipped);
     }
-
     let note_id = parts[0];
     let tag_id = parts[1];
     let data = &change.data;
-
-    // Determine the timestamp of this incoming change
-    let incoming_time = if change.operation == "delete" {
-        data["deleted_at"].as_str().or_else(|| data["modified_at"].as_str())
-    } else {
-        data["modified_at"].as_str().or_else(|| data["created_at"].as_str())
-    };
-
-    // If this change happened before or at last_sync, skip it
-    if let (Some(last), Some(incoming)) = (last_sync_at, incoming_time) {
-        if incoming <= last {
-            return Ok(ApplyResult::Skipped);
-        }
-    }
-
-    let existing = db.get_note_tag_raw(note_id, tag_id)?;
-
-    // Determine if local changed since last_sync
-    let local_changed = if let Some(ref ex) = existing {
-        let local_time = ex.get("modified_at")
-            .and_then(|v| v.as_str())
-            .or_else(|| ex.get("deleted_at").and_then(|v| v.as_str()))
-            .or_else(|| ex.get("created_at").and_then(|v| v.as_str()));
-        last_sync_at.is_none() || local_time.map_or(false, |lt| lt > last_sync_at.unwrap_or(""))
-    } else {
-        false
-    };
+    let remote_vector = remote_vector_of(data);
+    let remote_hlc = remote_hlc_of(data);
 
     let created_at = data["created_at"].as_str().unwrap_or("");
     let modified_at = data["modified_at"].as_str();
     let deleted_at = data["deleted_at"].as_str();
 
-    match change.operation.as_str() {
-        "create" => {
-            if let Some(ref ex) = existing {
-                if ex.get("deleted_at").and_then(|v| v.as_str()).is_none() {
-                    // Already active
-                    return Ok(ApplyResult::Skipped);
-                }
-                // Local is deleted, remote wants active - reactivate
-                let ex_created_at = ex.get("created_at").and_then(|v| v.as_str()).unwrap_or(created_at);
-                db.apply_sync_note_tag(note_id, tag_id, ex_created_at, modified_at, None)?;
-                return Ok(if local_changed { ApplyResult::Conflict } else { ApplyResult::Applied });
-            }
-            // New association
-            db.apply_sync_note_tag(note_id, tag_id, created_at, modified_at, None)?;
-            Ok(ApplyResult::Applied)
-        }
-        "delete" => {
-            if existing.is_none() {
-                // Create as deleted for sync consistency
-                db.apply_sync_note_tag(note_id, tag_id, created_at, modified_at, deleted_at)?;
-                return Ok(ApplyResult::Applied);
-            }
-            let ex = existing.unwrap();
-            if ex.get("deleted_at").and_then(|v| v.as_str()).is_some() {
-                return Ok(ApplyResult::Skipped); // Already deleted
-            }
-            // Local is active, remote wants to delete
-            if local_changed {
-                // Both changed - favor preservation (keep active)
-                return Ok(ApplyResult::Conflict);
-            }
-            // Apply the delete
-            let ex_created_at = ex.get("created_at").and_then(|v| v.as_str()).unwrap_or(created_at);
-            db.apply_sync_note_tag(note_id, tag_id, ex_created_at, modified_at, deleted_at)?;
-            Ok(ApplyResult::Applied)
-        }
-        "update" => {
-            // Update operation - typically reactivation (deleted_at cleared)
-            if existing.is_none() {
-                db.apply_sync_note_tag(note_id, tag_id, created_at, modified_at, deleted_at)?;
-                return Ok(ApplyResult::Applied);
-            }
+    let existing = db.get_note_tag_raw(note_id, tag_id)?;
+    let Some(existing) = existing else {
+        db.apply_sync_note_tag(note_id, tag_id, created_at, modified_at, deleted_at, &remote_vector, &remote_hlc)?;
+        return Ok(ApplyOutcome::Applied);
+    };
 
-            let ex = existing.unwrap();
-            let remote_deleted = deleted_at.is_some();
-            let local_deleted = ex.get("deleted_at").and_then(|v| v.as_str()).is_some();
-            let ex_created_at = ex.get("created_at").and_then(|v| v.as_str()).unwrap_or(created_at);
+    let local_vector = stored_vector_of(&existing);
+    let ex_created_at = existing.get("created_at").and_then(|v| v.as_str()).unwrap_or(created_at).to_string();
+    match local_vector.compare(&remote_vector) {
+        VectorOrdering::Equal | VectorOrdering::Dominates => Ok(ApplyOutcome::Skipped),
+        VectorOrdering::Dominated => {
+            db.apply_sync_note_tag(note_id, tag_id, &ex_created_at, modified_at, deleted_at, &remote_vector, &remote_hlc)?;
+            Ok(ApplyOutcome::Applied)
+        }
+        VectorOrdering::Concurrent => {
+            let local_hlc = stored_hlc_of(&existing);
+            let local_deleted_at = existing.get("deleted_at").and_then(|v| v.as_str()).map(str::to_string);
+            let local_modified_at = existing.get("modified_at").and_then(|v| v.as_str()).map(str::to_string);
 
-            if !remote_deleted && local_deleted {
-                // Remote reactivated, local still deleted - reactivate
-                db.apply_sync_note_tag(note_id, tag_id, ex_created_at, modified_at, None)?;
-                return Ok(if local_changed { ApplyResult::Conflict } else { ApplyResult::Applied });
-            }
+            db.create_note_tag_conflict(
+                note_id,
+                tag_id,
+                Some(&ex_created_at),
+                local_modified_at.as_deref(),
+                local_deleted_at.as_deref(),
+                Some(created_at),
+                modified_at,
+                deleted_at,
+                Some(&change.device_id),
+                change.device_name.as_deref(),
+            )?;
 
-            if remote_deleted && !local_deleted {
-                // Remote wants to delete, local is active
-                if local_changed {
-                    return Ok(ApplyResult::Conflict); // Keep active
-                }
-                db.apply_sync_note_tag(note_id, tag_id, ex_created_at, modified_at, deleted_at)?;
-                return Ok(ApplyResult::Applied);
+            // Both sides are preserved in the conflict row above; HLC decides which
+            // lifecycle state (attached/detached, and when) survives.
+            if remote_hlc > local_hlc {
+                db.apply_sync_note_tag(note_id, tag_id, &ex_created_at, modified_at, deleted_at, &remote_vector, &remote_hlc)?;
+            } else {
+                db.apply_sync_note_tag(
+                    note_id,
+                    tag_id,
+                    &ex_created_at,
+                    local_modified_at.as_deref(),
+                    local_deleted_at.as_deref(),
+                    &remote_vector,
+                    &remote_hlc,
+                )?;
             }
-
-            // Both have same deleted state - update timestamps
-            db.apply_sync_note_tag(note_id, tag_id, ex_created_at, modified_at, deleted_at)?;
-            Ok(ApplyResult::Applied)
+            Ok(ApplyOutcome::Conflict)
         }
-        _ => Ok(ApplyResult::Skipped),
     }
 }
 
-fn get_full_dataset(db: &Arc<Mutex<Database>>) -> VoiceResult<serde_json::Value> {
-    let db = db.lock().unwrap();
-    let conn = db.connection();
-
-    // Get all notes
-    let mut notes = Vec::new();
-    let mut stmt = conn.prepare(
-        "SELECT id, created_at, content, modified_at, deleted_at FROM notes",
-    )?;
-    let note_rows = stmt.query_map([], |row| {
-        let id_bytes: Vec<u8> = row.get(0)?;
-        let created_at: String = row.get(1)?;
-        let content: String = row.get(2)?;
-        let modified_at: Option<String> = row.get(3)?;
-        let deleted_at: Option<String> = row.get(4)?;
-        Ok((id_bytes, created_at, content, modified_at, deleted_at))
-    })?;
-
-    for row in note_rows {
-        let (id_bytes, created_at, content, modified_at, deleted_at) = row?;
-        let id_hex = crate::validation::uuid_bytes_to_hex(&id_bytes)?;
-        notes.push(serde_json::json!({
-            "id": id_hex,
-            "created_at": created_at,
-            "content": content,
-            "modified_at": modified_at,
-            "deleted_at": deleted_at,
-        }));
-    }
-
-    // Get all tags
-    let mut tags = Vec::new();
-    let mut stmt = conn.prepare(
-        "SELECT id, name, parent_id, created_at, modified_at FROM tags",
-    )?;
-    let tag_rows = stmt.query_map([], |row| {
-        let id_bytes: Vec<u8> = row.get(0)?;
-        let name: String = row.get(1)?;
-        let parent_id_bytes: Option<Vec<u8>> = row.get(2)?;
-        let created_at: Option<String> = row.get(3)?;
-        let modified_at: Option<String> = row.get(4)?;
-        Ok((id_bytes, name, parent_id_bytes, created_at, modified_at))
-    })?;
-
-    for row in tag_rows {
-        let (id_bytes, name, parent_id_bytes, created_at, modified_at) = row?;
-        let id_hex = crate::validation::uuid_bytes_to_hex(&id_bytes)?;
-        let parent_id_hex = parent_id_bytes
-            .map(|b| crate::validation::uuid_bytes_to_hex(&b))
-            .transpose()?;
-        tags.push(serde_json::json!({
-            "id": id_hex,
-            "name": name,
-            "parent_id": parent_id_hex,
-            "created_at": created_at,
-            "modified_at": modified_at,
-        }));
-    }
-
-    // Get all note_tags
-    let mut note_tags = Vec::new();
-    let mut stmt = conn.prepare(
-        "SELECT note_id, tag_id, created_at, modified_at, deleted_at FROM note_tags",
-    )?;
-    let note_tag_rows = stmt.query_map([], |row| {
-        let note_id_bytes: Vec<u8> = row.get(0)?;
-        let tag_id_bytes: Vec<u8> = row.get(1)?;
-        let created_at: String = row.get(2)?;
-        let modified_at: Option<String> = row.get(3)?;
-        let deleted_at: Option<String> = row.get(4)?;
-        Ok((note_id_bytes, tag_id_bytes, created_at, modified_at, deleted_at))
-    })?;
-
-    for row in note_tag_rows {
-        let (note_id_bytes, tag_id_bytes, created_at, modified_at, deleted_at) = row?;
-        let note_id_hex = crate::validation::uuid_bytes_to_hex(&note_id_bytes)?;
-        let tag_id_hex = crate::validation::uuid_bytes_to_hex(&tag_id_bytes)?;
-        note_tags.push(serde_json::json!({
-            "note_id": note_id_hex,
-            "tag_id": tag_id_hex,
-            "created_at": created_at,
-            "modified_at": modified_at,
-            "deleted_at": deleted_at,
-        }));
-    }
-
-    Ok(serde_json::json!({
-        "notes": notes,
-        "tags": tags,
-        "note_tags": note_tags,
-    }))
-}
-
 /// Create the sync server router
 pub fn create_router(
     db: Arc<Mutex<Database>>,
@@ -877,16 +1300,27 @@ pub fn create_router(
         .route("/sync/changes", get(get_changes))
         .route("/sync/apply", post(apply_changes))
         .route("/sync/full", get(get_full_sync))
+        .route("/sync/merkle", get(get_merkle_node))
+        .route("/sync/chunks", post(get_chunks))
+        .route("/sync/batch", post(batch))
+        .route("/sync/conflicts", get(list_conflicts))
+        .route("/sync/conflicts/resolve", post(resolve_conflict))
         .route("/sync/status", get(status))
         .with_state(state)
 }
 
-/// Start the sync server
+/// Start the sync server. Terminates TLS using the self-signed certificate
+/// [`crate::tls::ensure_server_certificate`] maintains, so peers authenticate us via
+/// [`crate::tls::TOFUVerifier`] instead of talking plaintext HTTP.
 pub async fn start_server(
     db: Arc<Mutex<Database>>,
     config: Arc<Mutex<Config>>,
     port: u16,
 ) -> VoiceResult<()> {
+    let tls_config = {
+        let cfg = config.lock().unwrap();
+        crate::tls::build_server_tls_config(&cfg)?
+    };
     let router = create_router(db, config);
 
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
@@ -895,16 +1329,20 @@ pub async fn start_server(
     let (tx, rx) = oneshot::channel::<()>();
     SHUTDOWN_TX.get_or_init(|| Mutex::new(Some(tx)));
 
-    tracing::info!("Starting sync server on {}", addr);
-
-    let listener = tokio::net::TcpListener::bind(addr)
-        .await
-        .map_err(|e| crate::error::VoiceError::Network(e.to_string()))?;
+    tracing::info!("Starting sync server on {} (TLS)", addr);
 
-    axum::serve(listener, router)
-        .with_graceful_shutdown(async {
+    let handle = axum_server::Handle::new();
+    {
+        let handle = handle.clone();
+        tokio::spawn(async move {
             rx.await.ok();
-        })
+            handle.graceful_shutdown(None);
+        });
+    }
+
+    axum_server::bind_rustls(addr, axum_server::tls_rustls::RustlsConfig::from_config(tls_config))
+        .handle(handle)
+        .serve(router.into_make_service())
         .await
         .map_err(|e| crate::error::VoiceError::Network(e.to_string()))?;
 
@@ -926,19 +1364,45 @@ pub fn stop_server() {
 // Python bindings
 // ============================================================================
 
-/// Start sync server (spawns in background)
+/// Start the sync server on a background OS thread with its own Tokio runtime, so the
+/// caller's Python thread is never blocked waiting on socket I/O.
+///
+/// This takes ownership of `db`'s connection (leaving it closed, same as
+/// [`crate::database::PyDatabase::close`]) since the server needs to keep it alive for
+/// as long as it's running; open a fresh `Database` handle if the caller still needs
+/// local access after starting the server.
 #[pyfunction]
 #[pyo3(name = "start_sync_server")]
-#[pyo3(signature = (_db, _config, _port=None))]
+#[pyo3(signature = (db, config, port=None))]
 pub fn py_start_sync_server(
-    _db: &crate::database::PyDatabase,
-    _config: &crate::config::PyConfig,
-    _port: Option<u16>,
+    db: &mut crate::database::PyDatabase,
+    config: &crate::config::PyConfig,
+    port: Option<u16>,
 ) -> PyResult<()> {
-    // TODO: This requires running tokio runtime in background
-    Err(pyo3::exceptions::PyNotImplementedError::new_err(
-        "start_sync_server not yet implemented for Python",
-    ))
+    let db = db.take_inner()?;
+    let config = config.inner_clone();
+    let port = port.unwrap_or_else(|| config.sync_server_port());
+
+    let db = Arc::new(Mutex::new(db));
+    let config = Arc::new(Mutex::new(config));
+
+    std::thread::Builder::new()
+        .name("voice-sync-server".to_string())
+        .spawn(move || {
+            let runtime = match tokio::runtime::Runtime::new() {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    tracing::error!("failed to start sync server runtime: {e}");
+                    return;
+                }
+            };
+            if let Err(e) = runtime.block_on(start_server(db, config, port)) {
+                tracing::error!("sync server stopped with error: {e}");
+            }
+        })
+        .map_err(|e| crate::error::VoiceError::Network(e.to_string()))?;
+
+    Ok(())
 }
 
 /// Stop sync server
@@ -8,15 +8,86 @@
 
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
 
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, CustomExtension, DistinguishedName, DnType,
+    IsCa, KeyUsagePurpose,
+};
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
 use sha2::{Digest, Sha256};
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+use x509_parser::prelude::*;
 
 use crate::config::Config;
 use crate::error::{VoiceError, VoiceResult};
+use crate::trust_graph::{find_trust_path, verify_vouch_signature, TrustEdge, TrustGraph};
 
 /// Certificate validity period (10 years in days)
 pub const CERT_VALIDITY_DAYS: u32 = 3650;
 
+/// Private OID arc under which Voice embeds its device-ID attestation extension.
+/// Analogous to how Android packs attestation data under 1.3.6.1.4.1.11129.2.1.17.
+const DEVICE_ID_EXTENSION_OID: &[u64] = &[1, 3, 6, 1, 4, 1, 59729, 1, 1];
+
+/// DER-encode `bytes` as a primitive OCTET STRING.
+fn der_encode_octet_string(bytes: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x04u8];
+    der_encode_length(bytes.len(), &mut out);
+    out.extend_from_slice(bytes);
+    out
+}
+
+fn der_encode_length(len: usize, out: &mut Vec<u8>) {
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let len_bytes = len.to_be_bytes();
+        let len_bytes: Vec<u8> = len_bytes
+            .iter()
+            .skip_while(|&&b| b == 0)
+            .copied()
+            .collect();
+        out.push(0x80 | len_bytes.len() as u8);
+        out.extend_from_slice(&len_bytes);
+    }
+}
+
+/// Decode a primitive DER OCTET STRING, returning its content bytes.
+fn der_decode_octet_string(der: &[u8]) -> VoiceResult<Vec<u8>> {
+    if der.len() < 2 || der[0] != 0x04 {
+        return Err(VoiceError::Tls("Expected a DER OCTET STRING".to_string()));
+    }
+    let (len, header_len) = der_decode_length(&der[1..])?;
+    let start = 1 + header_len;
+    let end = start + len;
+    if end > der.len() {
+        return Err(VoiceError::Tls("Truncated DER OCTET STRING".to_string()));
+    }
+    Ok(der[start..end].to_vec())
+}
+
+fn der_decode_length(buf: &[u8]) -> VoiceResult<(usize, usize)> {
+    if buf.is_empty() {
+        return Err(VoiceError::Tls("Empty DER length".to_string()));
+    }
+    if buf[0] & 0x80 == 0 {
+        Ok((buf[0] as usize, 1))
+    } else {
+        let num_bytes = (buf[0] & 0x7f) as usize;
+        if buf.len() < 1 + num_bytes {
+            return Err(VoiceError::Tls("Truncated DER length".to_string()));
+        }
+        let mut len = 0usize;
+        for &b in &buf[1..1 + num_bytes] {
+            len = (len << 8) | b as usize;
+        }
+        Ok((len, 1 + num_bytes))
+    }
+}
+
 /// Compute SHA-256 fingerprint from DER-encoded certificate data
 pub fn compute_fingerprint_from_der(der_data: &[u8]) -> String {
     let mut hasher = Sha256::new();
@@ -35,6 +106,22 @@ pub fn compute_fingerprint(cert_path: &Path) -> VoiceResult<String> {
 
 /// Compute SHA-256 fingerprint from PEM certificate data
 pub fn compute_fingerprint_from_pem(pem_data: &[u8]) -> VoiceResult<String> {
+    let der_data = pem_to_der(pem_data)?;
+    Ok(compute_fingerprint_from_der(&der_data))
+}
+
+/// Compute the raw 32-byte SHA-256 fingerprint (not the `SHA256:aa:bb:...` display
+/// form) of a PEM certificate file, e.g. to embed in a compact pairing token.
+pub fn compute_fingerprint_raw(cert_path: &Path) -> VoiceResult<[u8; 32]> {
+    let pem_data = fs::read(cert_path)?;
+    let der_data = pem_to_der(&pem_data)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&der_data);
+    Ok(hasher.finalize().into())
+}
+
+/// Extract the raw DER bytes from a PEM-encoded certificate
+fn pem_to_der(pem_data: &[u8]) -> VoiceResult<Vec<u8>> {
     // Find the base64 content between BEGIN and END markers
     let pem_str = std::str::from_utf8(pem_data)
         .map_err(|e| VoiceError::Tls(format!("Invalid PEM encoding: {}", e)))?;
@@ -57,11 +144,22 @@ pub fn compute_fingerprint_from_pem(pem_data: &[u8]) -> VoiceResult<String> {
 
     // Decode base64 to get DER
     use base64::Engine;
-    let der_data = base64::engine::general_purpose::STANDARD
+    base64::engine::general_purpose::STANDARD
         .decode(&base64_content)
-        .map_err(|e| VoiceError::Tls(format!("Invalid base64 in PEM: {}", e)))?;
+        .map_err(|e| VoiceError::Tls(format!("Invalid base64 in PEM: {}", e)))
+}
 
-    Ok(compute_fingerprint_from_der(&der_data))
+/// Wrap raw DER certificate bytes in PEM armor, the inverse of [`pem_to_der`].
+fn der_to_pem(der: &[u8]) -> String {
+    use base64::Engine;
+    let b64 = base64::engine::general_purpose::STANDARD.encode(der);
+    let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+    for chunk in b64.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(chunk).expect("base64 output is always ASCII"));
+        pem.push('\n');
+    }
+    pem.push_str("-----END CERTIFICATE-----\n");
+    pem
 }
 
 /// Verify that a certificate matches an expected fingerprint
@@ -70,105 +168,545 @@ pub fn verify_fingerprint(cert_path: &Path, expected_fingerprint: &str) -> Voice
     Ok(actual_fingerprint.to_lowercase() == expected_fingerprint.to_lowercase())
 }
 
+/// Locally pinned roots and known voucher certificates, used to let trust
+/// propagate to previously-unseen peers via [`crate::trust_graph`].
+pub struct WebOfTrustContext<'a> {
+    pub graph: &'a TrustGraph,
+    pub roots: &'a [Uuid],
+    pub voucher_certs: &'a std::collections::HashMap<Uuid, Vec<u8>>,
+    pub max_depth: usize,
+}
+
 /// Trust On First Use certificate verifier
 pub struct TOFUVerifier<'a> {
     config: &'a Config,
+    web_of_trust: Option<WebOfTrustContext<'a>>,
 }
 
 impl<'a> TOFUVerifier<'a> {
     pub fn new(config: &'a Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            web_of_trust: None,
+        }
+    }
+
+    /// Opt in to accepting previously-unseen peers that have a valid vouch chain
+    /// from one of `context.roots`.
+    pub fn with_web_of_trust(mut self, context: WebOfTrustContext<'a>) -> Self {
+        self.web_of_trust = Some(context);
+        self
     }
 
     /// Verify a peer's certificate using TOFU
     ///
+    /// If `expected_device_id` is provided, the certificate must carry a matching
+    /// device-ID attestation extension before the fingerprint is even considered,
+    /// so a rotated/stolen fingerprint can't silently swap identities.
+    ///
     /// Returns (is_trusted, fingerprint, error_message)
-    pub fn verify_peer(&self, peer_id: &str, peer_cert_pem: &[u8]) -> (bool, String, Option<String>) {
+    pub fn verify_peer(
+        &self,
+        peer_id: &str,
+        peer_cert_pem: &[u8],
+        expected_device_id: Option<&str>,
+    ) -> (bool, String, Option<String>) {
         let actual_fingerprint = match compute_fingerprint_from_pem(peer_cert_pem) {
             Ok(fp) => fp,
             Err(e) => return (false, String::new(), Some(format!("Failed to compute fingerprint: {}", e))),
         };
 
+        let der = match pem_to_der(peer_cert_pem) {
+            Ok(d) => d,
+            Err(e) => return (false, actual_fingerprint, Some(e.to_string())),
+        };
+
+        match parse_certificate_from_der(&der) {
+            Ok(info) => {
+                if !info.is_valid_at(chrono::Utc::now()) {
+                    return (
+                        false,
+                        actual_fingerprint,
+                        Some("certificate expired/not yet valid".to_string()),
+                    );
+                }
+            }
+            Err(e) => return (false, actual_fingerprint, Some(e.to_string())),
+        }
+
+        if let Some(expected) = expected_device_id {
+            match extract_device_id(&der) {
+                Ok(Some(found)) => {
+                    let expected_uuid = match crate::validation::validate_device_id(expected) {
+                        Ok(u) => u,
+                        Err(e) => return (false, actual_fingerprint, Some(e.to_string())),
+                    };
+                    if found != expected_uuid {
+                        return (
+                            false,
+                            actual_fingerprint,
+                            Some(format!(
+                                "Certificate device-id mismatch! Expected: {}, Got: {}. \
+                                 The peer's identity does not match its pinned device.",
+                                expected_uuid.simple(),
+                                found.simple()
+                            )),
+                        );
+                    }
+                }
+                Ok(None) => {
+                    return (
+                        false,
+                        actual_fingerprint,
+                        Some("Certificate is missing the device-id attestation extension".to_string()),
+                    )
+                }
+                Err(e) => return (false, actual_fingerprint, Some(e.to_string())),
+            }
+        }
+
         // Get stored fingerprint for this peer
         let peer = match self.config.get_peer(peer_id) {
             Some(p) => p,
-            None => return (false, actual_fingerprint, Some("Unknown peer".to_string())),
+            None => {
+                if let Some(ctx) = &self.web_of_trust {
+                    let candidate_device_id = match expected_device_id {
+                        Some(d) => crate::validation::validate_device_id(d).ok(),
+                        None => extract_device_id(&der).ok().flatten(),
+                    };
+                    if let Some(candidate_id) = candidate_device_id {
+                        if let Some(trust_path) = find_trust_path(
+                            ctx.graph,
+                            ctx.roots,
+                            candidate_id,
+                            &actual_fingerprint,
+                            ctx.voucher_certs,
+                            ctx.max_depth,
+                        ) {
+                            return (
+                                true,
+                                actual_fingerprint,
+                                Some(format!(
+                                    "Trusted via vouch chain rooted at device {}",
+                                    trust_path.root.simple()
+                                )),
+                            );
+                        }
+                    }
+                }
+                return (false, actual_fingerprint, Some("Unknown peer".to_string()));
+            }
         };
 
-        let stored_fingerprint = &peer.certificate_fingerprint;
+        let trusted_fingerprints = &peer.certificate_fingerprints;
 
-        match stored_fingerprint {
-            None => {
+        match trusted_fingerprints.is_empty() {
+            true => {
                 // First connection - TOFU: trust the fingerprint
                 // Note: The caller should update the config to store the fingerprint
                 (true, actual_fingerprint, None)
             }
-            Some(stored) => {
-                // Verify fingerprint matches
-                if actual_fingerprint.to_lowercase() == stored.to_lowercase() {
+            false => {
+                // Accept any fingerprint in the peer's trusted set, so a peer that
+                // legitimately rotated its certificate (see `rotate_peer_fingerprint`)
+                // doesn't trip a hard MITM failure.
+                if trusted_fingerprints
+                    .iter()
+                    .any(|fp| fp.eq_ignore_ascii_case(&actual_fingerprint))
+                {
                     (true, actual_fingerprint, None)
                 } else {
                     (
                         false,
                         actual_fingerprint.clone(),
                         Some(format!(
-                            "Certificate fingerprint mismatch! Expected: {}, Got: {}. \
-                             This could indicate a man-in-the-middle attack or \
-                             the peer regenerated their certificate.",
-                            stored, actual_fingerprint
+                            "Certificate fingerprint mismatch! Expected one of: {}, Got: {}. \
+                             This could indicate a man-in-the-middle attack. If the peer \
+                             legitimately regenerated its certificate, rotate it via \
+                             rotate_peer_fingerprint instead of re-trusting blindly.",
+                            trusted_fingerprints.join(", "), actual_fingerprint
                         )),
                     )
                 }
             }
         }
     }
+
+    /// Same as [`Self::verify_peer`], but takes a raw DER certificate - e.g. the certificate
+    /// a `rustls` verifier sees during a live handshake - instead of PEM.
+    pub fn verify_peer_der(
+        &self,
+        peer_id: &str,
+        peer_cert_der: &[u8],
+        expected_device_id: Option<&str>,
+    ) -> (bool, String, Option<String>) {
+        self.verify_peer(peer_id, der_to_pem(peer_cert_der).as_bytes(), expected_device_id)
+    }
+}
+
+/// Adapts [`TOFUVerifier`] to `rustls`'s certificate verification trait, so
+/// [`crate::sync_client`] can terminate TLS to a peer and reject anything that doesn't match
+/// the peer's pinned fingerprint (or a valid web-of-trust vouch) instead of falling back to
+/// the system's CA roots, which self-signed Voice certificates never chain to. Holds an owned
+/// [`Config`] rather than borrowing one, since `rustls::ClientConfig` requires its verifier to
+/// be `'static`.
+struct TofuRustlsVerifier {
+    config: Config,
+    peer_id: String,
+    expected_device_id: Option<String>,
+}
+
+impl ServerCertVerifier for TofuRustlsVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verifier = TOFUVerifier::new(&self.config);
+        let (trusted, _fingerprint, reason) =
+            verifier.verify_peer_der(&self.peer_id, &end_entity.0, self.expected_device_id.as_deref());
+        if trusted {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::General(
+                reason.unwrap_or_else(|| format!("certificate for peer {} is not trusted", self.peer_id)),
+            ))
+        }
+    }
+}
+
+/// Build a `rustls` client config that terminates TLS to `peer_id` and verifies its
+/// certificate via TOFU instead of the system's CA roots. `expected_device_id`, if known,
+/// additionally pins the peer's device-id attestation extension (see [`extract_device_id`]).
+pub fn build_peer_tls_config(config: &Config, peer_id: &str, expected_device_id: Option<&str>) -> Arc<rustls::ClientConfig> {
+    let verifier = TofuRustlsVerifier {
+        config: config.clone(),
+        peer_id: peer_id.to_string(),
+        expected_device_id: expected_device_id.map(|s| s.to_string()),
+    };
+    Arc::new(
+        rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(verifier))
+            .with_no_client_auth(),
+    )
+}
+
+/// Records whatever certificate a discovery connection actually presented, so the caller can
+/// pin its fingerprint for future connections instead of trusting it forever sight unseen.
+#[derive(Debug, Default, Clone)]
+pub struct DiscoveredFingerprint(std::sync::Arc<std::sync::Mutex<Option<String>>>);
+
+impl DiscoveredFingerprint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The fingerprint of the certificate seen on the connection this was built for, if the
+    /// handshake completed.
+    pub fn get(&self) -> Option<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Accepts any certificate, recording its fingerprint into `seen` rather than checking it
+/// against anything - used only for [`crate::sync_client::connect_and_sync`]'s first contact
+/// with a peer reachable by URL alone, where (unlike [`build_peer_tls_config`]) there is no
+/// `peer_id` yet to look up a pinned fingerprint under. The connection is still encrypted; it
+/// just isn't authenticated until the caller pins the recorded fingerprint via
+/// [`crate::config::Config::add_peer`] and switches to [`build_peer_tls_config`] for every
+/// sync after this one - the same "trust, then remember" shape [`TOFUVerifier`] uses elsewhere.
+struct DiscoveryVerifier {
+    seen: DiscoveredFingerprint,
+}
+
+impl ServerCertVerifier for DiscoveryVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        *self.seen.0.lock().unwrap() = Some(compute_fingerprint_from_der(&end_entity.0));
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// Build a `rustls` client config for an unauthenticated discovery connection (see
+/// [`DiscoveryVerifier`]), returning the config alongside a handle that fills in with the
+/// fingerprint of whatever certificate the peer actually presented once the handshake completes.
+pub fn build_discovery_tls_config() -> (Arc<rustls::ClientConfig>, DiscoveredFingerprint) {
+    let seen = DiscoveredFingerprint::new();
+    let verifier = DiscoveryVerifier { seen: seen.clone() };
+    let config = Arc::new(
+        rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(verifier))
+            .with_no_client_auth(),
+    );
+    (config, seen)
+}
+
+/// Build a `rustls` server config terminating TLS with the self-signed certificate from
+/// [`ensure_server_certificate`]. Verifying the *client's* identity is left to application-level
+/// signing (see [`crate::config::Config::sign`]/`verify_peer`, wired into `/sync/handshake` and
+/// `/sync/apply`) rather than mutual TLS, so a peer we haven't enrolled a public key for yet can
+/// still make first contact - the same TOFU-first philosophy as [`TOFUVerifier`].
+pub fn build_server_tls_config(config: &Config) -> VoiceResult<Arc<rustls::ServerConfig>> {
+    let (cert_path, key_path, _fingerprint, _not_after) = ensure_server_certificate(config, false)?;
+    let cert_pem = fs::read(&cert_path)?;
+    let key_pem = fs::read(&key_path)?;
+
+    let certs = rustls_pemfile::certs(&mut cert_pem.as_slice())
+        .map_err(|e| VoiceError::Tls(format!("failed to parse server certificate: {e}")))?
+        .into_iter()
+        .map(rustls::Certificate)
+        .collect();
+    let mut keys = rustls_pemfile::pkcs8_private_keys(&mut key_pem.as_slice())
+        .map_err(|e| VoiceError::Tls(format!("failed to parse server private key: {e}")))?;
+    let key = keys
+        .pop()
+        .map(rustls::PrivateKey)
+        .ok_or_else(|| VoiceError::Tls("server key file contains no PKCS#8 private key".to_string()))?;
+
+    rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| VoiceError::Tls(format!("invalid server certificate/key pair: {e}")))
+        .map(Arc::new)
+}
+
+/// Accept a peer's certificate rotation and add its new fingerprint to the trusted set.
+///
+/// `attestation` must be a [`TrustEdge`] that self-vouches (`voucher_device_id ==
+/// target_device_id == peer_device_id`) for `target_fingerprint`, signed by the key
+/// behind `old_cert_der` — i.e. produced by calling
+/// `trust_graph::sign_vouch(old_key_pem, peer_device_id, peer_device_id, new_fingerprint, validity_days)`
+/// with the peer's *previous* private key. `old_cert_der` must already be one of the
+/// peer's trusted fingerprints. An unsigned new fingerprint (no attestation, or one
+/// signed by an untrusted key) is rejected loudly rather than silently re-TOFU'd.
+pub fn rotate_peer_fingerprint(
+    config: &mut Config,
+    peer_id: &str,
+    peer_device_id: Uuid,
+    old_cert_der: &[u8],
+    attestation: &TrustEdge,
+) -> VoiceResult<()> {
+    let old_fingerprint = compute_fingerprint_from_der(old_cert_der);
+
+    let already_trusted = config
+        .peer_fingerprints(peer_id)
+        .map(|fps| fps.iter().any(|fp| fp.eq_ignore_ascii_case(&old_fingerprint)))
+        .unwrap_or(false);
+    if !already_trusted {
+        return Err(VoiceError::Tls(
+            "Rotation must be signed by a certificate already in the peer's trusted set".to_string(),
+        ));
+    }
+
+    if attestation.voucher_device_id != peer_device_id || attestation.target_device_id != peer_device_id {
+        return Err(VoiceError::Tls(
+            "Rotation attestation must self-vouch for the rotating peer's own device id".to_string(),
+        ));
+    }
+
+    if !attestation.is_valid_at(chrono::Utc::now()) {
+        return Err(VoiceError::Tls("Rotation attestation has expired or was revoked".to_string()));
+    }
+
+    if !verify_vouch_signature(attestation, old_cert_der)? {
+        return Err(VoiceError::Tls("Rotation attestation signature is invalid".to_string()));
+    }
+
+    config.add_peer_fingerprint(peer_id, &attestation.target_fingerprint)?;
+    Ok(())
+}
+
+/// Parsed fields of interest from an X.509 certificate's TBSCertificate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateInfo {
+    /// Certificate serial number, as a hex string
+    pub serial: String,
+    /// Subject common name, if present
+    pub subject_cn: Option<String>,
+    /// Start of the validity window
+    pub not_before: chrono::DateTime<chrono::Utc>,
+    /// End of the validity window
+    pub not_after: chrono::DateTime<chrono::Utc>,
+}
+
+impl CertificateInfo {
+    /// Whether `now` falls within [not_before, not_after]
+    pub fn is_valid_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        now >= self.not_before && now <= self.not_after
+    }
+}
+
+fn asn1_time_to_chrono(t: &x509_parser::time::ASN1Time) -> VoiceResult<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::from_timestamp(t.timestamp(), 0)
+        .ok_or_else(|| VoiceError::Tls("Certificate timestamp out of range".to_string()))
+}
+
+/// Parse a PEM-encoded certificate and extract validity/serial/subject info.
+pub fn parse_certificate_from_pem(pem_data: &[u8]) -> VoiceResult<CertificateInfo> {
+    let der = pem_to_der(pem_data)?;
+    parse_certificate_from_der(&der)
+}
+
+/// Parse a DER-encoded certificate and extract validity/serial/subject info.
+pub fn parse_certificate_from_der(der_cert: &[u8]) -> VoiceResult<CertificateInfo> {
+    let (_, cert) = X509Certificate::from_der(der_cert)
+        .map_err(|e| VoiceError::Tls(format!("Failed to parse certificate: {}", e)))?;
+
+    let subject_cn = cert
+        .subject()
+        .iter_common_name()
+        .next()
+        .and_then(|cn| cn.as_str().ok())
+        .map(|s| s.to_string());
+
+    Ok(CertificateInfo {
+        serial: cert.raw_serial_as_string(),
+        subject_cn,
+        not_before: asn1_time_to_chrono(&cert.validity().not_before)?,
+        not_after: asn1_time_to_chrono(&cert.validity().not_after)?,
+    })
+}
+
+/// Walk a peer's DER-encoded certificate and extract the embedded device-id
+/// attestation extension, if present.
+pub fn extract_device_id(der_cert: &[u8]) -> VoiceResult<Option<Uuid>> {
+    let (_, cert) = X509Certificate::from_der(der_cert)
+        .map_err(|e| VoiceError::Tls(format!("Failed to parse certificate: {}", e)))?;
+
+    let oid = x509_parser::der_parser::oid::Oid::from(DEVICE_ID_EXTENSION_OID)
+        .map_err(|_| VoiceError::Tls("Invalid device-id extension OID".to_string()))?;
+
+    for ext in cert.extensions() {
+        if ext.oid == oid {
+            let bytes = der_decode_octet_string(ext.value)?;
+            let uuid = Uuid::from_slice(&bytes)
+                .map_err(|e| VoiceError::Tls(format!("Invalid device-id extension: {}", e)))?;
+            return Ok(Some(uuid));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Build the `rcgen::CertificateParams` for a self-signed Voice sync leaf certificate.
+fn self_signed_params(common_name: &str) -> VoiceResult<CertificateParams> {
+    let mut params = CertificateParams::new(vec![common_name.to_string()]);
+
+    let mut dn = DistinguishedName::new();
+    dn.push(DnType::CommonName, common_name);
+    dn.push(DnType::OrganizationName, "Voice");
+    params.distinguished_name = dn;
+
+    let not_before = OffsetDateTime::now_utc();
+    let not_after = not_before + Duration::days(CERT_VALIDITY_DAYS as i64);
+    params.not_before = not_before;
+    params.not_after = not_after;
+
+    // Self-signed leaf: acts as its own CA so SubjectKeyIdentifier == AuthorityKeyIdentifier.
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.key_usages = vec![
+        KeyUsagePurpose::DigitalSignature,
+        KeyUsagePurpose::KeyCertSign,
+    ];
+
+    Ok(params)
 }
 
 /// Generate a self-signed certificate
 ///
-/// This is a placeholder - actual certificate generation would require
-/// the `rcgen` or `openssl` crate. For now, we assume certificates
-/// are generated externally or by the Python code during transition.
+/// Generates a fresh key pair and a self-signed leaf certificate for `common_name`,
+/// PEM-encodes both, and writes them to `cert_path`/`key_path`.
+///
+/// Returns (cert_path, key_path, fingerprint).
 pub fn generate_self_signed_cert(
-    _cert_path: &Path,
-    _key_path: &Path,
-    _common_name: &str,
-    _device_id: Option<&str>,
-) -> VoiceResult<(String, String)> {
-    Err(VoiceError::Tls(
-        "Certificate generation not yet implemented in Rust. \
-         Please generate certificates using the Python version or openssl."
-            .to_string(),
+    cert_path: &Path,
+    key_path: &Path,
+    common_name: &str,
+    device_id: Option<&str>,
+) -> VoiceResult<(String, String, String)> {
+    let mut params = self_signed_params(common_name)?;
+
+    if let Some(device_id) = device_id {
+        let uuid = crate::validation::validate_device_id(device_id)?;
+        let extn_value = der_encode_octet_string(uuid.as_bytes());
+        let mut ext = CustomExtension::from_oid_content(DEVICE_ID_EXTENSION_OID, extn_value);
+        ext.set_criticality(false);
+        params.custom_extensions.push(ext);
+    }
+
+    let cert = Certificate::from_params(params)
+        .map_err(|e| VoiceError::Tls(format!("Failed to generate certificate: {}", e)))?;
+
+    let cert_pem = cert
+        .serialize_pem()
+        .map_err(|e| VoiceError::Tls(format!("Failed to serialize certificate: {}", e)))?;
+    let key_pem = cert.serialize_private_key_pem();
+    let der = cert
+        .serialize_der()
+        .map_err(|e| VoiceError::Tls(format!("Failed to serialize certificate DER: {}", e)))?;
+
+    if let Some(parent) = cert_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(cert_path, &cert_pem)?;
+    fs::write(key_path, &key_pem)?;
+
+    let fingerprint = compute_fingerprint_from_der(&der);
+
+    Ok((
+        cert_path.display().to_string(),
+        key_path.display().to_string(),
+        fingerprint,
     ))
 }
 
 /// Ensure server certificate exists
 ///
-/// Returns (cert_path, key_path, fingerprint)
+/// Returns (cert_path, key_path, fingerprint, not_after) so callers can proactively
+/// regenerate before expiry rather than discovering a dead cert at connect time.
 pub fn ensure_server_certificate(
     config: &Config,
     force_regenerate: bool,
-) -> VoiceResult<(std::path::PathBuf, std::path::PathBuf, String)> {
+) -> VoiceResult<(
+    std::path::PathBuf,
+    std::path::PathBuf,
+    String,
+    chrono::DateTime<chrono::Utc>,
+)> {
     let certs_dir = config.certs_dir()?;
     let cert_path = certs_dir.join("server.crt");
     let key_path = certs_dir.join("server.key");
 
     if force_regenerate || !cert_path.exists() || !key_path.exists() {
-        // For now, return an error - certificate generation will be added later
-        return Err(VoiceError::Tls(format!(
-            "Server certificate not found at {}. \
-             Please generate certificates using: \
-             openssl req -x509 -newkey rsa:2048 -keyout {} -out {} -days 3650 -nodes",
-            cert_path.display(),
-            key_path.display(),
-            cert_path.display()
-        )));
-    }
-
-    // Compute fingerprint of existing certificate
+        let (_, _, fingerprint) = generate_self_signed_cert(
+            &cert_path,
+            &key_path,
+            config.device_name(),
+            Some(config.device_id_hex()),
+        )?;
+        let info = parse_certificate_from_pem(&fs::read(&cert_path)?)?;
+        return Ok((cert_path, key_path, fingerprint, info.not_after));
+    }
+
+    // Compute fingerprint and validity of existing certificate
     let fingerprint = compute_fingerprint(&cert_path)?;
+    let info = parse_certificate_from_pem(&fs::read(&cert_path)?)?;
 
-    Ok((cert_path, key_path, fingerprint))
+    Ok((cert_path, key_path, fingerprint, info.not_after))
 }
 
 #[cfg(test)]
@@ -204,4 +742,304 @@ FoAUvCgqF3jqPmqTEYCTiEzxJqG6hwowDwYDVR0TAQH/BAUwAwEB/zANBgkqhkiG
         let fingerprint = compute_fingerprint_from_der(&der_data);
         assert!(fingerprint.starts_with("SHA256:"));
     }
+
+    #[test]
+    fn test_generate_self_signed_cert() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cert_path = temp_dir.path().join("test.crt");
+        let key_path = temp_dir.path().join("test.key");
+
+        let (cert_str, key_str, fingerprint) =
+            generate_self_signed_cert(&cert_path, &key_path, "voice-test", None).unwrap();
+
+        assert_eq!(cert_str, cert_path.display().to_string());
+        assert_eq!(key_str, key_path.display().to_string());
+        assert!(cert_path.exists());
+        assert!(key_path.exists());
+        assert!(fingerprint.starts_with("SHA256:"));
+
+        // The fingerprint should match what compute_fingerprint derives independently.
+        let recomputed = compute_fingerprint(&cert_path).unwrap();
+        assert_eq!(fingerprint, recomputed);
+    }
+
+    #[test]
+    fn test_device_id_extension_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cert_path = temp_dir.path().join("test.crt");
+        let key_path = temp_dir.path().join("test.key");
+        let device_id = Uuid::now_v7();
+
+        generate_self_signed_cert(
+            &cert_path,
+            &key_path,
+            "voice-test",
+            Some(&device_id.simple().to_string()),
+        )
+        .unwrap();
+
+        let pem = fs::read(&cert_path).unwrap();
+        let der = pem_to_der(&pem).unwrap();
+        let extracted = extract_device_id(&der).unwrap();
+        assert_eq!(extracted, Some(device_id));
+    }
+
+    #[test]
+    fn test_verify_peer_device_id_mismatch() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+        let peer_id = "0".repeat(32);
+        config
+            .add_peer(&peer_id, "Test Peer", "https://example.com:8384", None, false)
+            .unwrap();
+
+        let cert_path = temp_dir.path().join("peer.crt");
+        let key_path = temp_dir.path().join("peer.key");
+        let actual_device_id = Uuid::now_v7();
+        generate_self_signed_cert(
+            &cert_path,
+            &key_path,
+            "voice-peer",
+            Some(&actual_device_id.simple().to_string()),
+        )
+        .unwrap();
+        let peer_cert_pem = fs::read(&cert_path).unwrap();
+
+        let verifier = TOFUVerifier::new(&config);
+        let expected_device_id = Uuid::now_v7();
+        let (trusted, _fp, err) = verifier.verify_peer(
+            &peer_id,
+            &peer_cert_pem,
+            Some(&expected_device_id.simple().to_string()),
+        );
+
+        assert!(!trusted);
+        assert!(err.unwrap().contains("device-id mismatch"));
+    }
+
+    #[test]
+    fn test_verify_peer_accepts_unknown_peer_via_web_of_trust() {
+        use crate::trust_graph::{sign_vouch, TrustGraph};
+        use std::collections::HashMap;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        // Root: a device already trusted via direct TOFU elsewhere.
+        let root_id = Uuid::now_v7();
+        let root_cert_path = temp_dir.path().join("root.crt");
+        let root_key_path = temp_dir.path().join("root.key");
+        generate_self_signed_cert(&root_cert_path, &root_key_path, "voice-root", Some(&root_id.simple().to_string()))
+            .unwrap();
+        let root_der = pem_to_der(&fs::read(&root_cert_path).unwrap()).unwrap();
+        let root_key_pem = fs::read_to_string(&root_key_path).unwrap();
+
+        // Candidate: a peer this device has never seen before, but the root vouches for.
+        let candidate_id = Uuid::now_v7();
+        let candidate_cert_path = temp_dir.path().join("candidate.crt");
+        let candidate_key_path = temp_dir.path().join("candidate.key");
+        generate_self_signed_cert(
+            &candidate_cert_path,
+            &candidate_key_path,
+            "voice-candidate",
+            Some(&candidate_id.simple().to_string()),
+        )
+        .unwrap();
+        let candidate_cert_pem = fs::read(&candidate_cert_path).unwrap();
+        let candidate_fingerprint = compute_fingerprint_from_pem(&candidate_cert_pem).unwrap();
+
+        let mut graph = TrustGraph::new();
+        graph.add_edge(sign_vouch(&root_key_pem, root_id, candidate_id, &candidate_fingerprint, 30).unwrap());
+
+        let mut voucher_certs = HashMap::new();
+        voucher_certs.insert(root_id, root_der);
+        let roots = vec![root_id];
+
+        let verifier = TOFUVerifier::new(&config).with_web_of_trust(WebOfTrustContext {
+            graph: &graph,
+            roots: &roots,
+            voucher_certs: &voucher_certs,
+            max_depth: 3,
+        });
+
+        let unknown_peer_id = "f".repeat(32);
+        let (trusted, _fp, note) = verifier.verify_peer(
+            &unknown_peer_id,
+            &candidate_cert_pem,
+            Some(&candidate_id.simple().to_string()),
+        );
+
+        assert!(trusted);
+        assert!(note.unwrap().contains(&root_id.simple().to_string()));
+    }
+
+    #[test]
+    fn test_rotate_peer_fingerprint_accepts_signed_rotation() {
+        use crate::trust_graph::sign_vouch;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+        let peer_id = "0".repeat(32);
+        let device_id = Uuid::now_v7();
+
+        let old_cert_path = temp_dir.path().join("old.crt");
+        let old_key_path = temp_dir.path().join("old.key");
+        let (_, _, old_fingerprint) = generate_self_signed_cert(
+            &old_cert_path,
+            &old_key_path,
+            "voice-peer",
+            Some(&device_id.simple().to_string()),
+        )
+        .unwrap();
+        let old_der = pem_to_der(&fs::read(&old_cert_path).unwrap()).unwrap();
+        let old_key_pem = fs::read_to_string(&old_key_path).unwrap();
+
+        config
+            .add_peer(&peer_id, "Test Peer", "https://example.com:8384", Some(&old_fingerprint), false)
+            .unwrap();
+
+        let new_cert_path = temp_dir.path().join("new.crt");
+        let new_key_path = temp_dir.path().join("new.key");
+        let (_, _, new_fingerprint) = generate_self_signed_cert(
+            &new_cert_path,
+            &new_key_path,
+            "voice-peer",
+            Some(&device_id.simple().to_string()),
+        )
+        .unwrap();
+
+        let attestation = sign_vouch(&old_key_pem, device_id, device_id, &new_fingerprint, 30).unwrap();
+
+        rotate_peer_fingerprint(&mut config, &peer_id, device_id, &old_der, &attestation).unwrap();
+
+        let fingerprints = config.peer_fingerprints(&peer_id).unwrap();
+        assert!(fingerprints.iter().any(|fp| fp.eq_ignore_ascii_case(&old_fingerprint)));
+        assert!(fingerprints.iter().any(|fp| fp.eq_ignore_ascii_case(&new_fingerprint)));
+    }
+
+    #[test]
+    fn test_rotate_peer_fingerprint_rejects_untrusted_old_cert() {
+        use crate::trust_graph::sign_vouch;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+        let peer_id = "0".repeat(32);
+        let device_id = Uuid::now_v7();
+
+        config
+            .add_peer(&peer_id, "Test Peer", "https://example.com:8384", Some("SHA256:aa"), false)
+            .unwrap();
+
+        let untrusted_cert_path = temp_dir.path().join("untrusted.crt");
+        let untrusted_key_path = temp_dir.path().join("untrusted.key");
+        let (_, _, new_fingerprint) = generate_self_signed_cert(
+            &untrusted_cert_path,
+            &untrusted_key_path,
+            "voice-peer",
+            Some(&device_id.simple().to_string()),
+        )
+        .unwrap();
+        let untrusted_der = pem_to_der(&fs::read(&untrusted_cert_path).unwrap()).unwrap();
+        let untrusted_key_pem = fs::read_to_string(&untrusted_key_path).unwrap();
+
+        let attestation =
+            sign_vouch(&untrusted_key_pem, device_id, device_id, &new_fingerprint, 30).unwrap();
+
+        let result = rotate_peer_fingerprint(&mut config, &peer_id, device_id, &untrusted_der, &attestation);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_ensure_server_certificate_generates_when_missing() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        let (cert_path, key_path, fingerprint, not_after) =
+            ensure_server_certificate(&config, false).unwrap();
+
+        assert!(cert_path.exists());
+        assert!(key_path.exists());
+        assert!(fingerprint.starts_with("SHA256:"));
+        assert!(not_after > chrono::Utc::now());
+    }
+
+    #[test]
+    fn test_parse_certificate_from_pem() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cert_path = temp_dir.path().join("test.crt");
+        let key_path = temp_dir.path().join("test.key");
+
+        generate_self_signed_cert(&cert_path, &key_path, "voice-test", None).unwrap();
+        let pem = fs::read(&cert_path).unwrap();
+
+        let info = parse_certificate_from_pem(&pem).unwrap();
+        assert_eq!(info.subject_cn.as_deref(), Some("voice-test"));
+        assert!(info.is_valid_at(chrono::Utc::now()));
+        assert!(info.not_after > info.not_before);
+    }
+
+    #[test]
+    fn test_build_server_tls_config_uses_generated_certificate() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+
+        // Builds successfully from the certificate/key `ensure_server_certificate` generates
+        // on first use.
+        assert!(build_server_tls_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_rustls_verifier_matches_tofu_verify_peer() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::new(Some(temp_dir.path().to_path_buf())).unwrap();
+        let peer_id = "0".repeat(32);
+
+        let cert_path = temp_dir.path().join("peer.crt");
+        let key_path = temp_dir.path().join("peer.key");
+        let (_, _, fingerprint) = generate_self_signed_cert(&cert_path, &key_path, "voice-peer", None).unwrap();
+        let der = pem_to_der(&fs::read(&cert_path).unwrap()).unwrap();
+
+        config
+            .add_peer(&peer_id, "Test Peer", "https://example.com:8384", None, false)
+            .unwrap();
+
+        let verifier = TofuRustlsVerifier {
+            config: config.clone(),
+            peer_id: peer_id.clone(),
+            expected_device_id: None,
+        };
+        let now = SystemTime::now();
+        // First contact with no pinned fingerprint yet - TOFU accepts it, same as
+        // `TOFUVerifier::verify_peer` would.
+        assert!(verifier
+            .verify_server_cert(
+                &rustls::Certificate(der.clone()),
+                &[],
+                &rustls::ServerName::try_from("voice-peer").unwrap(),
+                &mut std::iter::empty(),
+                &[],
+                now,
+            )
+            .is_ok());
+
+        config.add_peer_fingerprint(&peer_id, "SHA256:does-not-match").unwrap();
+        let verifier = TofuRustlsVerifier {
+            config,
+            peer_id,
+            expected_device_id: None,
+        };
+        // Now the peer has a different pinned fingerprint - the actual cert must be rejected
+        // instead of silently re-trusted.
+        assert!(verifier
+            .verify_server_cert(
+                &rustls::Certificate(der),
+                &[],
+                &rustls::ServerName::try_from("voice-peer").unwrap(),
+                &mut std::iter::empty(),
+                &[],
+                now,
+            )
+            .is_err());
+        let _ = fingerprint;
+    }
 }
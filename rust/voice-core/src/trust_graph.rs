@@ -0,0 +1,322 @@
+//! Peer web-of-trust layer built on top of single-peer TOFU ([`crate::tls::TOFUVerifier`]).
+//!
+//! TOFU only knows fingerprints a device has personally pinned, so adding a new
+//! device to an existing mesh means re-pinning every other peer by hand. This
+//! module lets an already-trusted peer *vouch* for another peer's
+//! `(device_id, fingerprint)` pair: a [`TrustEdge`] is an attestation, signed by
+//! the voucher's own certificate key, that a target device legitimately holds a
+//! given fingerprint. [`find_trust_path`] runs a bounded breadth-first search
+//! from a device's locally pinned roots and accepts a previously-unseen peer if
+//! a valid, unexpired, unrevoked chain of vouches reaches it. TOFU itself stays
+//! the trust anchor; this only lets that trust propagate across a user's own
+//! devices.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Utc};
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, KeyPair, UnparsedPublicKey, ECDSA_P256_SHA256_FIXED, ECDSA_P256_SHA256_FIXED_SIGNING};
+use uuid::Uuid;
+use x509_parser::prelude::*;
+
+use crate::error::{VoiceError, VoiceResult};
+
+/// A signed attestation that `voucher_device_id` vouches for `target_device_id`
+/// legitimately holding `target_fingerprint`.
+#[derive(Debug, Clone)]
+pub struct TrustEdge {
+    pub voucher_device_id: Uuid,
+    pub target_device_id: Uuid,
+    pub target_fingerprint: String,
+    pub expires_at: DateTime<Utc>,
+    pub signature: Vec<u8>,
+    pub revoked: bool,
+}
+
+impl TrustEdge {
+    /// Bytes signed by the voucher: binds voucher, target, fingerprint and expiry together
+    /// so none of them can be swapped out without invalidating the signature.
+    fn canonical_message(
+        voucher_device_id: Uuid,
+        target_device_id: Uuid,
+        target_fingerprint: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Vec<u8> {
+        format!(
+            "{}:{}:{}:{}",
+            voucher_device_id.simple(),
+            target_device_id.simple(),
+            target_fingerprint,
+            expires_at.timestamp()
+        )
+        .into_bytes()
+    }
+
+    /// Whether this edge is currently usable: not revoked and not expired.
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        !self.revoked && now <= self.expires_at
+    }
+}
+
+/// Extract the raw DER bytes from a PEM-encoded document (certificate or private key),
+/// regardless of its PEM label.
+fn pem_to_der_generic(pem_data: &str) -> VoiceResult<Vec<u8>> {
+    let start = pem_data
+        .find("-----BEGIN")
+        .ok_or_else(|| VoiceError::Tls("No PEM block found".to_string()))?;
+    let header_end = pem_data[start..]
+        .find("-----\n")
+        .or_else(|| pem_data[start..].find("-----\r\n"))
+        .map(|i| start + i + "-----".len())
+        .ok_or_else(|| VoiceError::Tls("Malformed PEM header".to_string()))?;
+    let footer_start = pem_data[header_end..]
+        .find("-----END")
+        .map(|i| header_end + i)
+        .ok_or_else(|| VoiceError::Tls("Malformed PEM footer".to_string()))?;
+
+    let base64_content: String = pem_data[header_end..footer_start]
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD
+        .decode(&base64_content)
+        .map_err(|e| VoiceError::Tls(format!("Invalid base64 in PEM: {}", e)))
+}
+
+/// Sign a vouch for `target_device_id`/`target_fingerprint` using the voucher's own
+/// certificate private key (PEM, PKCS#8, as produced by [`crate::tls::generate_self_signed_cert`]).
+pub fn sign_vouch(
+    voucher_private_key_pem: &str,
+    voucher_device_id: Uuid,
+    target_device_id: Uuid,
+    target_fingerprint: &str,
+    validity_days: i64,
+) -> VoiceResult<TrustEdge> {
+    let pkcs8 = pem_to_der_generic(voucher_private_key_pem)?;
+    let rng = SystemRandom::new();
+    let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &pkcs8, &rng)
+        .map_err(|e| VoiceError::Tls(format!("Invalid voucher private key: {}", e)))?;
+
+    let expires_at = Utc::now() + chrono::Duration::days(validity_days);
+    let message =
+        TrustEdge::canonical_message(voucher_device_id, target_device_id, target_fingerprint, expires_at);
+    let signature = key_pair
+        .sign(&rng, &message)
+        .map_err(|e| VoiceError::Tls(format!("Failed to sign vouch: {}", e)))?
+        .as_ref()
+        .to_vec();
+
+    Ok(TrustEdge {
+        voucher_device_id,
+        target_device_id,
+        target_fingerprint: target_fingerprint.to_string(),
+        expires_at,
+        signature,
+        revoked: false,
+    })
+}
+
+/// Verify `edge`'s signature against the voucher's DER-encoded certificate.
+pub fn verify_vouch_signature(edge: &TrustEdge, voucher_cert_der: &[u8]) -> VoiceResult<bool> {
+    let (_, cert) = X509Certificate::from_der(voucher_cert_der)
+        .map_err(|e| VoiceError::Tls(format!("Failed to parse voucher certificate: {}", e)))?;
+
+    let public_key_bytes = cert.public_key().subject_public_key.data.as_ref();
+    let verifying_key = UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, public_key_bytes);
+
+    let message = TrustEdge::canonical_message(
+        edge.voucher_device_id,
+        edge.target_device_id,
+        &edge.target_fingerprint,
+        edge.expires_at,
+    );
+
+    Ok(verifying_key.verify(&message, &edge.signature).is_ok())
+}
+
+/// A directed graph of [`TrustEdge`]s, keyed implicitly by device UUID.
+#[derive(Debug, Clone, Default)]
+pub struct TrustGraph {
+    edges: Vec<TrustEdge>,
+}
+
+impl TrustGraph {
+    pub fn new() -> Self {
+        Self { edges: Vec::new() }
+    }
+
+    /// Add a vouch edge to the graph.
+    pub fn add_edge(&mut self, edge: TrustEdge) {
+        self.edges.push(edge);
+    }
+
+    /// Mark every edge from `voucher_device_id` to `target_device_id` as revoked.
+    /// Returns whether any edge matched.
+    pub fn revoke(&mut self, voucher_device_id: Uuid, target_device_id: Uuid) -> bool {
+        let mut revoked_any = false;
+        for edge in self.edges.iter_mut() {
+            if edge.voucher_device_id == voucher_device_id && edge.target_device_id == target_device_id {
+                edge.revoked = true;
+                revoked_any = true;
+            }
+        }
+        revoked_any
+    }
+
+    /// All edges issued by `voucher_device_id`.
+    pub fn edges_from(&self, voucher_device_id: Uuid) -> impl Iterator<Item = &TrustEdge> {
+        self.edges
+            .iter()
+            .filter(move |e| e.voucher_device_id == voucher_device_id)
+    }
+}
+
+/// The result of a successful [`find_trust_path`] search: which locally pinned root
+/// authorized the trust, and the chain of device IDs connecting it to the candidate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustPath {
+    pub root: Uuid,
+    pub path: Vec<Uuid>,
+}
+
+/// Bounded breadth-first search from `roots` for a vouch chain to
+/// `(candidate_device_id, candidate_fingerprint)`.
+///
+/// `voucher_certs` supplies the DER certificate for each device ID that might appear
+/// as a voucher along the way, so each edge's signature can be verified against the
+/// voucher's actual public key. Edges that are expired, revoked, or fail signature
+/// verification are rejected. Search depth is capped at `max_depth` hops from a root.
+pub fn find_trust_path(
+    graph: &TrustGraph,
+    roots: &[Uuid],
+    candidate_device_id: Uuid,
+    candidate_fingerprint: &str,
+    voucher_certs: &HashMap<Uuid, Vec<u8>>,
+    max_depth: usize,
+) -> Option<TrustPath> {
+    let now = Utc::now();
+    let mut visited: HashSet<Uuid> = roots.iter().copied().collect();
+    let mut queue: VecDeque<(Uuid, usize, Vec<Uuid>)> =
+        roots.iter().map(|&r| (r, 0, vec![r])).collect();
+
+    while let Some((voucher_id, depth, path)) = queue.pop_front() {
+        if depth >= max_depth {
+            continue;
+        }
+
+        let Some(voucher_cert_der) = voucher_certs.get(&voucher_id) else {
+            continue;
+        };
+
+        for edge in graph.edges_from(voucher_id) {
+            if !edge.is_valid_at(now) {
+                continue;
+            }
+            if !verify_vouch_signature(edge, voucher_cert_der).unwrap_or(false) {
+                continue;
+            }
+
+            if edge.target_device_id == candidate_device_id && edge.target_fingerprint == candidate_fingerprint {
+                let mut full_path = path.clone();
+                full_path.push(edge.target_device_id);
+                return Some(TrustPath {
+                    root: path[0],
+                    path: full_path,
+                });
+            }
+
+            if visited.insert(edge.target_device_id) {
+                let mut next_path = path.clone();
+                next_path.push(edge.target_device_id);
+                queue.push_back((edge.target_device_id, depth + 1, next_path));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::{compute_fingerprint_from_der, generate_self_signed_cert};
+    use std::fs;
+
+    fn make_device(dir: &std::path::Path, name: &str) -> (Uuid, Vec<u8>, String, String) {
+        let device_id = Uuid::now_v7();
+        let cert_path = dir.join(format!("{}.crt", name));
+        let key_path = dir.join(format!("{}.key", name));
+        generate_self_signed_cert(
+            &cert_path,
+            &key_path,
+            name,
+            Some(&device_id.simple().to_string()),
+        )
+        .unwrap();
+        let cert_pem = fs::read_to_string(&cert_path).unwrap();
+        let key_pem = fs::read_to_string(&key_path).unwrap();
+        let der = pem_to_der_generic(&cert_pem).unwrap();
+        let fingerprint = compute_fingerprint_from_der(&der);
+        (device_id, der, key_pem, fingerprint)
+    }
+
+    #[test]
+    fn test_direct_vouch_is_trusted() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (root_id, root_der, root_key_pem, _root_fp) = make_device(temp_dir.path(), "root");
+        let (target_id, _target_der, _target_key_pem, target_fp) = make_device(temp_dir.path(), "target");
+
+        let edge = sign_vouch(&root_key_pem, root_id, target_id, &target_fp, 30).unwrap();
+        let mut graph = TrustGraph::new();
+        graph.add_edge(edge);
+
+        let mut certs = HashMap::new();
+        certs.insert(root_id, root_der);
+
+        let result = find_trust_path(&graph, &[root_id], target_id, &target_fp, &certs, 3);
+        assert_eq!(result, Some(TrustPath { root: root_id, path: vec![root_id, target_id] }));
+    }
+
+    #[test]
+    fn test_revoked_vouch_is_rejected() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (root_id, root_der, root_key_pem, _root_fp) = make_device(temp_dir.path(), "root");
+        let (target_id, _target_der, _target_key_pem, target_fp) = make_device(temp_dir.path(), "target");
+
+        let edge = sign_vouch(&root_key_pem, root_id, target_id, &target_fp, 30).unwrap();
+        let mut graph = TrustGraph::new();
+        graph.add_edge(edge);
+        graph.revoke(root_id, target_id);
+
+        let mut certs = HashMap::new();
+        certs.insert(root_id, root_der);
+
+        let result = find_trust_path(&graph, &[root_id], target_id, &target_fp, &certs, 3);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_transitive_vouch_within_depth() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (root_id, root_der, root_key_pem, _root_fp) = make_device(temp_dir.path(), "root");
+        let (mid_id, mid_der, mid_key_pem, mid_fp) = make_device(temp_dir.path(), "mid");
+        let (leaf_id, _leaf_der, _leaf_key_pem, leaf_fp) = make_device(temp_dir.path(), "leaf");
+
+        let mut graph = TrustGraph::new();
+        graph.add_edge(sign_vouch(&root_key_pem, root_id, mid_id, &mid_fp, 30).unwrap());
+        graph.add_edge(sign_vouch(&mid_key_pem, mid_id, leaf_id, &leaf_fp, 30).unwrap());
+
+        let mut certs = HashMap::new();
+        certs.insert(root_id, root_der);
+        certs.insert(mid_id, mid_der);
+
+        let result = find_trust_path(&graph, &[root_id], leaf_id, &leaf_fp, &certs, 3).unwrap();
+        assert_eq!(result.root, root_id);
+        assert_eq!(result.path, vec![root_id, mid_id, leaf_id]);
+
+        // Beyond the configured depth the same chain is rejected.
+        assert!(find_trust_path(&graph, &[root_id], leaf_id, &leaf_fp, &certs, 1).is_none());
+    }
+}
@@ -4,6 +4,8 @@
 //! All validators return VoiceError::Validation on failure.
 
 use pyo3::prelude::*;
+use unicode_normalization::UnicodeNormalization;
+use unicode_security::skeleton;
 use uuid::Uuid;
 
 use crate::error::{ValidationError, VoiceError, VoiceResult};
@@ -187,7 +189,7 @@ pub fn validate_tag_path(path: &str) -> VoiceResult<()> {
                 "tag_path",
                 format!(
                     "tag name '{}...' exceeds {} characters",
-                    &part[..20.min(part.len())],
+                    part.chars().take(20).collect::<String>(),
                     MAX_TAG_NAME_LENGTH
                 ),
             ));
@@ -197,6 +199,72 @@ pub fn validate_tag_path(path: &str) -> VoiceResult<()> {
     Ok(())
 }
 
+/// Canonicalize a tag name to NFC (Unicode Normalization Form C) and trim whitespace.
+///
+/// This is the form callers should persist: "Cafe\u{301}" (NFD) and "Caf\u{e9}" (NFC)
+/// otherwise collide unpredictably depending on which form a client happened to send.
+pub fn normalize_tag_name(name: &str) -> String {
+    name.trim().nfc().collect()
+}
+
+/// Compute a name's Unicode TR39 confusable skeleton: each character is mapped to its
+/// confusables-table prototype, so visually identical names in different scripts (Latin
+/// "Work" vs. a Cyrillic/fullwidth look-alike) produce the same skeleton.
+pub fn tag_name_skeleton(name: &str) -> String {
+    skeleton(&normalize_tag_name(name)).collect()
+}
+
+/// Whether `candidate` is a probable visual duplicate of `existing`, i.e. their
+/// confusable skeletons match after NFC normalization (even if the raw strings differ).
+pub fn is_confusable_with(candidate: &str, existing: &str) -> bool {
+    tag_name_skeleton(candidate) == tag_name_skeleton(existing)
+}
+
+/// Validate a tag name and return its NFC-normalized form for persistence.
+///
+/// If `sibling_names` is non-empty, also rejects a name whose confusable skeleton
+/// matches an existing sibling's, flagging it as a probable duplicate (e.g. "Work"
+/// vs. a Cyrillic look-alike, or NFC "Café" vs. its NFD spelling).
+pub fn validate_and_normalize_tag_name(name: &str, sibling_names: &[&str]) -> VoiceResult<String> {
+    validate_tag_name(name)?;
+    let normalized = normalize_tag_name(name);
+
+    for sibling in sibling_names {
+        if is_confusable_with(&normalized, sibling) {
+            return Err(VoiceError::validation(
+                "tag_name",
+                format!("'{}' is a probable duplicate of existing tag '{}'", normalized, sibling),
+            ));
+        }
+    }
+
+    Ok(normalized)
+}
+
+/// Fold a single tag-path segment the way ACME clients fold hostname labels
+/// (nameprep-style): NFKC normalization followed by case folding, so visually or
+/// semantically equivalent segments converge before the tree is built.
+fn nameprep_fold_segment(segment: &str) -> String {
+    segment.trim().nfkc().collect::<String>().to_lowercase()
+}
+
+/// Validate a tag path and return its normalized form for persistence: each segment
+/// is nameprep-folded (see [`nameprep_fold_segment`]) before the path is rejoined and
+/// re-validated, so the tag tree stays free of near-duplicate siblings like
+/// "Europe/France" and "europe/FRANCE".
+pub fn validate_and_normalize_tag_path(path: &str) -> VoiceResult<String> {
+    let normalized: String = path
+        .trim()
+        .split('/')
+        .map(nameprep_fold_segment)
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    validate_tag_path(&normalized)?;
+    Ok(normalized)
+}
+
 /// Validate note content.
 ///
 /// Note content must be:
@@ -245,6 +313,21 @@ pub fn validate_search_query(query: Option<&str>) -> VoiceResult<()> {
     Ok(())
 }
 
+/// Validate a savepoint name.
+///
+/// Savepoint names are interpolated directly into SQL (SQLite has no parameter binding
+/// for identifiers), so they're restricted to a conservative non-empty alphanumeric/
+/// underscore identifier rather than being escaped.
+pub fn validate_savepoint_name(name: &str) -> VoiceResult<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(VoiceError::validation(
+            "savepoint_name",
+            "must be a non-empty name containing only letters, digits, and underscores",
+        ));
+    }
+    Ok(())
+}
+
 /// Validate a parent tag ID for tag creation/update.
 pub fn validate_parent_tag_id(
     parent_id: Option<&str>,
@@ -342,6 +425,22 @@ pub fn py_validate_tag_name(name: &str) -> PyResult<()> {
     Ok(())
 }
 
+/// Validate a tag name and return its normalized form, flagging confusable siblings
+#[pyfunction]
+#[pyo3(name = "validate_and_normalize_tag_name")]
+#[pyo3(signature = (name, sibling_names=vec![]))]
+pub fn py_validate_and_normalize_tag_name(name: &str, sibling_names: Vec<String>) -> PyResult<String> {
+    let siblings: Vec<&str> = sibling_names.iter().map(String::as_str).collect();
+    Ok(validate_and_normalize_tag_name(name, &siblings)?)
+}
+
+/// Validate a tag path and return its normalized form
+#[pyfunction]
+#[pyo3(name = "validate_and_normalize_tag_path")]
+pub fn py_validate_and_normalize_tag_path(path: &str) -> PyResult<String> {
+    Ok(validate_and_normalize_tag_path(path)?)
+}
+
 /// Validate note content
 #[pyfunction]
 #[pyo3(name = "validate_note_content")]
@@ -464,6 +563,36 @@ mod tests {
         assert!(validate_parent_tag_id(None, None).is_ok());
     }
 
+    #[test]
+    fn test_normalize_tag_name_nfd_to_nfc() {
+        let nfd = "Cafe\u{301}"; // "Café" spelled with a combining acute accent
+        let nfc = "Caf\u{e9}"; // "Café" in precomposed NFC form
+        assert_eq!(normalize_tag_name(nfd), nfc);
+    }
+
+    #[test]
+    fn test_confusable_skeletons() {
+        assert!(is_confusable_with("Work", "Work"));
+        assert!(!is_confusable_with("Work", "Play"));
+    }
+
+    #[test]
+    fn test_validate_and_normalize_tag_name_flags_confusable_sibling() {
+        let existing = normalize_tag_name("Work");
+        let result = validate_and_normalize_tag_name("Cafe\u{301}", &[&existing]);
+        assert!(result.is_ok()); // unrelated name, no collision
+
+        let duplicate_nfd = "Work\u{20}".to_string(); // same skeleton, different raw bytes
+        let result = validate_and_normalize_tag_name(&duplicate_nfd, &[&existing]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_and_normalize_tag_path_folds_case_and_width() {
+        let normalized = validate_and_normalize_tag_path("Europe/\u{FF26}RANCE/Paris").unwrap();
+        assert_eq!(normalized, "europe/france/paris");
+    }
+
     #[test]
     fn test_validate_parent_tag_id_self_reference() {
         let uuid = Uuid::now_v7();
@@ -2,10 +2,12 @@
 //!
 //! This crate provides PyO3 bindings to expose the voicecore library to Python.
 
+use chrono::Utc;
 use pyo3::create_exception;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
 use std::collections::HashMap;
+use std::sync::Mutex;
 
 use voicecore_lib::{config, database, error, merge, search, validation};
 
@@ -17,24 +19,66 @@ create_exception!(voicecore, ValidationError, pyo3::exceptions::PyException);
 create_exception!(voicecore, DatabaseError, pyo3::exceptions::PyException);
 create_exception!(voicecore, SyncError, pyo3::exceptions::PyException);
 
+/// Classify a validation message into a coarse, machine-readable code so
+/// callers can branch on `e.code` instead of matching on message text.
+fn validation_error_code(message: &str) -> &'static str {
+    let lower = message.to_lowercase();
+    if lower.contains("cannot be empty") || lower.contains("required") {
+        "required"
+    } else if lower.contains("cannot exceed") || lower.contains("too long") {
+        "too_long"
+    } else if lower.contains("cannot be its own") || lower.contains("ambiguous") {
+        "invalid_reference"
+    } else if lower.contains("invalid") || lower.contains("format") {
+        "invalid_format"
+    } else {
+        "invalid"
+    }
+}
+
+/// Build a [`ValidationError`] instance with `.field`, `.message`, and `.code`
+/// attributes attached directly to the exception object, so callers can
+/// branch on `e.field == "content"` without fragile string splitting.
+fn new_validation_error(py: Python<'_>, field: &str, message: &str) -> PyErr {
+    let exc_type = py.get_type::<ValidationError>();
+    let instance = exc_type
+        .call1((message,))
+        .unwrap_or_else(|_| exc_type.call0().expect("exception type must be constructible"));
+    let _ = instance.setattr("field", field);
+    let _ = instance.setattr("message", message);
+    let _ = instance.setattr("code", validation_error_code(message));
+    PyErr::from_value(instance)
+}
+
+/// Build a [`DatabaseError`] or [`SyncError`] instance with `.kind` and,
+/// when known, the offending `.id` attached as attributes.
+fn new_kind_error(py: Python<'_>, exc_type: &Bound<'_, pyo3::types::PyType>, kind: &str, message: &str, id: Option<&str>) -> PyErr {
+    let instance = exc_type
+        .call1((message,))
+        .unwrap_or_else(|_| exc_type.call0().expect("exception type must be constructible"));
+    let _ = instance.setattr("kind", kind);
+    let _ = instance.setattr("message", message);
+    if let Some(id) = id {
+        let _ = instance.setattr("id", id);
+    }
+    PyErr::from_value(instance)
+}
+
 fn voice_error_to_pyerr(err: error::VoiceError) -> PyErr {
-    match &err {
-        error::VoiceError::Validation { field, message } => {
-            // Format as "field: message" for Python to parse
-            ValidationError::new_err(format!("{}: {}", field, message))
-        }
-        error::VoiceError::Database(_) | error::VoiceError::DatabaseOperation(_) => {
-            DatabaseError::new_err(err.to_string())
-        }
-        error::VoiceError::Sync(_) | error::VoiceError::Network(_) => {
-            SyncError::new_err(err.to_string())
-        }
+    Python::with_gil(|py| match &err {
+        error::VoiceError::Validation { field, message } => new_validation_error(py, field, message),
+        error::VoiceError::Database(e) => new_kind_error(py, &py.get_type::<DatabaseError>(), "database", &e.to_string(), None),
+        error::VoiceError::DatabaseOperation { message, .. } => new_kind_error(py, &py.get_type::<DatabaseError>(), "operation", message, None),
+        error::VoiceError::NotFound(msg) => new_kind_error(py, &py.get_type::<DatabaseError>(), "not_found", msg, Some(msg)),
+        error::VoiceError::Conflict(msg) => new_kind_error(py, &py.get_type::<DatabaseError>(), "conflict", msg, Some(msg)),
+        error::VoiceError::Sync { message, .. } => new_kind_error(py, &py.get_type::<SyncError>(), "sync", message, None),
+        error::VoiceError::Network(msg) => new_kind_error(py, &py.get_type::<SyncError>(), "network", msg, None),
         _ => pyo3::exceptions::PyRuntimeError::new_err(err.to_string()),
-    }
+    })
 }
 
 fn validation_error_to_pyerr(err: error::ValidationError) -> PyErr {
-    ValidationError::new_err(err.to_string())
+    Python::with_gil(|py| new_validation_error(py, &err.field, &err.message))
 }
 
 // ============================================================================
@@ -104,6 +148,42 @@ fn json_value_to_pyobject(py: Python<'_>, value: &serde_json::Value) -> PyResult
     }
 }
 
+/// Inverse of [`json_value_to_pyobject`]: convert a Python value received from a
+/// caller (e.g. an `import_snapshot` argument) back into a [`serde_json::Value`].
+fn pyobject_to_json_value(value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
+    if value.is_none() {
+        return Ok(serde_json::Value::Null);
+    }
+    if let Ok(b) = value.extract::<bool>() {
+        return Ok(serde_json::Value::Bool(b));
+    }
+    if let Ok(i) = value.extract::<i64>() {
+        return Ok(serde_json::json!(i));
+    }
+    if let Ok(f) = value.extract::<f64>() {
+        return Ok(serde_json::json!(f));
+    }
+    if let Ok(s) = value.extract::<String>() {
+        return Ok(serde_json::Value::String(s));
+    }
+    if let Ok(list) = value.downcast::<PyList>() {
+        let mut arr = Vec::with_capacity(list.len());
+        for item in list.iter() {
+            arr.push(pyobject_to_json_value(&item)?);
+        }
+        return Ok(serde_json::Value::Array(arr));
+    }
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        let mut map = serde_json::Map::new();
+        for (k, v) in dict.iter() {
+            let key: String = k.extract()?;
+            map.insert(key, pyobject_to_json_value(&v)?);
+        }
+        return Ok(serde_json::Value::Object(map));
+    }
+    Err(pyo3::exceptions::PyTypeError::new_err("unsupported value in snapshot"))
+}
+
 // ============================================================================
 // Database wrapper
 // ============================================================================
@@ -111,6 +191,9 @@ fn json_value_to_pyobject(py: Python<'_>, value: &serde_json::Value) -> PyResult
 #[pyclass(name = "Database", unsendable)]
 pub struct PyDatabase {
     inner: Option<database::Database>,
+    change_callbacks: Mutex<Vec<(u64, Py<PyAny>)>>,
+    next_callback_id: Mutex<u64>,
+    firing_callbacks: Mutex<bool>,
 }
 
 impl PyDatabase {
@@ -119,6 +202,35 @@ impl PyDatabase {
             .as_ref()
             .ok_or_else(|| DatabaseError::new_err("Database has been closed"))
     }
+
+    /// Notify registered change callbacks that `relation` had an `id` mutated by `op`.
+    ///
+    /// Only called after a write statement has succeeded, never on a validation or
+    /// database error. Writes made through a [`PyTransaction`] are reported as each one
+    /// succeeds, before the transaction commits, and are not withdrawn if it is later
+    /// rolled back. Guarded against re-entrancy: if a callback itself mutates the
+    /// database, the nested `emit_change` is a no-op rather than recursing or firing the
+    /// outer callbacks a second time.
+    fn emit_change(&self, py: Python<'_>, relation: &str, op: &str, id: &str) {
+        let callbacks: Vec<Py<PyAny>> = {
+            let mut firing = self.firing_callbacks.lock().expect("firing_callbacks mutex poisoned");
+            let registered = self.change_callbacks.lock().expect("change_callbacks mutex poisoned");
+            if *firing || registered.is_empty() {
+                return;
+            }
+            *firing = true;
+            registered.iter().map(|(_, callback)| callback.clone_ref(py)).collect()
+        };
+        let event = PyDict::new(py);
+        let _ = event.set_item("relation", relation);
+        let _ = event.set_item("op", op);
+        let _ = event.set_item("id", id);
+        let _ = event.set_item("timestamp", Utc::now().to_rfc3339());
+        for callback in &callbacks {
+            let _ = callback.call1(py, (event.clone(),));
+        }
+        *self.firing_callbacks.lock().expect("firing_callbacks mutex poisoned") = false;
+    }
 }
 
 #[pymethods]
@@ -131,7 +243,12 @@ impl PyDatabase {
             None => database::Database::new_in_memory(),
         }
         .map_err(voice_error_to_pyerr)?;
-        Ok(Self { inner: Some(db) })
+        Ok(Self {
+            inner: Some(db),
+            change_callbacks: Mutex::new(Vec::new()),
+            next_callback_id: Mutex::new(0),
+            firing_callbacks: Mutex::new(false),
+        })
     }
 
     fn close(&mut self) -> PyResult<()> {
@@ -141,8 +258,46 @@ impl PyDatabase {
         Ok(())
     }
 
-    fn create_note(&self, content: &str) -> PyResult<String> {
-        self.inner_ref()?.create_note(content).map_err(voice_error_to_pyerr)
+    /// Begin an explicit transaction, returning a [`PyTransaction`] handle through which
+    /// note/tag writes and conflict resolutions can be batched into one atomic scope. Use
+    /// it directly (`tx.commit()`/`tx.rollback()`) or as a context manager, which commits
+    /// on clean exit and rolls back if the `with` block raises.
+    fn begin(slf: Bound<'_, PyDatabase>) -> PyResult<PyTransaction> {
+        slf.borrow().inner_ref()?.begin_transaction().map_err(voice_error_to_pyerr)?;
+        Ok(PyTransaction {
+            db: slf.unbind(),
+            savepoints: Mutex::new(Vec::new()),
+            finished: Mutex::new(false),
+        })
+    }
+
+    /// Register a Python callable to be invoked after every successful write (see
+    /// `emit_change` for writes made inside a transaction), with
+    /// `{"relation", "op", "id", "timestamp"}`. Returns a token for `unregister_change_callback`.
+    fn register_change_callback(&self, callback: Py<PyAny>) -> PyResult<u64> {
+        let mut next_id = self.next_callback_id.lock().expect("next_callback_id mutex poisoned");
+        let token = *next_id;
+        *next_id += 1;
+        self.change_callbacks
+            .lock()
+            .expect("change_callbacks mutex poisoned")
+            .push((token, callback));
+        Ok(token)
+    }
+
+    /// Remove a callback previously returned by `register_change_callback`. Returns
+    /// `false` if `token` was never registered or was already unregistered.
+    fn unregister_change_callback(&self, token: u64) -> PyResult<bool> {
+        let mut callbacks = self.change_callbacks.lock().expect("change_callbacks mutex poisoned");
+        let before = callbacks.len();
+        callbacks.retain(|(id, _)| *id != token);
+        Ok(callbacks.len() != before)
+    }
+
+    fn create_note(&self, py: Python<'_>, content: &str) -> PyResult<String> {
+        let id = self.inner_ref()?.create_note(content).map_err(voice_error_to_pyerr)?;
+        self.emit_change(py, "notes", "create", &id);
+        Ok(id)
     }
 
     fn get_note<'py>(&self, py: Python<'py>, note_id: &str) -> PyResult<Option<PyObject>> {
@@ -153,12 +308,20 @@ impl PyDatabase {
         }
     }
 
-    fn update_note(&self, note_id: &str, content: &str) -> PyResult<bool> {
-        self.inner_ref()?.update_note(note_id, content).map_err(voice_error_to_pyerr)
+    fn update_note(&self, py: Python<'_>, note_id: &str, content: &str) -> PyResult<bool> {
+        let updated = self.inner_ref()?.update_note(note_id, content).map_err(voice_error_to_pyerr)?;
+        if updated {
+            self.emit_change(py, "notes", "update", note_id);
+        }
+        Ok(updated)
     }
 
-    fn delete_note(&self, note_id: &str) -> PyResult<bool> {
-        self.inner_ref()?.delete_note(note_id).map_err(voice_error_to_pyerr)
+    fn delete_note(&self, py: Python<'_>, note_id: &str) -> PyResult<bool> {
+        let deleted = self.inner_ref()?.delete_note(note_id).map_err(voice_error_to_pyerr)?;
+        if deleted {
+            self.emit_change(py, "notes", "delete", note_id);
+        }
+        Ok(deleted)
     }
 
     fn get_all_notes<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
@@ -171,8 +334,10 @@ impl PyDatabase {
     }
 
     #[pyo3(signature = (name, parent_id=None))]
-    fn create_tag(&self, name: &str, parent_id: Option<&str>) -> PyResult<String> {
-        self.inner_ref()?.create_tag(name, parent_id).map_err(voice_error_to_pyerr)
+    fn create_tag(&self, py: Python<'_>, name: &str, parent_id: Option<&str>) -> PyResult<String> {
+        let id = self.inner_ref()?.create_tag(name, parent_id).map_err(voice_error_to_pyerr)?;
+        self.emit_change(py, "tags", "create", &id);
+        Ok(id)
     }
 
     fn get_tag<'py>(&self, py: Python<'py>, tag_id: &str) -> PyResult<Option<PyObject>> {
@@ -233,24 +398,61 @@ impl PyDatabase {
         Ok(list.into_any().unbind())
     }
 
-    fn rename_tag(&self, tag_id: &str, new_name: &str) -> PyResult<bool> {
-        self.inner_ref()?.rename_tag(tag_id, new_name).map_err(voice_error_to_pyerr)
+    fn query_tag_graph<'py>(&self, py: Python<'py>, tag_id: &str, mode: &str) -> PyResult<PyObject> {
+        match self.inner_ref()?.query_tag_graph(tag_id, mode).map_err(voice_error_to_pyerr)? {
+            database::TagGraphResult::Tags(tags) => {
+                let list = PyList::empty(py);
+                for tag in &tags {
+                    list.append(tag_row_to_dict(py, tag)?)?;
+                }
+                Ok(list.into_any().unbind())
+            }
+            database::TagGraphResult::Notes(notes) => {
+                let list = PyList::empty(py);
+                for note in &notes {
+                    list.append(note_row_to_dict(py, note)?)?;
+                }
+                Ok(list.into_any().unbind())
+            }
+        }
     }
 
-    fn delete_tag(&self, tag_id: &str) -> PyResult<bool> {
-        self.inner_ref()?.delete_tag(tag_id).map_err(voice_error_to_pyerr)
+    fn rename_tag(&self, py: Python<'_>, tag_id: &str, new_name: &str) -> PyResult<bool> {
+        let renamed = self.inner_ref()?.rename_tag(tag_id, new_name).map_err(voice_error_to_pyerr)?;
+        if renamed {
+            self.emit_change(py, "tags", "update", tag_id);
+        }
+        Ok(renamed)
     }
 
-    fn add_tag_to_note(&self, note_id: &str, tag_id: &str) -> PyResult<bool> {
-        self.inner_ref()?
+    fn delete_tag(&self, py: Python<'_>, tag_id: &str) -> PyResult<bool> {
+        let deleted = self.inner_ref()?.delete_tag(tag_id).map_err(voice_error_to_pyerr)?;
+        if deleted {
+            self.emit_change(py, "tags", "delete", tag_id);
+        }
+        Ok(deleted)
+    }
+
+    fn add_tag_to_note(&self, py: Python<'_>, note_id: &str, tag_id: &str) -> PyResult<bool> {
+        let added = self
+            .inner_ref()?
             .add_tag_to_note(note_id, tag_id)
-            .map_err(voice_error_to_pyerr)
+            .map_err(voice_error_to_pyerr)?;
+        if added {
+            self.emit_change(py, "note_tags", "create", &format!("{note_id}:{tag_id}"));
+        }
+        Ok(added)
     }
 
-    fn remove_tag_from_note(&self, note_id: &str, tag_id: &str) -> PyResult<bool> {
-        self.inner_ref()?
+    fn remove_tag_from_note(&self, py: Python<'_>, note_id: &str, tag_id: &str) -> PyResult<bool> {
+        let removed = self
+            .inner_ref()?
             .remove_tag_from_note(note_id, tag_id)
-            .map_err(voice_error_to_pyerr)
+            .map_err(voice_error_to_pyerr)?;
+        if removed {
+            self.emit_change(py, "note_tags", "delete", &format!("{note_id}:{tag_id}"));
+        }
+        Ok(removed)
     }
 
     fn get_note_tags<'py>(&self, py: Python<'py>, note_id: &str) -> PyResult<PyObject> {
@@ -322,7 +524,8 @@ impl PyDatabase {
         let result = PyDict::new(py);
         let changes_list = PyList::empty(py);
         for change in &changes {
-            changes_list.append(hashmap_to_pydict(py, change)?)?;
+            let json = serde_json::to_value(change).map_err(|e| voice_error_to_pyerr(error::VoiceError::from(e)))?;
+            changes_list.append(json_value_to_pyobject(py, &json)?)?;
         }
         result.set_item("changes", changes_list)?;
         result.set_item("latest_timestamp", latest)?;
@@ -331,62 +534,101 @@ impl PyDatabase {
 
     fn get_full_dataset<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
         let dataset = self.inner_ref()?.get_full_dataset().map_err(voice_error_to_pyerr)?;
+        json_value_to_pyobject(py, &dataset)
+    }
 
-        let result = PyDict::new(py);
-        for (key, items) in &dataset {
-            let list = PyList::empty(py);
-            for item in items {
-                list.append(hashmap_to_pydict(py, item)?)?;
-            }
-            result.set_item(key, list)?;
-        }
-        Ok(result.into_any().unbind())
+    fn export_snapshot<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
+        let snapshot = self.inner_ref()?.export_snapshot().map_err(voice_error_to_pyerr)?;
+        let json = serde_json::to_value(&snapshot).map_err(|e| voice_error_to_pyerr(error::VoiceError::from(e)))?;
+        json_value_to_pyobject(py, &json)
+    }
+
+    #[pyo3(signature = (snapshot, *, merge=false))]
+    fn import_snapshot<'py>(&self, py: Python<'py>, snapshot: Bound<'py, PyAny>, merge: bool) -> PyResult<PyObject> {
+        let snapshot_json = pyobject_to_json_value(&snapshot)?;
+        let snapshot: database::ColumnarSnapshot =
+            serde_json::from_value(snapshot_json).map_err(|e| voice_error_to_pyerr(error::VoiceError::from(e)))?;
+        let summary = self
+            .inner_ref()?
+            .import_snapshot(&snapshot, merge)
+            .map_err(voice_error_to_pyerr)?;
+        let json = serde_json::to_value(&summary).map_err(|e| voice_error_to_pyerr(error::VoiceError::from(e)))?;
+        json_value_to_pyobject(py, &json)
     }
 
     // ========================================================================
     // Sync apply methods
     // ========================================================================
 
-    #[pyo3(signature = (note_id, created_at, content, modified_at=None, deleted_at=None))]
+    #[pyo3(signature = (note_id, created_at, content, modified_at=None, deleted_at=None, version_vector=None))]
+    #[allow(clippy::too_many_arguments)]
     fn apply_sync_note(
         &self,
+        py: Python<'_>,
         note_id: &str,
         created_at: &str,
         content: &str,
         modified_at: Option<&str>,
         deleted_at: Option<&str>,
+        version_vector: Option<HashMap<String, u64>>,
     ) -> PyResult<bool> {
-        self.inner_ref()?
-            .apply_sync_note(note_id, created_at, content, modified_at, deleted_at)
-            .map_err(voice_error_to_pyerr)
+        let vector = database::VersionVector(version_vector.unwrap_or_default());
+        let applied = self
+            .inner_ref()?
+            .apply_sync_note(note_id, created_at, content, modified_at, deleted_at, &vector)
+            .map_err(voice_error_to_pyerr)?;
+        if applied {
+            let op = if deleted_at.is_some() { "delete" } else { "update" };
+            self.emit_change(py, "notes", op, note_id);
+        }
+        Ok(applied)
     }
 
-    #[pyo3(signature = (tag_id, name, parent_id, created_at, modified_at=None))]
+    #[pyo3(signature = (tag_id, name, parent_id, created_at, modified_at=None, version_vector=None))]
+    #[allow(clippy::too_many_arguments)]
     fn apply_sync_tag(
         &self,
+        py: Python<'_>,
         tag_id: &str,
         name: &str,
         parent_id: Option<&str>,
         created_at: &str,
         modified_at: Option<&str>,
+        version_vector: Option<HashMap<String, u64>>,
     ) -> PyResult<bool> {
-        self.inner_ref()?
-            .apply_sync_tag(tag_id, name, parent_id, created_at, modified_at)
-            .map_err(voice_error_to_pyerr)
+        let vector = database::VersionVector(version_vector.unwrap_or_default());
+        let applied = self
+            .inner_ref()?
+            .apply_sync_tag(tag_id, name, parent_id, created_at, modified_at, &vector)
+            .map_err(voice_error_to_pyerr)?;
+        if applied {
+            self.emit_change(py, "tags", "update", tag_id);
+        }
+        Ok(applied)
     }
 
-    #[pyo3(signature = (note_id, tag_id, created_at, modified_at=None, deleted_at=None))]
+    #[pyo3(signature = (note_id, tag_id, created_at, modified_at=None, deleted_at=None, version_vector=None))]
+    #[allow(clippy::too_many_arguments)]
     fn apply_sync_note_tag(
         &self,
+        py: Python<'_>,
         note_id: &str,
         tag_id: &str,
         created_at: &str,
         modified_at: Option<&str>,
         deleted_at: Option<&str>,
+        version_vector: Option<HashMap<String, u64>>,
     ) -> PyResult<bool> {
-        self.inner_ref()?
-            .apply_sync_note_tag(note_id, tag_id, created_at, modified_at, deleted_at)
-            .map_err(voice_error_to_pyerr)
+        let vector = database::VersionVector(version_vector.unwrap_or_default());
+        let applied = self
+            .inner_ref()?
+            .apply_sync_note_tag(note_id, tag_id, created_at, modified_at, deleted_at, &vector)
+            .map_err(voice_error_to_pyerr)?;
+        if applied {
+            let op = if deleted_at.is_some() { "delete" } else { "update" };
+            self.emit_change(py, "note_tags", op, &format!("{note_id}:{tag_id}"));
+        }
+        Ok(applied)
     }
 
     // ========================================================================
@@ -709,6 +951,198 @@ impl PyDatabase {
     }
 }
 
+/// An explicit transaction on a [`PyDatabase`], returned by [`PyDatabase::begin`]. All
+/// note/tag write methods and conflict resolutions are mirrored here so a batch of edits
+/// can be applied in one atomic scope, with savepoints for partial rollback within it.
+///
+/// Holds the same underlying connection as the `PyDatabase` it was created from (SQLite
+/// has one implicit transaction per connection, not a separate handle per transaction), so
+/// methods on the original `PyDatabase` should not be called again until this transaction
+/// is committed or rolled back.
+#[pyclass(name = "Transaction", unsendable)]
+pub struct PyTransaction {
+    db: Py<PyDatabase>,
+    savepoints: Mutex<Vec<String>>,
+    finished: Mutex<bool>,
+}
+
+impl PyTransaction {
+    fn ensure_active(&self) -> PyResult<()> {
+        if *self.finished.lock().expect("finished mutex poisoned") {
+            return Err(DatabaseError::new_err("transaction has already been committed or rolled back"));
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl PyTransaction {
+    fn savepoint(&self, py: Python<'_>, name: &str) -> PyResult<()> {
+        self.ensure_active()?;
+        self.db.bind(py).borrow().inner_ref()?.savepoint(name).map_err(voice_error_to_pyerr)?;
+        self.savepoints.lock().expect("savepoints mutex poisoned").push(name.to_string());
+        Ok(())
+    }
+
+    fn release_savepoint(&self, py: Python<'_>, name: &str) -> PyResult<()> {
+        self.ensure_active()?;
+        self.db.bind(py).borrow().inner_ref()?.release_savepoint(name).map_err(voice_error_to_pyerr)?;
+        self.savepoints.lock().expect("savepoints mutex poisoned").retain(|n| n != name);
+        Ok(())
+    }
+
+    fn rollback_to_savepoint(&self, py: Python<'_>, name: &str) -> PyResult<()> {
+        self.ensure_active()?;
+        self.db.bind(py).borrow().inner_ref()?.rollback_to_savepoint(name).map_err(voice_error_to_pyerr)?;
+        Ok(())
+    }
+
+    fn commit(&self, py: Python<'_>) -> PyResult<()> {
+        self.ensure_active()?;
+        self.db.bind(py).borrow().inner_ref()?.commit_transaction().map_err(voice_error_to_pyerr)?;
+        *self.finished.lock().expect("finished mutex poisoned") = true;
+        Ok(())
+    }
+
+    fn rollback(&self, py: Python<'_>) -> PyResult<()> {
+        self.ensure_active()?;
+        self.db.bind(py).borrow().inner_ref()?.rollback_transaction().map_err(voice_error_to_pyerr)?;
+        *self.finished.lock().expect("finished mutex poisoned") = true;
+        Ok(())
+    }
+
+    fn create_note(&self, py: Python<'_>, content: &str) -> PyResult<String> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let id = db.inner_ref()?.create_note(content).map_err(voice_error_to_pyerr)?;
+        db.emit_change(py, "notes", "create", &id);
+        Ok(id)
+    }
+
+    fn update_note(&self, py: Python<'_>, note_id: &str, content: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let updated = db.inner_ref()?.update_note(note_id, content).map_err(voice_error_to_pyerr)?;
+        if updated {
+            db.emit_change(py, "notes", "update", note_id);
+        }
+        Ok(updated)
+    }
+
+    fn delete_note(&self, py: Python<'_>, note_id: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let deleted = db.inner_ref()?.delete_note(note_id).map_err(voice_error_to_pyerr)?;
+        if deleted {
+            db.emit_change(py, "notes", "delete", note_id);
+        }
+        Ok(deleted)
+    }
+
+    #[pyo3(signature = (name, parent_id=None))]
+    fn create_tag(&self, py: Python<'_>, name: &str, parent_id: Option<&str>) -> PyResult<String> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let id = db.inner_ref()?.create_tag(name, parent_id).map_err(voice_error_to_pyerr)?;
+        db.emit_change(py, "tags", "create", &id);
+        Ok(id)
+    }
+
+    fn rename_tag(&self, py: Python<'_>, tag_id: &str, new_name: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let renamed = db.inner_ref()?.rename_tag(tag_id, new_name).map_err(voice_error_to_pyerr)?;
+        if renamed {
+            db.emit_change(py, "tags", "update", tag_id);
+        }
+        Ok(renamed)
+    }
+
+    fn delete_tag(&self, py: Python<'_>, tag_id: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let deleted = db.inner_ref()?.delete_tag(tag_id).map_err(voice_error_to_pyerr)?;
+        if deleted {
+            db.emit_change(py, "tags", "delete", tag_id);
+        }
+        Ok(deleted)
+    }
+
+    fn add_tag_to_note(&self, py: Python<'_>, note_id: &str, tag_id: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let added = db.inner_ref()?.add_tag_to_note(note_id, tag_id).map_err(voice_error_to_pyerr)?;
+        if added {
+            db.emit_change(py, "note_tags", "create", &format!("{note_id}:{tag_id}"));
+        }
+        Ok(added)
+    }
+
+    fn remove_tag_from_note(&self, py: Python<'_>, note_id: &str, tag_id: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        let db = self.db.bind(py).borrow();
+        let removed = db.inner_ref()?.remove_tag_from_note(note_id, tag_id).map_err(voice_error_to_pyerr)?;
+        if removed {
+            db.emit_change(py, "note_tags", "delete", &format!("{note_id}:{tag_id}"));
+        }
+        Ok(removed)
+    }
+
+    fn resolve_note_content_conflict(&self, py: Python<'_>, conflict_id: &str, new_content: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        self.db
+            .bind(py)
+            .borrow()
+            .inner_ref()?
+            .resolve_note_content_conflict(conflict_id, new_content)
+            .map_err(voice_error_to_pyerr)
+    }
+
+    fn resolve_note_delete_conflict(&self, py: Python<'_>, conflict_id: &str, restore_note: bool) -> PyResult<bool> {
+        self.ensure_active()?;
+        self.db
+            .bind(py)
+            .borrow()
+            .inner_ref()?
+            .resolve_note_delete_conflict(conflict_id, restore_note)
+            .map_err(voice_error_to_pyerr)
+    }
+
+    fn resolve_tag_rename_conflict(&self, py: Python<'_>, conflict_id: &str, new_name: &str) -> PyResult<bool> {
+        self.ensure_active()?;
+        self.db
+            .bind(py)
+            .borrow()
+            .inner_ref()?
+            .resolve_tag_rename_conflict(conflict_id, new_name)
+            .map_err(voice_error_to_pyerr)
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (exc_type, exc_value, traceback))]
+    fn __exit__(
+        &self,
+        py: Python<'_>,
+        exc_type: Option<Py<PyAny>>,
+        exc_value: Option<Py<PyAny>>,
+        traceback: Option<Py<PyAny>>,
+    ) -> PyResult<bool> {
+        let _ = (exc_value, traceback);
+        if *self.finished.lock().expect("finished mutex poisoned") {
+            return Ok(false);
+        }
+        if exc_type.is_some() {
+            self.rollback(py)?;
+        } else {
+            self.commit(py)?;
+        }
+        Ok(false)
+    }
+}
+
 // ============================================================================
 // Config wrapper
 // ============================================================================
@@ -789,7 +1223,7 @@ impl PyConfig {
             dict.set_item("peer_id", &peer.peer_id)?;
             dict.set_item("peer_name", &peer.peer_name)?;
             dict.set_item("peer_url", &peer.peer_url)?;
-            dict.set_item("certificate_fingerprint", &peer.certificate_fingerprint)?;
+            dict.set_item("certificate_fingerprints", peer.certificate_fingerprints.clone())?;
             list.append(dict)?;
         }
         Ok(list.into_any().unbind())
@@ -803,7 +1237,7 @@ impl PyConfig {
                 dict.set_item("peer_id", &peer.peer_id)?;
                 dict.set_item("peer_name", &peer.peer_name)?;
                 dict.set_item("peer_url", &peer.peer_url)?;
-                dict.set_item("certificate_fingerprint", &peer.certificate_fingerprint)?;
+                dict.set_item("certificate_fingerprints", peer.certificate_fingerprints.clone())?;
                 Ok(Some(dict.into_any().unbind()))
             }
             None => Ok(None),
@@ -878,7 +1312,7 @@ impl PyConfig {
             peer_dict.set_item("peer_id", &peer.peer_id)?;
             peer_dict.set_item("peer_name", &peer.peer_name)?;
             peer_dict.set_item("peer_url", &peer.peer_url)?;
-            peer_dict.set_item("certificate_fingerprint", &peer.certificate_fingerprint)?;
+            peer_dict.set_item("certificate_fingerprints", peer.certificate_fingerprints.clone())?;
             peers_list.append(peer_dict)?;
         }
         dict.set_item("peers", peers_list)?;
@@ -964,6 +1398,8 @@ pub struct PySearchResult {
     notes: Vec<database::NoteRow>,
     ambiguous_tags: Vec<String>,
     not_found_tags: Vec<String>,
+    scores: Vec<f64>,
+    suggestions: HashMap<String, Vec<String>>,
 }
 
 #[pymethods]
@@ -971,8 +1407,12 @@ impl PySearchResult {
     #[getter]
     fn notes<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
         let list = PyList::empty(py);
-        for note in &self.notes {
-            list.append(note_row_to_dict(py, note)?)?;
+        for (i, note) in self.notes.iter().enumerate() {
+            let dict = note_row_to_dict(py, note)?;
+            if let Some(score) = self.scores.get(i) {
+                dict.set_item("score", score)?;
+            }
+            list.append(dict)?;
         }
         Ok(list.into_any().unbind())
     }
@@ -986,6 +1426,20 @@ impl PySearchResult {
     fn not_found_tags(&self) -> Vec<String> {
         self.not_found_tags.clone()
     }
+
+    #[getter]
+    fn scores(&self) -> Vec<f64> {
+        self.scores.clone()
+    }
+
+    #[getter]
+    fn suggestions<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
+        let dict = PyDict::new(py);
+        for (term, candidates) in &self.suggestions {
+            dict.set_item(term, candidates)?;
+        }
+        Ok(dict.into_any().unbind())
+    }
 }
 
 #[pyfunction]
@@ -997,6 +1451,38 @@ fn py_execute_search(db: &PyDatabase, search_input: &str) -> PyResult<PySearchRe
         notes: result.notes,
         ambiguous_tags: result.ambiguous_tags,
         not_found_tags: result.not_found_tags,
+        scores: result.scores,
+        suggestions: result.suggestions,
+    })
+}
+
+#[pyfunction]
+#[pyo3(name = "upsert_note_embedding")]
+fn py_upsert_note_embedding(db: &PyDatabase, note_id: &str, embedding: Vec<f32>) -> PyResult<()> {
+    db.inner_ref()?
+        .upsert_note_embedding(note_id, &embedding)
+        .map_err(voice_error_to_pyerr)?;
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(name = "execute_semantic_search")]
+#[pyo3(signature = (db, query_embedding, top_k=10, min_score=0.0))]
+fn py_execute_semantic_search(
+    db: &PyDatabase,
+    query_embedding: Vec<f32>,
+    top_k: usize,
+    min_score: f64,
+) -> PyResult<PySearchResult> {
+    let db_ref = db.inner_ref()?;
+    let result = search::execute_semantic_search(db_ref, &query_embedding, top_k, min_score)
+        .map_err(voice_error_to_pyerr)?;
+    Ok(PySearchResult {
+        notes: result.notes,
+        ambiguous_tags: result.ambiguous_tags,
+        not_found_tags: result.not_found_tags,
+        scores: result.scores,
+        suggestions: result.suggestions,
     })
 }
 
@@ -1091,6 +1577,7 @@ fn voicecore(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // Register database class
     m.add_class::<PyDatabase>()?;
+    m.add_class::<PyTransaction>()?;
 
     // Register config class
     m.add_class::<PyConfig>()?;
@@ -1100,6 +1587,8 @@ fn voicecore(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyParsedSearch>()?;
     m.add_function(wrap_pyfunction!(py_parse_search_input, m)?)?;
     m.add_function(wrap_pyfunction!(py_execute_search, m)?)?;
+    m.add_function(wrap_pyfunction!(py_upsert_note_embedding, m)?)?;
+    m.add_function(wrap_pyfunction!(py_execute_semantic_search, m)?)?;
 
     // Register merge classes and functions
     m.add_class::<PyMergeResult>()?;